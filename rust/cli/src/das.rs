@@ -0,0 +1,84 @@
+use serde_json::{json, Value};
+use shared::features::bubblegum::cnft_context::CnftContext;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::rpc::RpcClient;
+
+/// Everything needed to prove a license leaf on chain
+pub struct LicenseProof {
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub context: CnftContext,
+    /// Proof nodes above the canopy, passed as remaining accounts
+    pub proof: Vec<Pubkey>,
+}
+
+/// Fetches a compressed license and its proof from a DAS endpoint.
+/// `canopy_depth` nodes are dropped from the end of the proof since the tree stores them on chain.
+pub fn fetch_license_proof(das: &RpcClient, asset_id: &Pubkey, canopy_depth: usize) -> Result<LicenseProof, String> {
+    let asset = das.call("getAsset", json!({ "id": asset_id.to_string() }))?;
+    let asset_proof = das.call("getAssetProof", json!({ "id": asset_id.to_string() }))?;
+
+    let compression = &asset["compression"];
+    if compression["compressed"].as_bool() != Some(true) {
+        return Err(format!("Asset {asset_id} is not a compressed NFT"));
+    }
+
+    let owner = pubkey(&asset["ownership"]["owner"], "ownership.owner")?;
+    // Bubblegum treats a leaf without delegate as delegated to its owner
+    let delegate = match asset["ownership"]["delegate"].as_str() {
+        Some(delegate) => Pubkey::from_str(delegate).map_err(|err| format!("invalid ownership.delegate: {err}"))?,
+        None => owner,
+    };
+
+    let leaf_id = compression["leaf_id"].as_u64().ok_or("getAsset returned no compression.leaf_id")?;
+    let merkle_tree = pubkey(&asset_proof["tree_id"], "tree_id")?;
+
+    let mut proof = asset_proof["proof"]
+        .as_array()
+        .ok_or("getAssetProof returned no proof")?
+        .iter()
+        .map(|node| pubkey(node, "proof node"))
+        .collect::<Result<Vec<_>, _>>()?;
+    proof.truncate(proof.len().saturating_sub(canopy_depth));
+
+    let context = CnftContext {
+        owner,
+        delegate,
+        nonce: leaf_id,
+        index: leaf_id as u32,
+        root: hash(&asset_proof["root"], "root")?,
+        data_hash: hash(&compression["data_hash"], "compression.data_hash")?,
+        creator_hash: hash(&compression["creator_hash"], "compression.creator_hash")?,
+        // V2 only fields, DAS leaves them out for assets without a collection or asset data
+        collection_hash: optional_hash(&compression["collection_hash"])?,
+        asset_data_hash: optional_hash(&compression["asset_data_hash"])?,
+        flags: compression["flags"].as_u64().unwrap_or(0) as u8,
+    };
+
+    Ok(LicenseProof {
+        asset_id: *asset_id,
+        merkle_tree,
+        context,
+        proof,
+    })
+}
+
+fn pubkey(value: &Value, field: &str) -> Result<Pubkey, String> {
+    let encoded = value.as_str().ok_or_else(|| format!("DAS response has no {field}"))?;
+    Pubkey::from_str(encoded).map_err(|err| format!("invalid {field} {encoded}: {err}"))
+}
+
+fn hash(value: &Value, field: &str) -> Result<[u8; 32], String> {
+    let encoded = value.as_str().ok_or_else(|| format!("DAS response has no {field}"))?;
+    let bytes = bs58::decode(encoded.trim()).into_vec().map_err(|err| format!("invalid {field}: {err}"))?;
+    bytes.try_into().map_err(|_| format!("{field} is not 32 bytes"))
+}
+
+fn optional_hash(value: &Value) -> Result<[u8; 32], String> {
+    match value.as_str() {
+        Some(encoded) if !encoded.is_empty() => hash(value, "hash"),
+        _ => Ok([0; 32]),
+    }
+}
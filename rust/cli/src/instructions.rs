@@ -0,0 +1,319 @@
+use borsh::BorshSerialize;
+use checker::input::{ActivateCheckerInput, PayoutCheckerRewardsInput, PayoutMode, SetCheckerRewardBeneficiaryInput};
+use global::input::{ActivateCheckersInput, SetInstantPayoutHaircutInput, SetPauseFlagsInput};
+use shared::{
+    constants::{accounts::BMB_MINT, programs::MPL_ACCOUNT_COMPRESSION_PROGRAM},
+    features::{
+        checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
+        global::accounts::{BMBState, PauseState},
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{LockedTokens, TreasuryAuthority, TreasuryConfig, TreasuryState},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+    },
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_system_interface::program as system_program;
+use spl_associated_token_account::get_associated_token_address;
+use worker::input::{ActivateWorkerInput, SubmitWorkerProofInput};
+
+use crate::das::LicenseProof;
+
+/// Discriminators of the instructions the CLI sends, mirrors `DepinInstruction` and is checked against the IDL
+#[repr(u8)]
+#[derive(Clone, Copy)]
+enum DepinInstruction {
+    SubmitWorkerProof = 1,
+    InitNetwork = 2,
+    ActivateWorker = 3,
+    ActivateCheckerLicenses = 4,
+    ActivateChecker = 6,
+    Unlock = 7,
+    PayoutCheckerRewards = 8,
+    MigrateAccount = 36,
+    SetPauseFlags = 37,
+    SetCheckerRewardBeneficiary = 38,
+    SetInstantPayoutHaircut = 40,
+}
+
+impl DepinInstruction {
+    /// Instructions stopped by a PauseState flag, they take the PauseState PDA as their last account
+    fn is_pausable(self) -> bool {
+        matches!(
+            self,
+            Self::SubmitWorkerProof | Self::ActivateWorker | Self::ActivateChecker | Self::Unlock | Self::PayoutCheckerRewards
+        )
+    }
+}
+
+fn instruction(program_id: &Pubkey, variant: DepinInstruction, input: Option<&impl BorshSerialize>, mut accounts: Vec<AccountMeta>) -> Instruction {
+    if variant.is_pausable() {
+        accounts.push(AccountMeta::new_readonly(PauseState::find_pda(program_id).0, false));
+    }
+
+    let mut data = vec![variant as u8];
+    if let Some(input) = input {
+        // Serializing into a Vec cannot fail
+        input.serialize(&mut data).unwrap();
+    }
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
+
+fn proof_accounts(license: &LicenseProof) -> impl Iterator<Item = AccountMeta> + '_ {
+    license.proof.iter().map(|node| AccountMeta::new_readonly(*node, false))
+}
+
+/// Treasury authority's BMB account that locked rewards are paid from
+pub fn treasury_ata(program_id: &Pubkey) -> Pubkey {
+    let (treasury_authority, _) = TreasuryAuthority::find_pda(program_id);
+    get_associated_token_address(&treasury_authority, &BMB_MINT)
+}
+
+pub fn init_network(program_id: &Pubkey, caller: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*caller, true),
+        AccountMeta::new(GlobalRewards::find_pda(program_id).0, false),
+        AccountMeta::new(TreasuryState::find_pda(program_id).0, false),
+        AccountMeta::new(TreasuryConfig::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    instruction(program_id, DepinInstruction::InitNetwork, None::<&()>, accounts)
+}
+
+pub fn activate_worker(program_id: &Pubkey, owner: &Pubkey, license: LicenseProof, delegated_to: Pubkey, discovery_uri: String) -> Instruction {
+    let (worker_metadata, _) = WorkerMetadata::find_pda(program_id, &license.asset_id, &license.context.owner);
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(worker_metadata, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.merkle_tree, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(proof_accounts(&license));
+
+    let input = ActivateWorkerInput {
+        license_context: license.context,
+        delegated_to,
+        discovery_uri,
+        discovery: None,
+    };
+
+    instruction(program_id, DepinInstruction::ActivateWorker, Some(&input), accounts)
+}
+
+pub fn activate_checker(program_id: &Pubkey, owner: &Pubkey, license: LicenseProof, delegated_to: Pubkey) -> Instruction {
+    let (checker_metadata, _) = CheckerMetadata::find_pda(program_id, &license.asset_id, &license.context.owner);
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(checker_metadata, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.merkle_tree, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(proof_accounts(&license));
+
+    let input = ActivateCheckerInput {
+        license_context: license.context,
+        delegated_to,
+    };
+
+    instruction(program_id, DepinInstruction::ActivateChecker, Some(&input), accounts)
+}
+
+pub struct WorkerProofArgs {
+    pub period: u16,
+    pub proof_root: [u8; 32],
+    pub checkers: [u64; 8],
+    pub uptime: u32,
+    pub latency: u32,
+}
+
+pub fn submit_worker_proof(program_id: &Pubkey, delegate: &Pubkey, license: LicenseProof, args: WorkerProofArgs) -> Instruction {
+    let (worker_proof, _) = WorkerProof::find_pda(program_id, &license.asset_id, args.period);
+    let (worker_metadata, _) = WorkerMetadata::find_pda(program_id, &license.asset_id, &license.context.owner);
+    let (worker_license_metadata, _) = WorkerLicenseMetadata::find_pda(program_id, &license.asset_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(*delegate, true),
+        AccountMeta::new(GlobalRewards::find_pda(program_id).0, false),
+        AccountMeta::new(worker_proof, false),
+        AccountMeta::new_readonly(worker_metadata, false),
+        AccountMeta::new_readonly(worker_license_metadata, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.merkle_tree, false),
+        AccountMeta::new_readonly(BMBState::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(proof_accounts(&license));
+
+    let input = SubmitWorkerProofInput {
+        license_context: license.context,
+        proof_root: args.proof_root,
+        period: args.period,
+        checkers: args.checkers,
+        uptime: args.uptime,
+        latency: args.latency,
+    };
+
+    instruction(program_id, DepinInstruction::SubmitWorkerProof, Some(&input), accounts)
+}
+
+/// `lock_period` and `unlock_period` must match what the program derives from the clock at execution time,
+/// `recipient` is the reward beneficiary of the checker or its owner
+pub fn payout_checker_rewards(
+    program_id: &Pubkey,
+    signer: &Pubkey,
+    license: LicenseProof,
+    mode: PayoutMode,
+    max_haircut_bps: u16,
+    destination: &Pubkey,
+) -> Instruction {
+    let owner = license.context.owner;
+    let (checker_metadata, _) = CheckerMetadata::find_pda(program_id, &license.asset_id, &owner);
+    let (checker_license_metadata, _) = CheckerLicenseMetadata::find_pda(program_id, &license.asset_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(GlobalRewards::find_pda(program_id).0, false),
+        AccountMeta::new(checker_metadata, false),
+        AccountMeta::new_readonly(checker_license_metadata, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.merkle_tree, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(TreasuryState::find_pda(program_id).0, false),
+        AccountMeta::new(treasury_ata(program_id), false),
+        AccountMeta::new_readonly(TreasuryConfig::find_pda(program_id).0, false),
+        // LockedTokens PDA of the reward recipient, or its BMB token account for instant payouts
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(TreasuryAuthority::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend(proof_accounts(&license));
+
+    let input = PayoutCheckerRewardsInput {
+        license_context: license.context,
+        mode,
+        max_haircut_bps,
+    };
+
+    instruction(program_id, DepinInstruction::PayoutCheckerRewards, Some(&input), accounts)
+}
+
+pub fn unlock(program_id: &Pubkey, owner: &Pubkey, locked_tokens: &LockedTokens) -> Instruction {
+    let (locked_tokens_pda, _) = LockedTokens::find_pda(program_id, owner, locked_tokens.lock_period, locked_tokens.unlock_period);
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(TreasuryState::find_pda(program_id).0, false),
+        AccountMeta::new(treasury_ata(program_id), false),
+        AccountMeta::new_readonly(TreasuryAuthority::find_pda(program_id).0, false),
+        AccountMeta::new(locked_tokens_pda, false),
+        AccountMeta::new(get_associated_token_address(owner, &BMB_MINT), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    // `UnlockInput` is a single u16, the treasury crate is still on borsh 0.10 so it is encoded directly
+    instruction(program_id, DepinInstruction::Unlock, Some(&locked_tokens.lock_period), accounts)
+}
+
+pub fn set_checker_reward_beneficiary(program_id: &Pubkey, owner: &Pubkey, license: &Pubkey, beneficiary: Option<Pubkey>) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(CheckerMetadata::find_pda(program_id, license, owner).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    let input = SetCheckerRewardBeneficiaryInput { license: *license, beneficiary };
+
+    instruction(program_id, DepinInstruction::SetCheckerRewardBeneficiary, Some(&input), accounts)
+}
+
+pub fn set_checker_count(program_id: &Pubkey, admin: &Pubkey, period: u16, checker_count: u32) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(BMBState::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    let input = ActivateCheckersInput { period, checker_count };
+
+    instruction(program_id, DepinInstruction::ActivateCheckerLicenses, Some(&input), accounts)
+}
+
+pub fn migrate_account(program_id: &Pubkey, payer: &Pubkey, account: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    instruction(program_id, DepinInstruction::MigrateAccount, None::<&()>, accounts)
+}
+
+pub fn set_pause_flags(program_id: &Pubkey, admin: &Pubkey, flags: u8) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(PauseState::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    instruction(program_id, DepinInstruction::SetPauseFlags, Some(&SetPauseFlagsInput { flags }), accounts)
+}
+
+pub fn set_instant_payout_haircut(program_id: &Pubkey, admin: &Pubkey, haircut_bps: u16) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(TreasuryConfig::find_pda(program_id).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    instruction(program_id, DepinInstruction::SetInstantPayoutHaircut, Some(&SetInstantPayoutHaircutInput { haircut_bps }), accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDL: &str = include_str!("../../idl/depin.json");
+
+    #[test]
+    fn discriminators_match_the_idl() {
+        let idl: serde_json::Value = serde_json::from_str(IDL).unwrap();
+        let discriminant = |name: &str| {
+            idl["instructions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|instruction| instruction["name"] == name)
+                .unwrap_or_else(|| panic!("{name} is not in the IDL"))["discriminant"]["value"]
+                .as_u64()
+        };
+
+        let variants = [
+            ("submitWorkerProof", DepinInstruction::SubmitWorkerProof),
+            ("initNetwork", DepinInstruction::InitNetwork),
+            ("activateWorker", DepinInstruction::ActivateWorker),
+            ("activateCheckerLicenses", DepinInstruction::ActivateCheckerLicenses),
+            ("activateChecker", DepinInstruction::ActivateChecker),
+            ("unlock", DepinInstruction::Unlock),
+            ("payoutCheckerRewards", DepinInstruction::PayoutCheckerRewards),
+            ("migrateAccount", DepinInstruction::MigrateAccount),
+            ("setPauseFlags", DepinInstruction::SetPauseFlags),
+            ("setCheckerRewardBeneficiary", DepinInstruction::SetCheckerRewardBeneficiary),
+            ("setInstantPayoutHaircut", DepinInstruction::SetInstantPayoutHaircut),
+        ];
+        for (name, variant) in variants {
+            assert_eq!(discriminant(name), Some(variant as u64), "{name}");
+        }
+    }
+}
@@ -0,0 +1,443 @@
+mod das;
+mod instructions;
+mod rpc;
+
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use checker::input::PayoutMode;
+use clap::{Parser, Subcommand};
+use shared::{
+    constants::accounts::BMB_MINT,
+    events::DepinEvent,
+    features::{
+        checker::accounts::CheckerMetadata,
+        global::accounts::{BMBState, PauseState},
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{LockedTokens, TreasuryConfig},
+    },
+    types::account::DepinAccountType,
+    utils::{account::read_account_data, bmb::{days_to_periods, timestamp_to_period}},
+};
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{das::fetch_license_proof, instructions::WorkerProofArgs, rpc::RpcClient};
+
+const DEFAULT_PROGRAM_ID: &str = "bmb3KBHjzHzH7ZJ1aNG1v2XpciuX9g89TyRYK7sJXec";
+
+#[derive(Parser)]
+#[command(name = "depin-cli", about = "Operator CLI for the Beamable DePIN program")]
+struct Cli {
+    /// Solana JSON-RPC endpoint
+    #[arg(long, short = 'u', env = "DEPIN_RPC_URL", default_value = "http://127.0.0.1:8899", global = true)]
+    url: String,
+
+    /// Keypair file of the signer, defaults to the Solana CLI keypair
+    #[arg(long, short = 'k', env = "DEPIN_KEYPAIR", global = true)]
+    keypair: Option<PathBuf>,
+
+    #[arg(long, env = "DEPIN_PROGRAM_ID", default_value = DEFAULT_PROGRAM_ID, global = true)]
+    program_id: Pubkey,
+
+    /// DAS endpoint used to fetch cNFT licenses and proofs, defaults to --url
+    #[arg(long, env = "DEPIN_DAS_URL", global = true)]
+    das_url: Option<String>,
+
+    /// Canopy depth of the license trees, the canopy part of the proof is not sent
+    #[arg(long, default_value_t = 0, global = true)]
+    canopy_depth: usize,
+
+    /// Simulate the transaction and print its logs instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create GlobalRewards, TreasuryState and TreasuryConfig
+    InitNetwork,
+    /// Activate a worker license owned by the signer
+    ActivateWorker {
+        license: Pubkey,
+        /// Wallet allowed to submit proofs, defaults to the signer
+        #[arg(long)]
+        delegate: Option<Pubkey>,
+        #[arg(long, default_value = "")]
+        discovery_uri: String,
+    },
+    /// Activate a checker license owned by the signer
+    ActivateChecker {
+        license: Pubkey,
+        /// Wallet allowed to claim payouts, defaults to the signer
+        #[arg(long)]
+        delegate: Option<Pubkey>,
+    },
+    /// Submit a worker proof as the license delegate
+    SubmitProof {
+        license: Pubkey,
+        /// Hex encoded 32 byte proof root
+        #[arg(long)]
+        proof_root: String,
+        /// Period the proof is for, defaults to the previous period
+        #[arg(long)]
+        period: Option<u16>,
+        /// BRAND selection slots (0-511) of the checkers that verified the worker
+        #[arg(long, value_delimiter = ',')]
+        checkers: Vec<u16>,
+        #[arg(long, default_value_t = 0)]
+        uptime: u32,
+        #[arg(long, default_value_t = 0)]
+        latency: u32,
+    },
+    /// Pay out a checker's accumulated rewards as locked tokens, or at once with --instant
+    Payout {
+        license: Pubkey,
+        /// Pay to the recipient's BMB associated token account at once, less the treasury haircut.
+        /// The payout fails if the haircut is above MAX_HAIRCUT_BPS when it executes
+        #[arg(long, value_name = "MAX_HAIRCUT_BPS")]
+        instant: Option<u16>,
+    },
+    /// Lock the payouts of a checker owned by the signer for another wallet
+    SetCheckerBeneficiary {
+        license: Pubkey,
+        /// Wallet that receives the payouts, omit it to pay the owner again
+        beneficiary: Option<Pubkey>,
+    },
+    /// Release a LockedTokens account, with penalty before its unlock period
+    Unlock {
+        /// LockedTokens account address, see list-locks
+        locked_tokens: Pubkey,
+    },
+    /// Record the active checker count from a period on (admin only)
+    SetCheckerCount {
+        checker_count: u32,
+        /// Defaults to the current period
+        #[arg(long)]
+        period: Option<u16>,
+    },
+    /// Print BMBState and the non-zero GlobalRewards balances
+    ShowState,
+    /// List LockedTokens accounts of an owner
+    ListLocks {
+        /// Defaults to the signer
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Rewrite a program account at the current layout version of its type, the signer pays for any growth
+    MigrateAccount { account: Pubkey },
+    /// Set the paused instruction groups (admin only), unpausing a group is applied by a second call after the delay
+    SetPause {
+        /// Groups to keep paused: proofs, payouts, unlocks, escrow, activation or all. Empty unpauses everything
+        #[arg(value_delimiter = ',')]
+        groups: Vec<String>,
+    },
+    /// Set the share of instant payouts kept by the treasury, in basis points (admin only)
+    SetPayoutHaircut { haircut_bps: u16 },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let rpc = RpcClient::new(&cli.url);
+    let das = RpcClient::new(cli.das_url.clone().unwrap_or_else(|| cli.url.clone()));
+    let program_id = cli.program_id;
+
+    let instruction = match &cli.command {
+        Command::ShowState => return show_state(&rpc, &program_id),
+        Command::ListLocks { owner } => {
+            let owner = match owner {
+                Some(owner) => *owner,
+                None => load_keypair(&cli)?.pubkey(),
+            };
+            return list_locks(&rpc, &program_id, &owner);
+        }
+        Command::InitNetwork => {
+            let signer = load_keypair(&cli)?;
+            (instructions::init_network(&program_id, &signer.pubkey()), signer)
+        }
+        Command::ActivateWorker { license, delegate, discovery_uri } => {
+            let signer = load_keypair(&cli)?;
+            let license = fetch_license_proof(&das, license, cli.canopy_depth)?;
+            let delegate = delegate.unwrap_or(signer.pubkey());
+            (instructions::activate_worker(&program_id, &signer.pubkey(), license, delegate, discovery_uri.clone()), signer)
+        }
+        Command::ActivateChecker { license, delegate } => {
+            let signer = load_keypair(&cli)?;
+            let license = fetch_license_proof(&das, license, cli.canopy_depth)?;
+            let delegate = delegate.unwrap_or(signer.pubkey());
+            (instructions::activate_checker(&program_id, &signer.pubkey(), license, delegate), signer)
+        }
+        Command::SubmitProof { license, proof_root, period, checkers, uptime, latency } => {
+            let signer = load_keypair(&cli)?;
+            let license = fetch_license_proof(&das, license, cli.canopy_depth)?;
+            let args = WorkerProofArgs {
+                period: period.unwrap_or_else(|| current_period().saturating_sub(1)),
+                proof_root: parse_hash(proof_root)?,
+                checkers: checker_bitmap(checkers)?,
+                uptime: *uptime,
+                latency: *latency,
+            };
+            (instructions::submit_worker_proof(&program_id, &signer.pubkey(), license, args), signer)
+        }
+        Command::Payout { license, instant } => {
+            let signer = load_keypair(&cli)?;
+            let license = fetch_license_proof(&das, license, cli.canopy_depth)?;
+            let (config_address, _) = TreasuryConfig::find_pda(&program_id);
+            let config_data = rpc.get_account_data(&config_address)?.ok_or("TreasuryConfig does not exist, run init-network first")?;
+            let config: TreasuryConfig = read_account_data(&config_data, DepinAccountType::TreasuryConfig)
+                .map_err(|err| format!("Cannot decode TreasuryConfig: {err}"))?;
+            let lock_period = current_period();
+            let lock_periods = days_to_periods(config.checker_rewards_lock_days).map_err(|err| format!("Cannot convert the lock duration: {err}"))?;
+            let unlock_period = lock_period + lock_periods;
+            let owner = license.context.owner;
+            let (metadata_address, _) = CheckerMetadata::find_pda(&program_id, &license.asset_id, &owner);
+            let metadata_data = rpc.get_account_data(&metadata_address)?.ok_or("CheckerMetadata does not exist, run activate-checker first")?;
+            let metadata: CheckerMetadata = read_account_data(&metadata_data, DepinAccountType::CheckerMetadata)
+                .map_err(|err| format!("Cannot decode CheckerMetadata: {err}"))?;
+            let recipient = metadata.reward_recipient(&owner);
+            let (mode, max_haircut_bps, destination) = match instant {
+                Some(max_haircut_bps) => (PayoutMode::Instant, *max_haircut_bps, get_associated_token_address(&recipient, &BMB_MINT)),
+                None => (PayoutMode::Locked, 0, LockedTokens::find_pda(&program_id, &recipient, lock_period, unlock_period).0),
+            };
+            (instructions::payout_checker_rewards(&program_id, &signer.pubkey(), license, mode, max_haircut_bps, &destination), signer)
+        }
+        Command::SetCheckerBeneficiary { license, beneficiary } => {
+            let signer = load_keypair(&cli)?;
+            (instructions::set_checker_reward_beneficiary(&program_id, &signer.pubkey(), license, *beneficiary), signer)
+        }
+        Command::Unlock { locked_tokens } => {
+            let signer = load_keypair(&cli)?;
+            let data = rpc.get_account_data(locked_tokens)?.ok_or_else(|| format!("Account {locked_tokens} does not exist"))?;
+            let locked: LockedTokens = read_account_data(&data, DepinAccountType::LockedTokens)
+                .map_err(|err| format!("{locked_tokens} is not a LockedTokens account: {err}"))?;
+            if locked.owner != signer.pubkey() {
+                return Err(format!("{locked_tokens} belongs to {}, not the signer", locked.owner));
+            }
+            (instructions::unlock(&program_id, &signer.pubkey(), &locked), signer)
+        }
+        Command::SetCheckerCount { checker_count, period } => {
+            let signer = load_keypair(&cli)?;
+            let period = period.unwrap_or_else(current_period);
+            (instructions::set_checker_count(&program_id, &signer.pubkey(), period, *checker_count), signer)
+        }
+        Command::MigrateAccount { account } => {
+            let signer = load_keypair(&cli)?;
+            (instructions::migrate_account(&program_id, &signer.pubkey(), account), signer)
+        }
+        Command::SetPause { groups } => {
+            let signer = load_keypair(&cli)?;
+            (instructions::set_pause_flags(&program_id, &signer.pubkey(), pause_flags(groups)?), signer)
+        }
+        Command::SetPayoutHaircut { haircut_bps } => {
+            let signer = load_keypair(&cli)?;
+            (instructions::set_instant_payout_haircut(&program_id, &signer.pubkey(), *haircut_bps), signer)
+        }
+    };
+
+    let (instruction, signer) = instruction;
+    execute(&rpc, instruction, &signer, cli.dry_run)
+}
+
+fn execute(rpc: &RpcClient, instruction: Instruction, signer: &Keypair, dry_run: bool) -> Result<(), String> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&signer.pubkey()), &[signer], blockhash);
+
+    if dry_run {
+        let logs = rpc.simulate_transaction(&transaction)?;
+        for log in &logs {
+            println!("{log}");
+        }
+        for event in decode_events(&logs) {
+            println!("Event: {event:?}");
+        }
+        return Ok(());
+    }
+
+    let signature = rpc.send_transaction(&transaction)?;
+    println!("Sent {signature} to {}", rpc.url());
+    Ok(())
+}
+
+fn show_state(rpc: &RpcClient, program_id: &Pubkey) -> Result<(), String> {
+    println!("Current period: {}", current_period());
+
+    let (bmb_state_address, _) = BMBState::find_pda(program_id);
+    match rpc.get_account_data(&bmb_state_address)? {
+        Some(data) => {
+            let state: BMBState = read_account_data(&data, DepinAccountType::BMBState)
+                .map_err(|err| format!("Cannot decode BMBState: {err}"))?;
+            let mut entries = state.get_all_entries();
+            entries.sort_unstable();
+
+            println!("\nBMBState {bmb_state_address}");
+            println!("{:>8}  {:>10}", "period", "checkers");
+            for (period, checker_count) in entries {
+                println!("{period:>8}  {checker_count:>10}");
+            }
+        }
+        None => println!("\nBMBState {bmb_state_address} does not exist"),
+    }
+
+    let (pause_state_address, _) = PauseState::find_pda(program_id);
+    match rpc.get_account_data(&pause_state_address)? {
+        Some(data) => {
+            let state: PauseState = read_account_data(&data, DepinAccountType::PauseState)
+                .map_err(|err| format!("Cannot decode PauseState: {err}"))?;
+            println!("\nPauseState {pause_state_address}: flags {:#04x}", state.flags);
+            if state.unpause_at != 0 {
+                println!("Unpause to {:#04x} can be applied from unix time {}", state.pending_flags, state.unpause_at);
+            }
+        }
+        None => println!("\nPauseState {pause_state_address} does not exist, nothing is paused"),
+    }
+
+    let (global_rewards_address, _) = GlobalRewards::find_pda(program_id);
+    match rpc.get_account_data(&global_rewards_address)? {
+        Some(data) => {
+            if data.len() < GlobalRewards::LEN {
+                return Err(format!("GlobalRewards is {} bytes, expected {}", data.len(), GlobalRewards::LEN));
+            }
+
+            let mut total = 0u64;
+            println!("\nGlobalRewards {global_rewards_address}");
+            println!("{:>8}  {:>10}", "checker", "balance");
+            for checker_index in 0..GlobalRewards::ELEMENTS {
+                let balance = GlobalRewards::read_checker_balance(&data, checker_index)
+                    .map_err(|err| format!("Cannot read checker {checker_index} balance: {err}"))?;
+                if balance > 0 {
+                    total += balance as u64;
+                    println!("{checker_index:>8}  {balance:>10}");
+                }
+            }
+            println!("Total unpaid: {total}");
+        }
+        None => println!("\nGlobalRewards {global_rewards_address} does not exist"),
+    }
+
+    Ok(())
+}
+
+fn list_locks(rpc: &RpcClient, program_id: &Pubkey, owner: &Pubkey) -> Result<(), String> {
+    let accounts = rpc.get_program_accounts(program_id, &[DepinAccountType::LockedTokens as u8])?;
+    let current_period = current_period();
+
+    println!("{:<44}  {:>16}  {:>6}  {:>6}  status", "address", "amount", "locked", "unlock");
+    for (address, data) in accounts {
+        let locked: LockedTokens = match read_account_data(&data, DepinAccountType::LockedTokens) {
+            Ok(locked) => locked,
+            Err(err) => {
+                eprintln!("Skipping {address}: {err}");
+                continue;
+            }
+        };
+        if locked.owner != *owner {
+            continue;
+        }
+
+        let status = match locked.unlocked_at {
+            Some(_) => "unlocked",
+            None if current_period >= locked.unlock_period => "unlockable",
+            None => "locked",
+        };
+        println!(
+            "{:<44}  {:>16}  {:>6}  {:>6}  {status}",
+            address.to_string(),
+            locked.total_locked,
+            locked.lock_period,
+            locked.unlock_period
+        );
+    }
+
+    Ok(())
+}
+
+fn load_keypair(cli: &Cli) -> Result<Keypair, String> {
+    let path = match &cli.keypair {
+        Some(path) => path.clone(),
+        None => {
+            let home = std::env::var("HOME").map_err(|_| "HOME is not set, pass --keypair")?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+
+    read_keypair_file(&path).map_err(|err| format!("Cannot read keypair {}: {err}", path.display()))
+}
+
+fn current_period() -> u16 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("system clock before 1970");
+    timestamp_to_period(now.as_secs() as i64).expect("period exceeds u16::MAX")
+}
+
+fn parse_hash(hex: &str) -> Result<[u8; 32], String> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() != 64 {
+        return Err(format!("Expected 64 hex characters, got {}", hex.len()));
+    }
+
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|err| format!("Invalid hex {hex}: {err}"))?;
+    }
+    Ok(hash)
+}
+
+fn pause_flags(groups: &[String]) -> Result<u8, String> {
+    let mut flags = 0;
+    for group in groups {
+        flags |= match group.as_str() {
+            "proofs" => PauseState::PROOFS,
+            "payouts" => PauseState::PAYOUTS,
+            "unlocks" => PauseState::UNLOCKS,
+            "escrow" => PauseState::ESCROW,
+            "activation" => PauseState::ACTIVATION,
+            "all" => PauseState::ALL,
+            _ => return Err(format!("Unknown pause group {group}, expected proofs, payouts, unlocks, escrow, activation or all")),
+        };
+    }
+    Ok(flags)
+}
+
+fn checker_bitmap(slots: &[u16]) -> Result<[u64; 8], String> {
+    let mut bitmap = [0u64; 8];
+    for &slot in slots {
+        if slot >= 512 {
+            return Err(format!("Checker slot {slot} is out of range 0-511"));
+        }
+        bitmap[slot as usize / 64] |= 1 << (slot % 64);
+    }
+    Ok(bitmap)
+}
+
+fn decode_events(logs: &[String]) -> Vec<DepinEvent> {
+    let fields: Vec<Vec<u8>> = logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .flat_map(|data| data.split(' '))
+        .filter_map(|field| STANDARD.decode(field).ok())
+        .collect();
+
+    DepinEvent::decode_all(fields.iter().map(Vec::as_slice))
+}
+
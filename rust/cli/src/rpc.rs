@@ -0,0 +1,129 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, transaction::Transaction};
+use std::str::FromStr;
+
+/// Minimal JSON-RPC client, covers only the calls the CLI needs
+pub struct RpcClient {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<Value>,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+
+        let response: RpcResponse = ureq::post(&self.url)
+            .send_json(request)
+            .map_err(|err| format!("{method} request failed: {err}"))?
+            .into_json()
+            .map_err(|err| format!("invalid {method} response: {err}"))?;
+
+        if let Some(error) = response.error {
+            return Err(format!("{method} returned an error: {error}"));
+        }
+
+        response.result.ok_or_else(|| format!("{method} returned no result"))
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash, String> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"].as_str().ok_or("getLatestBlockhash returned no blockhash")?;
+        Hash::from_str(blockhash).map_err(|err| format!("invalid blockhash {blockhash}: {err}"))
+    }
+
+    /// Returns None when the account does not exist
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, String> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+
+        if result["value"].is_null() {
+            return Ok(None);
+        }
+
+        decode_data(&result["value"]["data"]).map(Some)
+    }
+
+    /// Fetches program accounts whose data starts with `prefix`
+    pub fn get_program_accounts(&self, program_id: &Pubkey, prefix: &[u8]) -> Result<Vec<(Pubkey, Vec<u8>)>, String> {
+        let result = self.call(
+            "getProgramAccounts",
+            json!([
+                program_id.to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": "confirmed",
+                    "filters": [{ "memcmp": { "offset": 0, "bytes": bs58::encode(prefix).into_string() } }],
+                },
+            ]),
+        )?;
+
+        result
+            .as_array()
+            .ok_or("getProgramAccounts returned no accounts")?
+            .iter()
+            .map(|keyed| {
+                let pubkey = keyed["pubkey"].as_str().ok_or("account without pubkey")?;
+                let address = Pubkey::from_str(pubkey).map_err(|err| format!("invalid pubkey {pubkey}: {err}"))?;
+                Ok((address, decode_data(&keyed["account"]["data"])?))
+            })
+            .collect()
+    }
+
+    /// Simulates without signature verification and returns the program logs
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<Vec<String>, String> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "sigVerify": false, "replaceRecentBlockhash": true, "commitment": "confirmed" },
+            ]),
+        )?;
+
+        let logs: Vec<String> = result["value"]["logs"]
+            .as_array()
+            .map(|logs| logs.iter().filter_map(|log| log.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+
+        if !result["value"]["err"].is_null() {
+            return Err(format!("simulation failed: {}\n{}", result["value"]["err"], logs.join("\n")));
+        }
+
+        Ok(logs)
+    }
+
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<String, String> {
+        let result = self.call(
+            "sendTransaction",
+            json!([encode_transaction(transaction)?, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+        )?;
+
+        result.as_str().map(str::to_string).ok_or_else(|| "sendTransaction returned no signature".to_string())
+    }
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String, String> {
+    let bytes = bincode::serialize(transaction).map_err(|err| format!("cannot serialize transaction: {err}"))?;
+    Ok(STANDARD.encode(bytes))
+}
+
+fn decode_data(data: &Value) -> Result<Vec<u8>, String> {
+    let encoded = data[0].as_str().ok_or("account data is not base64 encoded")?;
+    STANDARD.decode(encoded).map_err(|err| format!("invalid base64 account data: {err}"))
+}
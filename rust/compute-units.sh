@@ -0,0 +1,9 @@
+#!/bin/bash
+# Measures the compute units of the hot instructions on the SBF build of depin and checks them against
+# programs/depin/tests/compute_units.baseline. Set CU_RECORD=1 to rewrite the baseline instead.
+
+set -e
+cd "$(dirname "$0")"
+
+cargo build-sbf --manifest-path programs/depin/Cargo.toml --features test --sbf-out-dir target/deploy
+SBF_OUT_DIR="$PWD/target/deploy" cargo test -p depin --test compute_units -- --ignored --nocapture
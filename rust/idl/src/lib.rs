@@ -0,0 +1,224 @@
+//! Renders the program interface declared in `depin_instructions!` as a Shank-format IDL, which
+//! Codama and the Anchor client generators can read.
+
+use borsh::schema::{BorshSchemaContainer, Declaration, Definition, Fields};
+use depin::idl::{instructions, AccountDef, InstructionArgs, InstructionDef};
+use serde_json::{json, Map, Value};
+use shared::features::global::accounts::PauseState;
+
+/// Path of the committed IDL, relative to this crate
+pub const IDL_PATH: &str = "depin.json";
+
+const PAUSE_GROUPS: [(u8, &str); 5] = [
+    (PauseState::PROOFS, "PROOFS"),
+    (PauseState::PAYOUTS, "PAYOUTS"),
+    (PauseState::UNLOCKS, "UNLOCKS"),
+    (PauseState::ESCROW, "ESCROW"),
+    (PauseState::ACTIVATION, "ACTIVATION"),
+];
+
+/// The IDL of the program as pretty-printed JSON with a trailing newline
+pub fn generate() -> String {
+    let mut types = Types::default();
+    let instructions: Vec<Value> = instructions().iter().map(|instruction| instruction_idl(instruction, &mut types)).collect();
+
+    let idl = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "name": "depin",
+        "instructions": instructions,
+        "accounts": [],
+        "types": types.idl,
+        "metadata": {
+            "origin": "shank",
+            "address": depin::ID.to_string(),
+        },
+    });
+
+    let mut rendered = serde_json::to_string_pretty(&idl).unwrap();
+    rendered.push('\n');
+    rendered
+}
+
+fn instruction_idl(instruction: &InstructionDef, types: &mut Types) -> Value {
+    let mut docs: Vec<String> = instruction.docs.iter().map(|line| line.trim().to_string()).collect();
+    let mut accounts: Vec<Value> = instruction.accounts.iter().map(account_idl).collect();
+
+    if let Some(remaining) = instruction.remaining_accounts {
+        docs.push(format!("Remaining accounts: {remaining}."));
+    }
+
+    if let Some(flag) = instruction.pause_flag {
+        let group = PAUSE_GROUPS.iter().find(|(value, _)| *value == flag).map(|(_, name)| *name).unwrap();
+        docs.push(format!("Stopped by the {group} pause flag, the PauseState PDA is the last account."));
+
+        // The PDA only has a fixed position when no remaining accounts come before it
+        if instruction.remaining_accounts.is_none() {
+            accounts.push(json!({
+                "name": "pauseState",
+                "isMut": false,
+                "isSigner": false,
+                "desc": "PauseState PDA account (may be empty)",
+            }));
+        }
+    }
+
+    let args: Vec<Value> = match &instruction.args {
+        InstructionArgs::Input(container) => {
+            let Some(Definition::Struct { fields: Fields::NamedFields(fields) }) = container.get_definition(container.declaration()) else {
+                panic!("{} input must be a struct with named fields", instruction.name);
+            };
+            fields.iter().map(|(name, declaration)| arg_idl(name, types.idl_type(container, declaration))).collect()
+        }
+        InstructionArgs::Fields(fields) => fields
+            .iter()
+            .map(|(name, container)| arg_idl(name, types.idl_type(container, container.declaration())))
+            .collect(),
+    };
+
+    let mut idl = Map::new();
+    idl.insert("name".into(), camel_case(instruction.name).into());
+    if !docs.is_empty() {
+        idl.insert("docs".into(), docs.into());
+    }
+    idl.insert("accounts".into(), accounts.into());
+    idl.insert("args".into(), args.into());
+    idl.insert("discriminant".into(), json!({ "type": "u8", "value": instruction.discriminant }));
+    idl.into()
+}
+
+fn account_idl(account: &AccountDef) -> Value {
+    let mut idl = Map::new();
+    idl.insert("name".into(), camel_case(account.name).into());
+    idl.insert("isMut".into(), account.is("writable").into());
+    idl.insert("isSigner".into(), account.is("signer").into());
+    if account.is("optional") {
+        idl.insert("isOptional".into(), true.into());
+    }
+    idl.insert("desc".into(), account.description.into());
+    idl.into()
+}
+
+fn arg_idl(name: &str, idl_type: Value) -> Value {
+    json!({ "name": camel_case(name), "type": idl_type })
+}
+
+/// Struct and enum definitions referenced by the arguments, in order of first use
+#[derive(Default)]
+struct Types {
+    idl: Vec<Value>,
+    names: Vec<Declaration>,
+}
+
+impl Types {
+    fn idl_type(&mut self, container: &BorshSchemaContainer, declaration: &Declaration) -> Value {
+        match declaration.as_str() {
+            "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128" | "bool" => {
+                return declaration.as_str().into()
+            }
+            "String" => return "string".into(),
+            "Pubkey" => return "publicKey".into(),
+            _ => {}
+        }
+
+        match container.get_definition(declaration) {
+            Some(Definition::Sequence { length_width: 0, length_range, elements }) if length_range.start() == length_range.end() => {
+                json!({ "array": [self.idl_type(container, elements), length_range.start()] })
+            }
+            Some(Definition::Sequence { length_width: 4, elements, .. }) => {
+                json!({ "vec": self.idl_type(container, elements) })
+            }
+            Some(Definition::Enum { variants, .. }) if declaration.starts_with("Option<") => {
+                let (_, _, some) = variants.iter().find(|(_, name, _)| name == "Some").unwrap();
+                json!({ "option": self.idl_type(container, some) })
+            }
+            Some(definition @ (Definition::Struct { .. } | Definition::Enum { .. })) => {
+                if !self.names.contains(declaration) {
+                    self.names.push(declaration.clone());
+                    let defined = self.defined_type(container, declaration, definition);
+                    self.idl.push(defined);
+                }
+                json!({ "defined": declaration })
+            }
+            definition => panic!("{declaration} has no IDL type: {definition:?}"),
+        }
+    }
+
+    fn defined_type(&mut self, container: &BorshSchemaContainer, declaration: &Declaration, definition: &Definition) -> Value {
+        let kind = match definition {
+            Definition::Struct { fields: Fields::NamedFields(fields) } => {
+                let fields: Vec<Value> = fields.iter().map(|(name, field)| arg_idl(name, self.idl_type(container, field))).collect();
+                json!({ "kind": "struct", "fields": fields })
+            }
+            Definition::Enum { tag_width: 1, variants } => {
+                let variants: Vec<Value> = variants
+                    .iter()
+                    .map(|(_, name, variant)| match container.get_definition(variant) {
+                        Some(Definition::Struct { fields: Fields::Empty }) => json!({ "name": name }),
+                        other => panic!("{declaration}::{name} carries data, only unit variants are supported: {other:?}"),
+                    })
+                    .collect();
+                json!({ "kind": "enum", "variants": variants })
+            }
+            other => panic!("{declaration} has no IDL type: {other:?}"),
+        };
+        json!({ "name": declaration, "type": kind })
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for (index, character) in name.chars().enumerate() {
+        if character == '_' {
+            upper = true;
+        } else if index == 0 {
+            camel.push(character.to_ascii_lowercase());
+        } else if upper {
+            camel.push(character.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(character);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_idl_is_up_to_date() {
+        let committed = std::fs::read_to_string(format!("{}/{IDL_PATH}", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        assert!(
+            committed == generate(),
+            "{IDL_PATH} is out of date with the program, regenerate it with `cargo run -p depin-idl`"
+        );
+    }
+
+    #[test]
+    fn names_are_camel_case() {
+        assert_eq!(camel_case("SubmitWorkerProof"), "submitWorkerProof");
+        assert_eq!(camel_case("proof_accounts_len"), "proofAccountsLen");
+        assert_eq!(camel_case("bmb_state"), "bmbState");
+    }
+
+    #[test]
+    fn fields_follow_the_borsh_layout() {
+        let idl: Value = serde_json::from_str(&generate()).unwrap();
+        let instruction = |name: &str| idl["instructions"].as_array().unwrap().iter().find(|ix| ix["name"] == name).unwrap().clone();
+
+        let submit = instruction("submitWorkerProof");
+        assert_eq!(submit["discriminant"], json!({ "type": "u8", "value": 1 }));
+        assert_eq!(submit["args"][0], json!({ "name": "licenseContext", "type": { "defined": "CnftContext" } }));
+        assert_eq!(submit["args"][3], json!({ "name": "checkers", "type": { "array": ["u64", 8] } }));
+        assert_eq!(submit["accounts"].as_array().unwrap().len(), 9);
+
+        let unlock = instruction("unlock");
+        assert_eq!(unlock["args"], json!([{ "name": "lockPeriod", "type": "u16" }]));
+        assert_eq!(unlock["accounts"].as_array().unwrap().last().unwrap()["name"], "pauseState");
+
+        let register = instruction("registerInfraOffer");
+        assert_eq!(register["args"].as_array().unwrap().last().unwrap()["type"], json!({ "option": { "defined": "SlaTerms" } }));
+    }
+}
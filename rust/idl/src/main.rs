@@ -0,0 +1,7 @@
+//! Writes the IDL of the program to `depin.json` next to this crate's manifest
+
+fn main() {
+    let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), depin_idl::IDL_PATH);
+    std::fs::write(&path, depin_idl::generate()).unwrap_or_else(|error| panic!("Failed to write {path}: {error}"));
+    println!("Wrote {path}");
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+use shared::{
+    features::{
+        checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
+        escrow::accounts::{EscrowAuthority, EscrowState},
+        global::accounts::{AllowedMints, BMBState, PauseState},
+        infra_offer::accounts::InfraOffer,
+        lease::accounts::Lease,
+        payment_stream::accounts::PaymentStream,
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{LockedTokens, TreasuryConfig, TreasuryState},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+    },
+    types::account::{DepinAccount, DepinAccountType},
+    utils::account::{read_account_data, VERSIONED_FLAG},
+};
+use solana_program::program_error::ProgramError;
+
+#[derive(Debug)]
+pub enum DecodedAccount {
+    WorkerMetadata(WorkerMetadata),
+    WorkerLicenseMetadata(WorkerLicenseMetadata),
+    /// Non-zero checker balances as `(checker_index, balance)`
+    GlobalRewards(Vec<(u32, u32)>),
+    WorkerProof(WorkerProof),
+    BMBState(BMBState),
+    CheckerMetadata(CheckerMetadata),
+    CheckerLicenseMetadata(CheckerLicenseMetadata),
+    TreasuryState(TreasuryState),
+    LockedTokens(LockedTokens),
+    TreasuryConfig(TreasuryConfig),
+    InfraOffer(InfraOffer),
+    Lease(Lease),
+    PaymentStream(PaymentStream),
+    AllowedMints(AllowedMints),
+    EscrowAuthority(EscrowAuthority),
+    EscrowState(EscrowState),
+    PauseState(PauseState),
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    Empty,
+    UnknownType(u8),
+    Invalid(DepinAccountType, ProgramError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Empty => write!(f, "account has no data"),
+            DecodeError::UnknownType(discriminator) => write!(f, "unknown account discriminator {discriminator}"),
+            DecodeError::Invalid(account_type, err) => write!(f, "cannot decode {account_type:?}: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decodes program account data by its `DepinAccountType` discriminator, older layout versions are upgraded
+pub fn decode_account(data: &[u8]) -> Result<DecodedAccount, DecodeError> {
+    let discriminator = *data.first().ok_or(DecodeError::Empty)?;
+    let account_type = DepinAccountType::try_from(discriminator & !VERSIONED_FLAG)
+        .map_err(|_| DecodeError::UnknownType(discriminator))?;
+
+    let decoded = match account_type {
+        DepinAccountType::WorkerMetadata => DecodedAccount::WorkerMetadata(read(data, account_type)?),
+        DepinAccountType::WorkerLicenseMetadata => DecodedAccount::WorkerLicenseMetadata(read(data, account_type)?),
+        DepinAccountType::GlobalRewards => DecodedAccount::GlobalRewards(read_global_rewards(data)?),
+        DepinAccountType::WorkerProof => DecodedAccount::WorkerProof(read(data, account_type)?),
+        DepinAccountType::BMBState => DecodedAccount::BMBState(read(data, account_type)?),
+        DepinAccountType::CheckerMetadata => DecodedAccount::CheckerMetadata(read(data, account_type)?),
+        DepinAccountType::CheckerLicenseMetadata => DecodedAccount::CheckerLicenseMetadata(read(data, account_type)?),
+        DepinAccountType::TreasuryState => DecodedAccount::TreasuryState(read(data, account_type)?),
+        DepinAccountType::LockedTokens => DecodedAccount::LockedTokens(read(data, account_type)?),
+        DepinAccountType::TreasuryConfig => DecodedAccount::TreasuryConfig(read(data, account_type)?),
+        DepinAccountType::InfraOffer => DecodedAccount::InfraOffer(read(data, account_type)?),
+        DepinAccountType::Lease => DecodedAccount::Lease(read(data, account_type)?),
+        DepinAccountType::PaymentStream => DecodedAccount::PaymentStream(read(data, account_type)?),
+        DepinAccountType::AllowedMints => DecodedAccount::AllowedMints(read(data, account_type)?),
+        DepinAccountType::EscrowAuthority => DecodedAccount::EscrowAuthority(read(data, account_type)?),
+        DepinAccountType::EscrowState => DecodedAccount::EscrowState(read(data, account_type)?),
+        DepinAccountType::PauseState => DecodedAccount::PauseState(read(data, account_type)?),
+    };
+
+    Ok(decoded)
+}
+
+fn read<T: DepinAccount>(data: &[u8], account_type: DepinAccountType) -> Result<T, DecodeError> {
+    read_account_data(data, account_type).map_err(|err| DecodeError::Invalid(account_type, err))
+}
+
+/// GlobalRewards is too large to deserialize as a whole, balances are read in place like the program does
+fn read_global_rewards(data: &[u8]) -> Result<Vec<(u32, u32)>, DecodeError> {
+    if data.len() < GlobalRewards::LEN {
+        return Err(DecodeError::Invalid(DepinAccountType::GlobalRewards, ProgramError::AccountDataTooSmall));
+    }
+
+    let mut balances = Vec::new();
+    for checker_index in 0..GlobalRewards::ELEMENTS {
+        let balance = GlobalRewards::read_checker_balance(data, checker_index)
+            .map_err(|err| DecodeError::Invalid(DepinAccountType::GlobalRewards, err))?;
+        if balance > 0 {
+            balances.push((checker_index as u32, balance));
+        }
+    }
+
+    Ok(balances)
+}
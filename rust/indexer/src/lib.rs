@@ -0,0 +1,7 @@
+pub mod decode;
+pub mod snapshot;
+pub mod store;
+
+pub use decode::{decode_account, DecodeError, DecodedAccount};
+pub use snapshot::{fetch_program_accounts, load_fixture, AccountSnapshot};
+pub use store::{IndexSummary, Store};
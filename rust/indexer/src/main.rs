@@ -0,0 +1,53 @@
+use std::{env, process::ExitCode, str::FromStr};
+
+use depin_indexer::{fetch_program_accounts, load_fixture, Store};
+use solana_program::pubkey::Pubkey;
+
+const DEFAULT_PROGRAM_ID: &str = "bmb3KBHjzHzH7ZJ1aNG1v2XpciuX9g89TyRYK7sJXec";
+
+const USAGE: &str = "Usage: depin-indexer --db <path> (--rpc <url> [--program <id>] | --fixture <path>)";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut db = None;
+    let mut rpc = None;
+    let mut fixture = None;
+    let mut program = DEFAULT_PROGRAM_ID.to_string();
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("Missing value for {flag}\n{USAGE}"))?;
+        match flag.as_str() {
+            "--db" => db = Some(value),
+            "--rpc" => rpc = Some(value),
+            "--fixture" => fixture = Some(value),
+            "--program" => program = value,
+            _ => return Err(format!("Unknown argument {flag}\n{USAGE}")),
+        }
+    }
+
+    let db = db.ok_or(USAGE)?;
+    let snapshots = match (rpc, fixture) {
+        (Some(rpc), None) => {
+            let program_id = Pubkey::from_str(&program).map_err(|err| format!("Invalid program id {program}: {err}"))?;
+            fetch_program_accounts(&rpc, &program_id)?
+        }
+        (None, Some(fixture)) => load_fixture(fixture)?,
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let mut store = Store::open(&db).map_err(|err| format!("Cannot open {db}: {err}"))?;
+    let summary = store.index(&snapshots).map_err(|err| format!("Indexing failed: {err}"))?;
+
+    println!("Indexed {} accounts into {db}, skipped {}", summary.indexed, summary.skipped);
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::{fs, path::Path, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::json;
+use solana_program::pubkey::Pubkey;
+
+/// Raw program account as returned by `getProgramAccounts`
+#[derive(Debug, Clone)]
+pub struct AccountSnapshot {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct KeyedAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+struct UiAccount {
+    lamports: u64,
+    /// `[data, encoding]`, only base64 is requested and accepted
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Vec<KeyedAccount>>,
+    error: Option<serde_json::Value>,
+}
+
+/// Fetches every account owned by `program_id`
+pub fn fetch_program_accounts(rpc_url: &str, program_id: &Pubkey) -> Result<Vec<AccountSnapshot>, String> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getProgramAccounts",
+        "params": [program_id.to_string(), { "encoding": "base64" }],
+    });
+
+    let response: RpcResponse = ureq::post(rpc_url)
+        .send_json(request)
+        .map_err(|err| format!("getProgramAccounts request failed: {err}"))?
+        .into_json()
+        .map_err(|err| format!("invalid getProgramAccounts response: {err}"))?;
+
+    if let Some(error) = response.error {
+        return Err(format!("getProgramAccounts returned an error: {error}"));
+    }
+
+    parse_keyed_accounts(response.result.unwrap_or_default())
+}
+
+/// Loads a fixture holding the `result` array of a `getProgramAccounts` response
+pub fn load_fixture(path: impl AsRef<Path>) -> Result<Vec<AccountSnapshot>, String> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    let accounts: Vec<KeyedAccount> =
+        serde_json::from_str(&contents).map_err(|err| format!("invalid fixture {}: {err}", path.display()))?;
+
+    parse_keyed_accounts(accounts)
+}
+
+fn parse_keyed_accounts(accounts: Vec<KeyedAccount>) -> Result<Vec<AccountSnapshot>, String> {
+    accounts
+        .into_iter()
+        .map(|keyed| {
+            let address = Pubkey::from_str(&keyed.pubkey).map_err(|err| format!("invalid pubkey {}: {err}", keyed.pubkey))?;
+
+            let (data, encoding) = keyed.account.data;
+            if encoding != "base64" {
+                return Err(format!("account {address} uses unsupported encoding {encoding}"));
+            }
+
+            let data = STANDARD.decode(data).map_err(|err| format!("account {address} has invalid base64 data: {err}"))?;
+
+            Ok(AccountSnapshot {
+                address,
+                lamports: keyed.account.lamports,
+                data,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,422 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection, Transaction};
+use shared::features::infra_offer::accounts::SlaTerms;
+use solana_program::pubkey::Pubkey;
+
+use crate::{decode::DecodedAccount, decode_account, snapshot::AccountSnapshot};
+
+/// One table per account type, keyed by account address unless the account is a list
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS worker_metadata (
+    address TEXT PRIMARY KEY,
+    license TEXT NOT NULL,
+    owner TEXT NOT NULL,
+    delegated_to TEXT NOT NULL,
+    reward_beneficiary TEXT,
+    suspended_at INTEGER,
+    discovery_uri TEXT NOT NULL,
+    region TEXT,
+    runtimes TEXT,
+    capacity_slots INTEGER,
+    protocol_version INTEGER,
+    endpoint TEXT
+);
+CREATE TABLE IF NOT EXISTS worker_license_metadata (
+    address TEXT PRIMARY KEY,
+    suspended_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS global_rewards (
+    checker_index INTEGER PRIMARY KEY,
+    balance INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS worker_proof (
+    address TEXT PRIMARY KEY,
+    period INTEGER NOT NULL,
+    proof_root TEXT NOT NULL,
+    checkers TEXT NOT NULL,
+    uptime INTEGER NOT NULL,
+    latency INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS bmb_state (
+    period INTEGER PRIMARY KEY,
+    checker_count INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS checker_metadata (
+    address TEXT PRIMARY KEY,
+    delegated_to TEXT NOT NULL,
+    reward_beneficiary TEXT,
+    suspended_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS checker_license_metadata (
+    address TEXT PRIMARY KEY,
+    suspended_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS treasury_state (
+    address TEXT PRIMARY KEY,
+    locked_balance INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS locked_tokens (
+    address TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    total_locked INTEGER NOT NULL,
+    lock_period INTEGER NOT NULL,
+    unlock_period INTEGER NOT NULL,
+    unlocked_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS treasury_config (
+    address TEXT PRIMARY KEY,
+    checker_rewards_lock_days INTEGER NOT NULL,
+    instant_payout_haircut_bps INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS infra_offer (
+    address TEXT PRIMARY KEY,
+    provider TEXT NOT NULL,
+    worker_license TEXT NOT NULL,
+    offer_id INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    cpu INTEGER NOT NULL,
+    memory INTEGER NOT NULL,
+    region TEXT NOT NULL,
+    price_mint TEXT NOT NULL,
+    price_per_period INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    sla_min_uptime INTEGER,
+    sla_max_latency INTEGER,
+    sla_penalty_bps INTEGER
+);
+CREATE TABLE IF NOT EXISTS lease (
+    address TEXT PRIMARY KEY,
+    offer TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    worker_license TEXT NOT NULL,
+    buyer TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    lease_id INTEGER NOT NULL,
+    price_per_period INTEGER NOT NULL,
+    start_period INTEGER NOT NULL,
+    periods INTEGER NOT NULL,
+    settled_mask INTEGER NOT NULL,
+    penalized_mask INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    sla_min_uptime INTEGER,
+    sla_max_latency INTEGER,
+    sla_penalty_bps INTEGER
+);
+CREATE TABLE IF NOT EXISTS payment_stream (
+    address TEXT PRIMARY KEY,
+    payer TEXT NOT NULL,
+    worker_license TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    stream_id INTEGER NOT NULL,
+    rate_per_period INTEGER NOT NULL,
+    start_period INTEGER NOT NULL,
+    end_period INTEGER NOT NULL,
+    next_period INTEGER NOT NULL,
+    status TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS allowed_mint (
+    mint TEXT PRIMARY KEY,
+    max_deposit INTEGER NOT NULL,
+    paused INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS escrow_delegate (
+    beneficiary TEXT NOT NULL,
+    delegate TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    spend_limit INTEGER NOT NULL,
+    spent INTEGER NOT NULL,
+    PRIMARY KEY (beneficiary, delegate, mint)
+);
+CREATE TABLE IF NOT EXISTS escrow_state (
+    address TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    encumbered INTEGER NOT NULL,
+    pending_withdrawal INTEGER NOT NULL,
+    unlock_period INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS pause_state (
+    address TEXT PRIMARY KEY,
+    flags INTEGER NOT NULL,
+    pending_flags INTEGER NOT NULL,
+    unpause_at INTEGER NOT NULL
+);
+";
+
+/// Tables rebuilt from a single account on every run, so entries removed on chain disappear here too
+const LIST_TABLES: [&str; 3] = ["global_rewards", "bmb_state", "allowed_mint"];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IndexSummary {
+    pub indexed: usize,
+    /// Accounts that are not depin accounts or could not be decoded
+    pub skipped: usize,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Decodes and stores a full snapshot in one transaction
+    pub fn index(&mut self, snapshots: &[AccountSnapshot]) -> rusqlite::Result<IndexSummary> {
+        let tx = self.conn.transaction()?;
+        let mut summary = IndexSummary::default();
+
+        for snapshot in snapshots {
+            match decode_account(&snapshot.data) {
+                Ok(account) => {
+                    insert(&tx, &snapshot.address, &account)?;
+                    summary.indexed += 1;
+                }
+                Err(err) => {
+                    eprintln!("Skipping {}: {err}", snapshot.address);
+                    summary.skipped += 1;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(summary)
+    }
+}
+
+fn insert(tx: &Transaction, address: &Pubkey, account: &DecodedAccount) -> rusqlite::Result<()> {
+    let address = address.to_string();
+
+    match account {
+        DecodedAccount::WorkerMetadata(metadata) => {
+            let discovery = metadata.discovery.as_ref();
+            tx.execute(
+                "INSERT OR REPLACE INTO worker_metadata VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    address,
+                    metadata.license.to_string(),
+                    metadata.owner.to_string(),
+                    metadata.delegated_to.to_string(),
+                    metadata.reward_beneficiary.map(|beneficiary| beneficiary.to_string()),
+                    metadata.suspended_at,
+                    metadata.discovery_uri,
+                    discovery.map(|discovery| discovery.region.clone()),
+                    discovery.map(|discovery| discovery.runtimes.join(",")),
+                    discovery.map(|discovery| discovery.capacity_slots),
+                    discovery.map(|discovery| discovery.protocol_version),
+                    discovery.map(|discovery| discovery.endpoint.clone()),
+                ],
+            )?;
+        }
+        DecodedAccount::WorkerLicenseMetadata(metadata) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO worker_license_metadata VALUES (?1, ?2)",
+                params![address, metadata.suspended_at],
+            )?;
+        }
+        DecodedAccount::GlobalRewards(balances) => {
+            clear(tx, "global_rewards")?;
+            let mut stmt = tx.prepare("INSERT INTO global_rewards VALUES (?1, ?2)")?;
+            for (checker_index, balance) in balances {
+                stmt.execute(params![checker_index, balance])?;
+            }
+        }
+        DecodedAccount::WorkerProof(proof) => {
+            // The bitmap is kept as 8 hex words so it round-trips without sign issues
+            let checkers: Vec<String> = proof.checkers.iter().map(|word| format!("{word:016x}")).collect();
+            tx.execute(
+                "INSERT OR REPLACE INTO worker_proof VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![address, proof.period, hex(&proof.proof_root), checkers.join(","), proof.uptime, proof.latency],
+            )?;
+        }
+        DecodedAccount::BMBState(state) => {
+            clear(tx, "bmb_state")?;
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO bmb_state VALUES (?1, ?2)")?;
+            for (period, checker_count) in state.get_all_entries() {
+                stmt.execute(params![period, checker_count])?;
+            }
+        }
+        DecodedAccount::CheckerMetadata(metadata) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO checker_metadata VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    address,
+                    metadata.delegated_to.to_string(),
+                    metadata.reward_beneficiary.map(|beneficiary| beneficiary.to_string()),
+                    metadata.suspended_at,
+                ],
+            )?;
+        }
+        DecodedAccount::CheckerLicenseMetadata(metadata) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO checker_license_metadata VALUES (?1, ?2)",
+                params![address, metadata.suspended_at],
+            )?;
+        }
+        DecodedAccount::TreasuryState(state) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO treasury_state VALUES (?1, ?2)",
+                params![address, state.locked_balance],
+            )?;
+        }
+        DecodedAccount::LockedTokens(locked) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO locked_tokens VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    address,
+                    locked.owner.to_string(),
+                    locked.total_locked,
+                    locked.lock_period,
+                    locked.unlock_period,
+                    locked.unlocked_at,
+                ],
+            )?;
+        }
+        DecodedAccount::TreasuryConfig(config) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO treasury_config VALUES (?1, ?2, ?3)",
+                params![address, config.checker_rewards_lock_days, config.instant_payout_haircut_bps],
+            )?;
+        }
+        DecodedAccount::InfraOffer(offer) => {
+            let (min_uptime, max_latency, penalty_bps) = sla_columns(offer.sla.as_ref());
+            tx.execute(
+                "INSERT OR REPLACE INTO infra_offer VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    address,
+                    offer.provider.to_string(),
+                    offer.worker_license.to_string(),
+                    offer.offer_id,
+                    format!("{:?}", offer.status),
+                    offer.cpu,
+                    offer.memory,
+                    offer.region,
+                    offer.price_mint.to_string(),
+                    offer.price_per_period,
+                    offer.name,
+                    offer.description,
+                    min_uptime,
+                    max_latency,
+                    penalty_bps,
+                ],
+            )?;
+        }
+        DecodedAccount::Lease(lease) => {
+            let (min_uptime, max_latency, penalty_bps) = sla_columns(lease.sla.as_ref());
+            tx.execute(
+                "INSERT OR REPLACE INTO lease VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    address,
+                    lease.offer.to_string(),
+                    lease.provider.to_string(),
+                    lease.worker_license.to_string(),
+                    lease.buyer.to_string(),
+                    lease.mint.to_string(),
+                    lease.lease_id,
+                    lease.price_per_period,
+                    lease.start_period,
+                    lease.periods,
+                    // Bitmasks use all 64 bits, stored as their two's complement
+                    lease.settled_mask as i64,
+                    lease.penalized_mask as i64,
+                    format!("{:?}", lease.status),
+                    min_uptime,
+                    max_latency,
+                    penalty_bps,
+                ],
+            )?;
+        }
+        DecodedAccount::PaymentStream(stream) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO payment_stream VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    address,
+                    stream.payer.to_string(),
+                    stream.worker_license.to_string(),
+                    stream.mint.to_string(),
+                    stream.stream_id,
+                    stream.rate_per_period,
+                    stream.start_period,
+                    stream.end_period,
+                    stream.next_period,
+                    format!("{:?}", stream.status),
+                ],
+            )?;
+        }
+        DecodedAccount::AllowedMints(allowed_mints) => {
+            clear(tx, "allowed_mint")?;
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO allowed_mint VALUES (?1, ?2, ?3)")?;
+            for entry in allowed_mints.mints.iter() {
+                stmt.execute(params![entry.mint.to_string(), entry.max_deposit, entry.paused])?;
+            }
+        }
+        DecodedAccount::EscrowAuthority(authority) => {
+            let beneficiary = authority.beneficiary.to_string();
+            tx.execute("DELETE FROM escrow_delegate WHERE beneficiary = ?1", params![beneficiary])?;
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO escrow_delegate VALUES (?1, ?2, ?3, ?4, ?5)")?;
+            for entry in authority.delegates.iter() {
+                stmt.execute(params![
+                    beneficiary,
+                    entry.delegate.to_string(),
+                    entry.mint.to_string(),
+                    entry.spend_limit,
+                    entry.spent,
+                ])?;
+            }
+        }
+        DecodedAccount::EscrowState(state) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO escrow_state VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    address,
+                    state.owner.to_string(),
+                    state.mint.to_string(),
+                    state.encumbered,
+                    state.pending_withdrawal,
+                    state.unlock_period,
+                ],
+            )?;
+        }
+        DecodedAccount::PauseState(state) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO pause_state VALUES (?1, ?2, ?3, ?4)",
+                params![address, state.flags, state.pending_flags, state.unpause_at],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn clear(tx: &Transaction, table: &str) -> rusqlite::Result<()> {
+    debug_assert!(LIST_TABLES.contains(&table));
+    tx.execute(&format!("DELETE FROM {table}"), [])?;
+    Ok(())
+}
+
+fn sla_columns(sla: Option<&SlaTerms>) -> (Option<u32>, Option<u32>, Option<u16>) {
+    match sla {
+        Some(sla) => (Some(sla.min_uptime), Some(sla.max_latency), Some(sla.penalty_bps)),
+        None => (None, None, None),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
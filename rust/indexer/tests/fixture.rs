@@ -0,0 +1,130 @@
+use depin_indexer::{decode_account, load_fixture, AccountSnapshot, DecodeError, DecodedAccount, IndexSummary, Store};
+use shared::{
+    features::{rewards::accounts::GlobalRewards, treasury::accounts::TreasuryConfig},
+    types::account::DepinAccountType,
+};
+use solana_program::pubkey::Pubkey;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/program_accounts.json");
+
+fn indexed_store() -> Store {
+    let snapshots = load_fixture(FIXTURE).unwrap();
+    let mut store = Store::open_in_memory().unwrap();
+    let summary = store.index(&snapshots).unwrap();
+    assert_eq!(summary, IndexSummary { indexed: 6, skipped: 1 });
+    store
+}
+
+#[test]
+fn fixture_accounts_are_decoded_into_their_tables() {
+    let store = indexed_store();
+    let conn = store.connection();
+
+    let (owner, delegated_to, reward_beneficiary, region, runtimes): (String, String, Option<String>, String, String) = conn
+        .query_row("SELECT owner, delegated_to, reward_beneficiary, region, runtimes FROM worker_metadata", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })
+        .unwrap();
+    assert_eq!(owner, Pubkey::new_from_array([2; 32]).to_string());
+    assert_eq!(delegated_to, Pubkey::new_from_array([3; 32]).to_string());
+    // The fixture account predates versioning and reads as paying its owner
+    assert_eq!(reward_beneficiary, None);
+    assert_eq!(region, "eu-west");
+    assert_eq!(runtimes, "wasm,docker");
+
+    let (period, checkers, uptime): (u16, String, u32) = conn
+        .query_row("SELECT period, checkers, uptime FROM worker_proof", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_eq!(period, 120);
+    assert!(checkers.starts_with("000000000000000b,"));
+    assert!(checkers.ends_with(",8000000000000000"));
+    assert_eq!(uptime, 9_950);
+
+    let (total_locked, unlock_period, unlocked_at): (u64, u16, Option<i64>) = conn
+        .query_row("SELECT total_locked, unlock_period, unlocked_at FROM locked_tokens", [], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .unwrap();
+    assert_eq!((total_locked, unlock_period, unlocked_at), (5_000, 465, None));
+
+    let periods: Vec<(u16, u32)> = conn
+        .prepare("SELECT period, checker_count FROM bmb_state ORDER BY period")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(periods, vec![(100, 250), (110, 300)]);
+
+    // The fixture's TreasuryConfig predates the haircut and is read with instant payouts disabled
+    let config: (u16, u16) = conn
+        .query_row("SELECT checker_rewards_lock_days, instant_payout_haircut_bps FROM treasury_config", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(config, (365, TreasuryConfig::INSTANT_PAYOUTS_DISABLED));
+
+    let max_deposit: u64 = conn
+        .query_row("SELECT max_deposit FROM allowed_mint", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(max_deposit, 1_000_000);
+}
+
+#[test]
+fn reindexing_replaces_rows_instead_of_duplicating_them() {
+    let mut store = indexed_store();
+    let snapshots = load_fixture(FIXTURE).unwrap();
+    store.index(&snapshots).unwrap();
+
+    for table in ["worker_metadata", "worker_proof", "locked_tokens", "treasury_config", "allowed_mint"] {
+        let count: u32 = store
+            .connection()
+            .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1, "{table}");
+    }
+
+    let periods: u32 = store.connection().query_row("SELECT COUNT(*) FROM bmb_state", [], |row| row.get(0)).unwrap();
+    assert_eq!(periods, 2);
+}
+
+#[test]
+fn global_rewards_keeps_only_non_zero_balances() {
+    let mut data = vec![0u8; GlobalRewards::LEN];
+    data[0] = DepinAccountType::GlobalRewards as u8;
+    GlobalRewards::add_checker_balance(&mut data, 7, 1_000).unwrap();
+    GlobalRewards::add_checker_balance(&mut data, 99_999, 350).unwrap();
+
+    let mut store = Store::open_in_memory().unwrap();
+    store
+        .index(&[AccountSnapshot { address: Pubkey::new_unique(), lamports: 0, data }])
+        .unwrap();
+
+    let balances: Vec<(u32, u32)> = store
+        .connection()
+        .prepare("SELECT checker_index, balance FROM global_rewards ORDER BY checker_index")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(balances, vec![(7, 1_000), (99_999, 350)]);
+}
+
+#[test]
+fn unknown_and_truncated_accounts_are_rejected() {
+    assert!(matches!(decode_account(&[]), Err(DecodeError::Empty)));
+    assert!(matches!(decode_account(&[0, 1, 2]), Err(DecodeError::UnknownType(0))));
+    assert!(matches!(decode_account(&[200]), Err(DecodeError::UnknownType(200))));
+    assert!(matches!(
+        decode_account(&[DepinAccountType::LockedTokens as u8, 1, 2]),
+        Err(DecodeError::Invalid(DepinAccountType::LockedTokens, _))
+    ));
+    assert!(matches!(
+        decode_account(&[DepinAccountType::TreasuryConfig as u8, 10, 0]),
+        Ok(DecodedAccount::TreasuryConfig(config)) if config.checker_rewards_lock_days == 10
+            && config.instant_payout_haircut_bps == TreasuryConfig::INSTANT_PAYOUTS_DISABLED
+    ));
+}
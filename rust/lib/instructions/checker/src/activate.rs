@@ -7,11 +7,12 @@ use solana_program::{
     msg, 
     program_error::ProgramError, 
     pubkey::Pubkey, 
-    system_instruction, 
     sysvar::{rent::Rent, Sysvar}, 
     program::invoke_signed
 };
+use solana_system_interface::instruction as system_instruction;
 use shared::{
+    events::{emit, DepinEvent},
     features::checker::accounts::CheckerMetadata, types::account::DepinAccountType, utils::{account::{read_account_data, reallocate_account_if_needed, write_account_data}, bgum::verify_license_and_owner, bmb::validate_checker_tree}
 };
 use crate::input;
@@ -78,9 +79,10 @@ pub fn process_activate_checker<'info>(
         return Err(ProgramError::InvalidArgument);
     }
 
-    let metadata = CheckerMetadata {
+    let mut metadata = CheckerMetadata {
         suspended_at: None,
         delegated_to: input.delegated_to,
+        reward_beneficiary: None,
     };
 
     // Check if CheckerMetadata already exists (upsert logic)
@@ -98,6 +100,9 @@ pub fn process_activate_checker<'info>(
             msg!("Error: Checker is currently suspended and cannot be activated");
             return Err(ProgramError::InvalidAccountData);
         }
+
+        // Re-activating keeps the payout wallet the owner chose
+        metadata.reward_beneficiary = existing_metadata.reward_beneficiary;
         
         // Handle account reallocation if needed
         reallocate_account_if_needed(
@@ -140,6 +145,12 @@ pub fn process_activate_checker<'info>(
     let mut data = checker_metadata_account.try_borrow_mut_data()?;
     write_account_data(&mut data, CheckerMetadata::account_type(), &metadata)?;
 
+    emit(&DepinEvent::CheckerActivated {
+        license: leaf_asset_id,
+        owner: license.owner,
+        checker_index: license.index,
+    });
+
     msg!("Checker activated successfully");
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use shared::{
+    events::{emit, DepinEvent},
+    features::checker::accounts::CheckerMetadata,
+    utils::account::{reallocate_account_if_needed, write_account_data},
+};
+use crate::{delegate::load_owned_checker_metadata, input};
+
+pub fn process_set_checker_reward_beneficiary<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Checker license owner (pays for growing metadata written at an older version)
+    // 1. [writable] CheckerMetadata PDA account (must exist)
+    // 2. [readonly] System program account (for the rent transfer)
+    let account_info_iter = &mut accounts.iter();
+    let checker_owner_account = next_account_info(account_info_iter)?;
+    let checker_metadata_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let input = input::SetCheckerRewardBeneficiaryInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_checker_metadata(
+        program_id,
+        checker_owner_account,
+        checker_metadata_account,
+        &input.license,
+    )?;
+
+    // Naming the owner itself is the same as clearing the beneficiary
+    metadata.reward_beneficiary = input.beneficiary.filter(|beneficiary| beneficiary != checker_owner_account.key);
+
+    if checker_metadata_account.data_len() < CheckerMetadata::LEN {
+        reallocate_account_if_needed(
+            checker_owner_account,
+            checker_metadata_account,
+            system_program,
+            &Rent::get()?,
+            CheckerMetadata::LEN,
+        )?;
+    }
+
+    let mut data = checker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, CheckerMetadata::account_type(), &metadata)?;
+
+    emit(&DepinEvent::CheckerRewardBeneficiarySet {
+        license: input.license,
+        owner: *checker_owner_account.key,
+        beneficiary: metadata.reward_beneficiary,
+    });
+
+    match metadata.reward_beneficiary {
+        Some(beneficiary) => msg!("Checker rewards now go to {}", beneficiary),
+        None => msg!("Checker rewards now go to the owner"),
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::checker::accounts::CheckerMetadata,
+    utils::account::{read_account_data, write_account_data},
+};
+use crate::input;
+
+pub fn process_set_checker_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Checker license owner
+    // 1. [writable] CheckerMetadata PDA account (must exist)
+    let account_info_iter = &mut accounts.iter();
+    let checker_owner_account = next_account_info(account_info_iter)?;
+    let checker_metadata_account = next_account_info(account_info_iter)?;
+
+    let input = input::SetCheckerDelegateInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_checker_metadata(
+        program_id,
+        checker_owner_account,
+        checker_metadata_account,
+        &input.license,
+    )?;
+
+    metadata.delegated_to = input.delegated_to;
+
+    let mut data = checker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, CheckerMetadata::account_type(), &metadata)?;
+
+    msg!("Checker delegate set to {}", input.delegated_to);
+    Ok(())
+}
+
+pub fn process_revoke_checker_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Checker license owner
+    // 1. [writable] CheckerMetadata PDA account (must exist)
+    let account_info_iter = &mut accounts.iter();
+    let checker_owner_account = next_account_info(account_info_iter)?;
+    let checker_metadata_account = next_account_info(account_info_iter)?;
+
+    let input = input::RevokeCheckerDelegateInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_checker_metadata(
+        program_id,
+        checker_owner_account,
+        checker_metadata_account,
+        &input.license,
+    )?;
+
+    if metadata.delegated_to == *checker_owner_account.key {
+        msg!("Checker has no delegate to revoke");
+        return Ok(());
+    }
+
+    // Authority returns to the owner, the old delegate can no longer pay out for this checker
+    metadata.delegated_to = *checker_owner_account.key;
+
+    let mut data = checker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, CheckerMetadata::account_type(), &metadata)?;
+
+    msg!("Checker delegate revoked");
+    Ok(())
+}
+
+/// Loads CheckerMetadata after checking it is the PDA of the signing owner.
+/// The PDA is derived from the owner key, so no cNFT proof is needed here:
+/// metadata left behind by a previous owner is never read by payouts.
+pub(crate) fn load_owned_checker_metadata(
+    program_id: &Pubkey,
+    checker_owner_account: &AccountInfo,
+    checker_metadata_account: &AccountInfo,
+    license: &Pubkey,
+) -> Result<CheckerMetadata, ProgramError> {
+    if !checker_owner_account.is_signer {
+        msg!("Error: Checker license owner must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (checker_metadata_pda, _) = CheckerMetadata::find_pda(program_id, license, checker_owner_account.key);
+
+    // Validate CheckerMetadata PDA
+    if *checker_metadata_account.key != checker_metadata_pda {
+        msg!("Error: CheckerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Check that CheckerMetadata account exists
+    if checker_metadata_account.data_is_empty() {
+        msg!("Error: CheckerMetadata account does not exist. Checker must be activated first.");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let metadata: CheckerMetadata = read_account_data(
+        &checker_metadata_account.try_borrow_data()?,
+        CheckerMetadata::account_type(),
+    )?;
+
+    // Check if checker is suspended
+    if metadata.suspended_at.is_some() {
+        msg!("Error: Checker is currently suspended and its metadata cannot be changed");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(metadata)
+}
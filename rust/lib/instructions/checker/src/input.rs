@@ -3,12 +3,68 @@ use shared::features::bubblegum::cnft_context::CnftContext;
 use solana_program::pubkey::Pubkey;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct ActivateCheckerInput {
     pub license_context: CnftContext,
     pub delegated_to: Pubkey
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct PayoutCheckerRewardsInput {
     pub license_context: CnftContext,
-}
\ No newline at end of file
+    pub mode: PayoutMode,
+    /// Highest TreasuryConfig haircut an instant payout accepts, ignored for locked payouts
+    pub max_haircut_bps: u16,
+}
+
+/// How a checker payout reaches the reward recipient
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub enum PayoutMode {
+    /// Into LockedTokens for `checker_rewards_lock_days`
+    Locked,
+    /// Straight to the recipient's BMB token account, less the TreasuryConfig haircut
+    Instant,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct CheckerPayoutBatchEntry {
+    pub license_context: CnftContext,
+    pub proof_accounts_len: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct PayoutCheckerRewardsBatchInput {
+    pub entries: Vec<CheckerPayoutBatchEntry>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct MigrateCheckerMetadataInput {
+    pub license_context: CnftContext,
+    pub previous_owner: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetCheckerDelegateInput {
+    pub license: Pubkey,
+    pub delegated_to: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct RevokeCheckerDelegateInput {
+    pub license: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetCheckerRewardBeneficiaryInput {
+    pub license: Pubkey,
+    /// None sends payouts back to the owner
+    pub beneficiary: Option<Pubkey>,
+}
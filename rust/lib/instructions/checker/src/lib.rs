@@ -1,3 +1,7 @@
 pub mod payout;
+pub mod payout_batch;
 pub mod input;
-pub mod activate;
\ No newline at end of file
+pub mod activate;
+pub mod migrate_metadata;
+pub mod delegate;
+pub mod beneficiary;
\ No newline at end of file
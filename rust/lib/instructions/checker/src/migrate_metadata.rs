@@ -0,0 +1,190 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use shared::{
+    constants::seeds::{CHECKER_SEED, LICENSE_SEED, METADATA_SEED},
+    features::checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
+    utils::{
+        account::{close_account, create_pda_account, read_account_data, reallocate_account_if_needed, write_account_data},
+        bgum::verify_license,
+        bmb::validate_checker_tree,
+    },
+};
+use crate::input;
+
+pub fn process_migrate_checker_metadata<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Payer (permissionless, usually the new license owner)
+    // 1. [writable] Stale CheckerMetadata PDA account of the previous owner (will be closed)
+    // 2. [writable] CheckerMetadata PDA account of the current owner (will be created or updated)
+    // 3. [writable] CheckerLicenseMetadata PDA account (will be created if a suspension must be kept)
+    // 4. [writable] Previous owner account (receives the rent of the stale account)
+    // 5. [readonly] mpl_account_compression program
+    // 6. [readonly] Merkle tree account
+    // 7. [readonly] System program account (for account creation)
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?;
+    let stale_metadata_account = next_account_info(account_info_iter)?;
+    let checker_metadata_account = next_account_info(account_info_iter)?;
+    let checker_license_metadata_account = next_account_info(account_info_iter)?;
+    let previous_owner_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    if !payer_account.is_signer {
+        msg!("Error: Payer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::MigrateCheckerMetadataInput::try_from_slice(instruction_data)?;
+    let license = input.license_context;
+
+    // Calculate the leaf PDA (checker license)
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    // Verify tree and leaf, this proves who the current owner is
+    validate_checker_tree(merkle_tree_account.key)?;
+    verify_license(
+        merkle_tree_account,
+        &proof_accounts,
+        license.root,
+        license_leaf.hash(),
+        license.index,
+    )?;
+
+    if input.previous_owner == license.owner {
+        msg!("Error: License has not changed owner, nothing to migrate");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if *previous_owner_account.key != input.previous_owner {
+        msg!("Error: Previous owner account does not match the stale metadata owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate stale CheckerMetadata PDA
+    let (stale_metadata_pda, _) = CheckerMetadata::find_pda(program_id, &leaf_asset_id, &input.previous_owner);
+    if *stale_metadata_account.key != stale_metadata_pda {
+        msg!("Error: Stale CheckerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if stale_metadata_account.data_is_empty() {
+        msg!("Error: Stale CheckerMetadata account does not exist");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let stale_metadata: CheckerMetadata = read_account_data(
+        &stale_metadata_account.try_borrow_data()?,
+        CheckerMetadata::account_type(),
+    )?;
+
+    // Validate current owner's CheckerMetadata PDA
+    let (checker_metadata_pda, checker_metadata_bump) = CheckerMetadata::find_pda(program_id, &leaf_asset_id, &license.owner);
+    if *checker_metadata_account.key != checker_metadata_pda {
+        msg!("Error: CheckerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate CheckerLicenseMetadata PDA
+    let (checker_license_metadata_pda, checker_license_metadata_bump) = CheckerLicenseMetadata::find_pda(program_id, &leaf_asset_id);
+    if *checker_license_metadata_account.key != checker_license_metadata_pda {
+        msg!("Error: CheckerLicenseMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Move a suspension onto the license itself so it survives any further transfer
+    if let Some(suspended_at) = stale_metadata.suspended_at {
+        let license_metadata = if checker_license_metadata_account.data_is_empty() {
+            create_pda_account(
+                payer_account,
+                checker_license_metadata_account,
+                system_program,
+                program_id,
+                CheckerLicenseMetadata::LEN,
+                &[CHECKER_SEED, LICENSE_SEED, METADATA_SEED, leaf_asset_id.as_ref(), &[checker_license_metadata_bump]],
+            )?;
+            CheckerLicenseMetadata { suspended_at: Some(suspended_at) }
+        } else {
+            let existing: CheckerLicenseMetadata = read_account_data(
+                &checker_license_metadata_account.try_borrow_data()?,
+                CheckerLicenseMetadata::account_type(),
+            )?;
+            CheckerLicenseMetadata { suspended_at: existing.suspended_at.or(Some(suspended_at)) }
+        };
+
+        let mut data = checker_license_metadata_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, CheckerLicenseMetadata::account_type(), &license_metadata)?;
+        msg!("Checker suspension moved to the license");
+    }
+
+    // The previous owner's delegate and reward beneficiary are never carried over, control returns to the new owner
+    let metadata = if checker_metadata_account.data_is_empty() {
+        create_pda_account(
+            payer_account,
+            checker_metadata_account,
+            system_program,
+            program_id,
+            CheckerMetadata::LEN,
+            &[CHECKER_SEED, METADATA_SEED, leaf_asset_id.as_ref(), license.owner.as_ref(), &[checker_metadata_bump]],
+        )?;
+
+        CheckerMetadata {
+            suspended_at: stale_metadata.suspended_at,
+            delegated_to: license.owner,
+            reward_beneficiary: None,
+        }
+    } else {
+        // The new owner already activated, only carry the suspension over
+        let mut existing: CheckerMetadata = read_account_data(
+            &checker_metadata_account.try_borrow_data()?,
+            CheckerMetadata::account_type(),
+        )?;
+        existing.suspended_at = existing.suspended_at.or(stale_metadata.suspended_at);
+
+        // Metadata activated before the current layout version has to grow before it is rewritten
+        if checker_metadata_account.data_len() < CheckerMetadata::LEN {
+            reallocate_account_if_needed(payer_account, checker_metadata_account, system_program, &Rent::get()?, CheckerMetadata::LEN)?;
+        }
+        existing
+    };
+
+    {
+        let mut data = checker_metadata_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, CheckerMetadata::account_type(), &metadata)?;
+    }
+
+    close_account(stale_metadata_account, previous_owner_account)?;
+
+    msg!("Checker metadata migrated from {} to {}", input.previous_owner, license.owner);
+    Ok(())
+}
@@ -8,15 +8,16 @@ use solana_program::{
     pubkey::Pubkey
 };
 use shared::{
+    events::{emit, DepinEvent},
     features::{
         bubblegum::cnft_context::CnftContext,
         checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
         rewards::accounts::GlobalRewards,
-        treasury::{accounts::TreasuryConfig, utils::grant_locked}
+        treasury::{accounts::TreasuryConfig, utils::{grant_locked, pay_instantly}}
     },
-    utils::{account::read_account_data, bgum::verify_license, bmb::validate_checker_tree}
+    utils::{account::read_account_data, bgum::verify_license, bmb::{validate_checker_tree, SysvarClock}}
 };
-use crate::input;
+use crate::input::{self, PayoutMode};
 
 pub fn process_payout_checker_rewards<'info>(
     program_id: &Pubkey,
@@ -28,18 +29,28 @@ pub fn process_payout_checker_rewards<'info>(
     let input = input::PayoutCheckerRewardsInput::try_from_slice(instruction_data)?;
 
     // Validate all preconditions
-    validate_payout_preconditions(program_id, &accounts, &input)?;
+    let checker_metadata = validate_payout_preconditions(program_id, &accounts, &input)?;
+    let recipient = checker_metadata.reward_recipient(&input.license_context.owner);
 
     // Get checker balance and validate it's not zero
     let checker_index = input.license_context.index as usize;
     let payout_amount = get_and_validate_checker_balance(accounts.global_rewards, checker_index)?;
 
     // Execute the payout
-    execute_payout(program_id, &accounts, &input, payout_amount)?;
+    let config = read_treasury_config(program_id, accounts.treasury_config)?;
+    match input.mode {
+        PayoutMode::Locked => execute_payout(program_id, &accounts, &recipient, payout_amount, &config)?,
+        PayoutMode::Instant => execute_instant_payout(program_id, &accounts, &recipient, payout_amount, &config, input.max_haircut_bps)?,
+    }
 
     // Reset balance and log success
     reset_checker_balance(accounts.global_rewards, checker_index)?;
-    msg!("Successfully paid out {} BMB as locked tokens to checker (12-month lock)", payout_amount);
+    emit(&DepinEvent::CheckerRewardsPaid {
+        owner: input.license_context.owner,
+        checker_indices: vec![checker_index as u32],
+        amount: payout_amount,
+    });
+    msg!("Successfully paid out {} BMB of checker rewards ({:?})", payout_amount, input.mode);
 
     Ok(())
 }
@@ -54,7 +65,9 @@ struct PayoutAccounts<'info> {
     treasury_state: &'info AccountInfo<'info>,
     treasury_ata: &'info AccountInfo<'info>,
     treasury_config: &'info AccountInfo<'info>,
-    locked_tokens: &'info AccountInfo<'info>,
+    payout_destination: &'info AccountInfo<'info>,
+    treasury_authority: &'info AccountInfo<'info>,
+    token_program: &'info AccountInfo<'info>,
     proof_accounts: Vec<AccountInfo<'info>>,
 }
 
@@ -70,7 +83,10 @@ fn parse_accounts<'info>(accounts: &'info [AccountInfo<'info>]) -> Result<Payout
     // 7. [writable] TreasuryState PDA account
     // 8. [writable] Treasury ATA account (treasury authority's associated token account)
     // 9. [readonly] TreasuryConfig PDA account
-    // 10. [writable] LockedTokens PDA account (will be created)
+    // 10. [writable] Locked mode: LockedTokens PDA account of the reward recipient (will be created)
+    //     Instant mode: BMB token account of the reward recipient
+    // 11. [readonly] Treasury authority PDA account (signs instant payouts)
+    // 12. [readonly] Token program account (for instant payouts)
     // N. [readonly] Proof accounts as remaining accounts
 
     let mut account_info_iter = accounts.iter();
@@ -84,7 +100,9 @@ fn parse_accounts<'info>(accounts: &'info [AccountInfo<'info>]) -> Result<Payout
     let treasury_state = next_account_info(&mut account_info_iter)?;
     let treasury_ata = next_account_info(&mut account_info_iter)?;
     let treasury_config = next_account_info(&mut account_info_iter)?;
-    let locked_tokens = next_account_info(&mut account_info_iter)?;
+    let payout_destination = next_account_info(&mut account_info_iter)?;
+    let treasury_authority = next_account_info(&mut account_info_iter)?;
+    let token_program = next_account_info(&mut account_info_iter)?;
 
     // Collect remaining accounts as proof accounts
     let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
@@ -105,7 +123,9 @@ fn parse_accounts<'info>(accounts: &'info [AccountInfo<'info>]) -> Result<Payout
         treasury_state,
         treasury_ata,
         treasury_config,
-        locked_tokens,
+        payout_destination,
+        treasury_authority,
+        token_program,
         proof_accounts,
     })
 }
@@ -114,7 +134,7 @@ fn validate_payout_preconditions(
     program_id: &Pubkey,
     accounts: &PayoutAccounts,
     input: &input::PayoutCheckerRewardsInput,
-) -> ProgramResult {
+) -> Result<CheckerMetadata, ProgramError> {
     let license = &input.license_context;
     let leaf_asset_id = get_asset_id(accounts.merkle_tree.key, license.nonce);
 
@@ -122,12 +142,18 @@ fn validate_payout_preconditions(
     validate_license_and_tree(accounts, license, &leaf_asset_id)?;
 
     // Validate all metadata accounts
-    validate_metadata_accounts(program_id, accounts, &leaf_asset_id, &license.owner)?;
+    let checker_metadata = validate_metadata_accounts(program_id, accounts, &leaf_asset_id, &license.owner)?;
+
+    // The haircut comes out of the owner's rewards, so a delegate can only lock them
+    if input.mode == PayoutMode::Instant && *accounts.signer.key != license.owner {
+        msg!("Error: Only the license owner can take an instant payout");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
     // Validate global rewards account
     validate_global_rewards_account(program_id, accounts.global_rewards)?;
 
-    Ok(())
+    Ok(checker_metadata)
 }
 
 fn validate_license_and_tree(
@@ -168,9 +194,9 @@ fn validate_metadata_accounts(
     accounts: &PayoutAccounts,
     leaf_asset_id: &Pubkey,
     license_owner: &Pubkey,
-) -> ProgramResult {
+) -> Result<CheckerMetadata, ProgramError> {
     // Validate checker metadata and authorization (owner or delegate)
-    validate_checker_metadata_and_authorization(
+    let checker_metadata = validate_checker_metadata_and_authorization(
         program_id,
         accounts.checker_metadata,
         accounts.signer,
@@ -185,10 +211,10 @@ fn validate_metadata_accounts(
         leaf_asset_id,
     )?;
 
-    Ok(())
+    Ok(checker_metadata)
 }
 
-fn validate_global_rewards_account(program_id: &Pubkey, global_rewards_account: &AccountInfo) -> ProgramResult {
+pub(crate) fn validate_global_rewards_account(program_id: &Pubkey, global_rewards_account: &AccountInfo) -> ProgramResult {
     let (global_rewards_pda, _) = GlobalRewards::find_pda(program_id);
     if global_rewards_account.key != &global_rewards_pda {
         msg!("Error: Global rewards account does not match expected PDA");
@@ -217,55 +243,98 @@ fn get_and_validate_checker_balance(global_rewards_account: &AccountInfo, checke
 fn execute_payout(
     program_id: &Pubkey,
     accounts: &PayoutAccounts,
-    input: &input::PayoutCheckerRewardsInput,
+    recipient: &Pubkey,
     payout_amount: u64,
+    config: &TreasuryConfig,
 ) -> ProgramResult {
-    // Read lock duration days from TreasuryConfig
-    let (treasury_config_pda, _) = TreasuryConfig::find_pda(program_id);
-    if accounts.treasury_config.key != &treasury_config_pda {
-        msg!("Error: TreasuryConfig account does not match expected PDA");
-        return Err(ProgramError::InvalidArgument);
+    grant_locked(
+        program_id,
+        accounts.signer, // payer
+        accounts.treasury_state,
+        accounts.treasury_ata,
+        accounts.payout_destination,
+        accounts.system_program,
+        recipient,
+        payout_amount,
+        config.checker_rewards_lock_days,
+        &SysvarClock,
+    )?;
+
+    Ok(())
+}
+
+fn execute_instant_payout(
+    program_id: &Pubkey,
+    accounts: &PayoutAccounts,
+    recipient: &Pubkey,
+    payout_amount: u64,
+    config: &TreasuryConfig,
+    max_haircut_bps: u16,
+) -> ProgramResult {
+    if !config.instant_payouts_enabled() {
+        msg!("Error: Instant payouts are disabled until the admin sets a haircut");
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    if accounts.treasury_config.data_is_empty() {
-        msg!("Error: TreasuryConfig account is not initialized");
-        return Err(ProgramError::UninitializedAccount);
+    // The admin can change the haircut between signing and execution
+    if config.instant_payout_haircut_bps > max_haircut_bps {
+        msg!(
+            "Error: Instant payout haircut of {} bps exceeds the accepted {} bps",
+            config.instant_payout_haircut_bps,
+            max_haircut_bps
+        );
+        return Err(ProgramError::InvalidArgument);
     }
 
-    let config: TreasuryConfig = read_account_data(
-        &accounts.treasury_config.try_borrow_data()?,
-        TreasuryConfig::account_type(),
-    )?;
-    let lock_duration_days: u16 = config.checker_rewards_lock_days;
+    let (instant_amount, haircut_amount) = config.instant_payout_split(payout_amount);
 
-    grant_locked(
+    pay_instantly(
         program_id,
-        accounts.signer, // payer
         accounts.treasury_state,
         accounts.treasury_ata,
-        accounts.locked_tokens,
-        accounts.system_program,
-        &input.license_context.owner,
-        payout_amount,
-        lock_duration_days,
+        accounts.treasury_authority,
+        accounts.payout_destination,
+        accounts.token_program,
+        recipient,
+        instant_amount,
+        haircut_amount,
     )?;
 
     Ok(())
 }
 
+/// Reads TreasuryConfig, which holds the lock duration and instant payout haircut of checker rewards
+pub(crate) fn read_treasury_config(program_id: &Pubkey, treasury_config_account: &AccountInfo) -> Result<TreasuryConfig, ProgramError> {
+    let (treasury_config_pda, _) = TreasuryConfig::find_pda(program_id);
+    if treasury_config_account.key != &treasury_config_pda {
+        msg!("Error: TreasuryConfig account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if treasury_config_account.data_is_empty() {
+        msg!("Error: TreasuryConfig account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    read_account_data(
+        &treasury_config_account.try_borrow_data()?,
+        TreasuryConfig::account_type(),
+    )
+}
+
 fn reset_checker_balance(global_rewards_account: &AccountInfo, checker_index: usize) -> ProgramResult {
     let mut global_rewards_data = global_rewards_account.try_borrow_mut_data()?;
     GlobalRewards::reset_checker_balance(&mut global_rewards_data, checker_index)?;
     Ok(())
 }
 
-fn validate_checker_metadata_and_authorization(
+pub(crate) fn validate_checker_metadata_and_authorization(
     program_id: &Pubkey,
     checker_metadata_account: &AccountInfo,
     signer_account: &AccountInfo,
     leaf_asset_id: &Pubkey,
     license_owner: &Pubkey,
-) -> ProgramResult {
+) -> Result<CheckerMetadata, ProgramError> {
     // Calculate expected CheckerMetadata PDA
     let (checker_metadata_pda, _) =
         CheckerMetadata::find_pda(program_id, leaf_asset_id, license_owner);
@@ -294,10 +363,10 @@ fn validate_checker_metadata_and_authorization(
         msg!("Error: Transaction signer is not authorized to payout rewards for this checker");
         return Err(ProgramError::MissingRequiredSignature);
     }
-    Ok(())
+    Ok(checker_metadata)
 }
 
-fn validate_checker_license_metadata(
+pub(crate) fn validate_checker_license_metadata(
     program_id: &Pubkey,
     checker_license_metadata_account: &AccountInfo,
     leaf_asset_id: &Pubkey
@@ -0,0 +1,215 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::{types::LeafSchema, utils::get_asset_id};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey
+};
+use shared::{
+    events::{emit, DepinEvent},
+    features::{rewards::accounts::GlobalRewardsView, treasury::utils::grant_locked},
+    utils::{
+        bgum::verify_license,
+        bmb::{validate_checker_tree, SysvarClock},
+        zero_copy::{load_account, load_account_mut},
+    }
+};
+use crate::{
+    input,
+    payout::{
+        read_treasury_config, validate_checker_license_metadata,
+        validate_checker_metadata_and_authorization, validate_global_rewards_account,
+    },
+};
+
+/// Upper bound on licenses per batch, transaction size caps it well before this in practice
+pub const MAX_CHECKER_PAYOUT_BATCH: usize = 32;
+
+const FIXED_ACCOUNTS: usize = 9;
+const ACCOUNTS_PER_ENTRY: usize = 2;
+
+pub fn process_payout_checker_rewards_batch<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Signer (owner of all licenses, or delegate of every license in the batch)
+    // 1. [writable] Global rewards account
+    // 2. [readonly] mpl_account_compression program
+    // 3. [readonly] Merkle tree account
+    // 4. [readonly] System program account (for account creation)
+    // 5. [writable] TreasuryState PDA account
+    // 6. [writable] Treasury ATA account (treasury authority's associated token account)
+    // 7. [readonly] TreasuryConfig PDA account
+    // 8. [writable] LockedTokens PDA account of the common reward recipient (will be created or topped up)
+    // Then for each entry, in input order:
+    //   [readonly] CheckerMetadata PDA account
+    //   [readonly] CheckerLicenseMetadata PDA account
+    //   [readonly] `proof_accounts_len` proof accounts (only the nodes above the tree canopy)
+    if accounts.len() < FIXED_ACCOUNTS {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let signer = &accounts[0];
+    let global_rewards = &accounts[1];
+    let merkle_tree = &accounts[3];
+    let system_program = &accounts[4];
+    let treasury_state = &accounts[5];
+    let treasury_ata = &accounts[6];
+    let treasury_config = &accounts[7];
+    let locked_tokens = &accounts[8];
+
+    // Check signer is present
+    if !signer.is_signer {
+        msg!("Error: Transaction must be signed");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::PayoutCheckerRewardsBatchInput::try_from_slice(instruction_data)?;
+
+    if input.entries.is_empty() || input.entries.len() > MAX_CHECKER_PAYOUT_BATCH {
+        msg!("Error: Batch must contain between 1 and {} licenses, got {}", MAX_CHECKER_PAYOUT_BATCH, input.entries.len());
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    validate_checker_tree(merkle_tree.key)?;
+    validate_global_rewards_account(program_id, global_rewards)?;
+
+    // All licenses must belong to the same wallet and pay the same recipient so they can share one LockedTokens account
+    let owner = input.entries[0].license_context.owner;
+    let mut recipient: Option<Pubkey> = None;
+
+    let mut checker_indices: Vec<usize> = Vec::with_capacity(input.entries.len());
+    let mut cursor = FIXED_ACCOUNTS;
+
+    for entry in input.entries.iter() {
+        let license = &entry.license_context;
+        let proof_accounts_len = entry.proof_accounts_len as usize;
+        let entry_end = cursor + ACCOUNTS_PER_ENTRY + proof_accounts_len;
+        if accounts.len() < entry_end {
+            msg!("Error: Not enough accounts for batch entry {}", checker_indices.len());
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let checker_metadata = &accounts[cursor];
+        let checker_license_metadata = &accounts[cursor + 1];
+        let proof_accounts = &accounts[cursor + ACCOUNTS_PER_ENTRY..entry_end];
+        cursor = entry_end;
+
+        if license.owner != owner {
+            msg!("Error: All licenses in a payout batch must have the same owner");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let checker_index = license.index as usize;
+        if checker_indices.contains(&checker_index) {
+            msg!("Error: Checker {} appears more than once in the batch", checker_index);
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let leaf_asset_id = get_asset_id(merkle_tree.key, license.nonce);
+
+        // Build license leaf schema
+        let license_leaf = LeafSchema::V2 {
+            id: leaf_asset_id,
+            owner: license.owner,
+            delegate: license.delegate,
+            nonce: license.nonce,
+            data_hash: license.data_hash,
+            creator_hash: license.creator_hash,
+            collection_hash: license.collection_hash,
+            asset_data_hash: license.asset_data_hash,
+            flags: license.flags,
+        };
+
+        // Verify license
+        verify_license(
+            merkle_tree,
+            proof_accounts,
+            license.root,
+            license_leaf.hash(),
+            license.index,
+        )?;
+
+        // Validate checker metadata and authorization (owner or delegate)
+        let metadata = validate_checker_metadata_and_authorization(
+            program_id,
+            checker_metadata,
+            signer,
+            &leaf_asset_id,
+            &license.owner,
+        )?;
+
+        let entry_recipient = metadata.reward_recipient(&owner);
+        if *recipient.get_or_insert(entry_recipient) != entry_recipient {
+            msg!("Error: All licenses in a payout batch must have the same reward beneficiary");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Validate checker license metadata
+        validate_checker_license_metadata(
+            program_id,
+            checker_license_metadata,
+            &leaf_asset_id,
+        )?;
+
+        checker_indices.push(checker_index);
+    }
+
+    if cursor != accounts.len() {
+        msg!("Error: Unexpected trailing accounts in batch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Sum all balances, licenses without rewards are skipped rather than failing the batch
+    let payout_amount = {
+        let global_rewards_data = global_rewards.try_borrow_data()?;
+        let rewards = load_account::<GlobalRewardsView>(&global_rewards_data)?;
+        let mut total: u64 = 0;
+        for &checker_index in checker_indices.iter() {
+            let balance = rewards.checker_balance(checker_index)?;
+            total += balance as u64;
+        }
+        total
+    };
+
+    if payout_amount == 0 {
+        msg!("Error: None of the checkers in the batch have a balance");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let lock_duration_days = read_treasury_config(program_id, treasury_config)?.checker_rewards_lock_days;
+    let recipient = recipient.unwrap_or(owner);
+
+    grant_locked(
+        program_id,
+        signer, // payer
+        treasury_state,
+        treasury_ata,
+        locked_tokens,
+        system_program,
+        &recipient,
+        payout_amount,
+        lock_duration_days,
+        &SysvarClock,
+    )?;
+
+    {
+        let mut global_rewards_data = global_rewards.try_borrow_mut_data()?;
+        let rewards = load_account_mut::<GlobalRewardsView>(&mut global_rewards_data)?;
+        for &checker_index in checker_indices.iter() {
+            rewards.reset_checker_balance(checker_index)?;
+        }
+    }
+
+    emit(&DepinEvent::CheckerRewardsPaid {
+        owner,
+        checker_indices: checker_indices.iter().map(|&checker_index| checker_index as u32).collect(),
+        amount: payout_amount,
+    });
+
+    msg!("Successfully paid out {} BMB as locked tokens for {} checker licenses", payout_amount, checker_indices.len());
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{AUTHORITY_SEED, ESCROW_SEED},
+    features::escrow::accounts::{EscrowAuthority, EscrowDelegate},
+    utils::account::{create_pda_account, read_account_data, write_account_data},
+};
+use crate::input;
+
+pub fn process_add_escrow_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Escrow beneficiary (pays for account creation)
+    // 1. [writable] EscrowAuthority PDA account of the beneficiary (will be created if doesn't exist)
+    // 2. [readonly] System program account (for account creation)
+    let account_info_iter = &mut accounts.iter();
+    let beneficiary_account = next_account_info(account_info_iter)?;
+    let escrow_authority_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !beneficiary_account.is_signer {
+        msg!("Error: Escrow beneficiary must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::AddEscrowDelegateInput::try_from_slice(instruction_data)?;
+
+    if input.delegate == *beneficiary_account.key {
+        msg!("Error: The beneficiary can always withdraw and cannot be its own delegate");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (escrow_authority_pda, bump_seed) = EscrowAuthority::find_pda(program_id, beneficiary_account.key);
+    if *escrow_authority_account.key != escrow_authority_pda {
+        msg!("Error: EscrowAuthority account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut escrow_authority = if escrow_authority_account.data_is_empty() {
+        create_pda_account(
+            beneficiary_account,
+            escrow_authority_account,
+            system_program,
+            program_id,
+            EscrowAuthority::LEN,
+            &[ESCROW_SEED, AUTHORITY_SEED, beneficiary_account.key.as_ref(), &[bump_seed]],
+        )?;
+        msg!("Created new EscrowAuthority account: {}", escrow_authority_pda);
+        EscrowAuthority {
+            beneficiary: *beneficiary_account.key,
+            delegates: Vec::new(),
+        }
+    } else {
+        read_account_data(&escrow_authority_account.try_borrow_data()?, EscrowAuthority::account_type())?
+    };
+
+    // Setting a delegate again starts a fresh allowance
+    let entry = EscrowDelegate {
+        delegate: input.delegate,
+        mint: input.mint,
+        spend_limit: input.spend_limit,
+        spent: 0,
+    };
+
+    if !escrow_authority.upsert(entry) {
+        msg!("Error: EscrowAuthority is full, at most {} delegates can be set", EscrowAuthority::MAX_DELEGATES);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut data = escrow_authority_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, EscrowAuthority::account_type(), &escrow_authority)?;
+
+    msg!("Escrow delegate {} may withdraw up to {} of mint {}", input.delegate, input.spend_limit, input.mint);
+    Ok(())
+}
+
+pub fn process_remove_escrow_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Escrow beneficiary
+    // 1. [writable] EscrowAuthority PDA account of the beneficiary
+    let account_info_iter = &mut accounts.iter();
+    let beneficiary_account = next_account_info(account_info_iter)?;
+    let escrow_authority_account = next_account_info(account_info_iter)?;
+
+    if !beneficiary_account.is_signer {
+        msg!("Error: Escrow beneficiary must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::RemoveEscrowDelegateInput::try_from_slice(instruction_data)?;
+
+    let (escrow_authority_pda, _) = EscrowAuthority::find_pda(program_id, beneficiary_account.key);
+    if *escrow_authority_account.key != escrow_authority_pda {
+        msg!("Error: EscrowAuthority account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if escrow_authority_account.data_is_empty() {
+        msg!("Error: EscrowAuthority account does not exist");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let mut escrow_authority: EscrowAuthority = read_account_data(
+        &escrow_authority_account.try_borrow_data()?,
+        EscrowAuthority::account_type(),
+    )?;
+
+    if !escrow_authority.remove(&input.delegate, &input.mint) {
+        msg!("Error: {} is not an escrow delegate for mint {}", input.delegate, input.mint);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The list shrank, clear the stale tail before writing
+    let mut data = escrow_authority_account.try_borrow_mut_data()?;
+    data.fill(0);
+    write_account_data(&mut data, EscrowAuthority::account_type(), &escrow_authority)?;
+
+    msg!("Escrow delegate {} removed for mint {}", input.delegate, input.mint);
+    Ok(())
+}
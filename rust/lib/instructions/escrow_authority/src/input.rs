@@ -0,0 +1,17 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct AddEscrowDelegateInput {
+    pub delegate: Pubkey,
+    pub mint: Pubkey,
+    pub spend_limit: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct RemoveEscrowDelegateInput {
+    pub delegate: Pubkey,
+    pub mint: Pubkey,
+}
@@ -0,0 +1,4 @@
+pub mod input;
+mod delegate;
+
+pub use delegate::{process_add_escrow_delegate, process_remove_escrow_delegate};
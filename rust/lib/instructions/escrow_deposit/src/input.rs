@@ -0,0 +1,10 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct EscrowDepositInput {
+    pub amount: u64,
+    /// None deposits into the depositor's own escrow
+    pub beneficiary: Option<Pubkey>,
+}
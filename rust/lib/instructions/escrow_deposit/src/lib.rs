@@ -1,24 +1,19 @@
+pub mod input;
+
+use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
-    program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction,
-    sysvar::{rent::Rent, Sysvar},
 };
-
-use spl_token::{
-    instruction as token_instruction, solana_program::program_pack::Pack,
-    state::Account as TokenAccount, ID as TOKEN_PROGRAM_ID,
+use shared::{
+    constants::seeds::{ESCROW_SEED, TOKEN_SEED},
+    events::{emit, DepinEvent},
+    features::escrow::utils::validate_deposit_mint,
+    utils::token::{create_pda_token_account, read_mint, read_token_account, transfer_from_signer, validate_token_program},
 };
-use std::convert::TryInto;
-
-use shared::constants::seeds::{ESCROW_SEED, TOKEN_SEED};
-
-#[cfg(not(feature = "test"))]
-use shared::constants::accounts::{BMB_MINT, USDC_MINT};
 
 pub fn process_deposit_request(
     program_id: &Pubkey,
@@ -26,12 +21,13 @@ pub fn process_deposit_request(
     instruction_data: &[u8],
 ) -> ProgramResult {
     // Expected Accounts:
-    // 0. [signer] Depositor
-    // 1. [writable] Depositor's token account (must be either USDC or BMB token)
-    // 2. [writable] Escrow token account (PDA, address calculated by client)
-    // 4. [] Mint account
-    // 5. [] Token program account
-    // 6. [] System program account (for account creation if needed)
+    // 0. [signer] Depositor (pays for escrow creation, need not be the beneficiary)
+    // 1. [writable] Depositor's token account (its mint must be listed in AllowedMints)
+    // 2. [writable] Escrow token account of the beneficiary (PDA, address calculated by client)
+    // 3. [] Mint account
+    // 4. [] Token program account (SPL token or Token-2022, must own the mint)
+    // 5. [] System program account (for account creation if needed)
+    // 6. [] AllowedMints PDA account
 
     let account_info_iter = &mut accounts.iter();
     let depositor = next_account_info(account_info_iter)?;
@@ -40,44 +36,47 @@ pub fn process_deposit_request(
     let mint_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
     
     if !depositor.is_signer {
         msg!("Error: Depositor must sign the transaction");
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Validate depositor token account
-    let depositor_token_state: TokenAccount;
-    { // Drop the borrow of the `depositor_token_account` because we need to borrow it again later in the transfer instruction
-        let depositor_token_data = depositor_token_account.try_borrow_data()?;
-        depositor_token_state = TokenAccount::unpack(&depositor_token_data)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-    } 
-
-    #[cfg(feature = "test")]
-    {
-        msg!("[Test mode] Skipping token mint validation");
-    }
+    validate_token_program(token_program)?;
+
+    let input = input::EscrowDepositInput::try_from_slice(instruction_data)?;
+    let deposit_amount = input.amount;
 
-    #[cfg(not(feature = "test"))]
-    {
-        if depositor_token_state.mint != USDC_MINT && depositor_token_state.mint != BMB_MINT {
-            msg!("Error: Token mint is not supported for deposits");
-            return Err(ProgramError::InvalidAccountData);
-        }
+    if deposit_amount == 0 {
+        msg!("Error: Deposit amount must be greater than zero");
+        return Err(ProgramError::InvalidInstructionData);
     }
 
+    // Anyone can top up someone else's escrow
+    let beneficiary = input.beneficiary.unwrap_or(*depositor.key);
+
+    // Validate depositor token account
+    let depositor_token_state = read_token_account(depositor_token_account)?;
+
     if *mint_account.key != depositor_token_state.mint {
         msg!("Error: Mint account does not match token account mint");
         return Err(ProgramError::InvalidAccountData);
     }
 
+    if mint_account.owner != token_program.key {
+        msg!("Error: Mint account is not owned by the given token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mint = read_mint(mint_account)?;
+
     // Validate escrow PDA
     let (pda, bump_seed) = Pubkey::find_program_address(
         &[
             ESCROW_SEED,
             TOKEN_SEED,
-            depositor.key.as_ref(),
+            beneficiary.as_ref(),
             depositor_token_state.mint.as_ref(),
         ],
         program_id,
@@ -90,88 +89,48 @@ pub fn process_deposit_request(
 
     if escrow_token_account.data_is_empty() {
         msg!("Initializing escrow token account at address: {}", pda);
-        // Calculate rent-exempt minimum balance
-        let rent = Rent::get()?;
-        let space = TokenAccount::LEN;
-        let rent_lamports = rent.minimum_balance(space);
-
-        // Create the token account
-        invoke_signed(
-            &system_instruction::create_account(
-                depositor.key,
-                &pda,
-                rent_lamports,
-                space as u64,
-                &TOKEN_PROGRAM_ID,
-            ),
+        create_pda_token_account(
+            depositor,
+            escrow_token_account,
+            mint_account,
+            system_program,
+            token_program,
             &[
-                depositor.clone(),
-                escrow_token_account.clone(),
-                system_program.clone(),
-            ],
-            &[&[
                 ESCROW_SEED,
                 TOKEN_SEED,
-                depositor.key.as_ref(),
+                beneficiary.as_ref(),
                 depositor_token_state.mint.as_ref(),
                 &[bump_seed],
-            ]],
-        )?;
-
-        // Initialize as a token account
-        invoke_signed(
-            &token_instruction::initialize_account3(
-                &TOKEN_PROGRAM_ID,
-                &pda,
-                &depositor_token_state.mint,
-                &pda
-            )?,
-            &[
-                escrow_token_account.clone(),
-                mint_account.clone()
-                ],
-            &[&[
-                ESCROW_SEED,
-                TOKEN_SEED,
-                depositor.key.as_ref(),
-                depositor_token_state.mint.as_ref(),
-                &[bump_seed],
-            ]],
+            ],
         )?;
     }
 
-    msg!(&instruction_data.len().to_string());
+    validate_deposit_mint(program_id, allowed_mints_account, &depositor_token_state.mint, deposit_amount)?;
 
-    // Parse deposit amount
-    if instruction_data.len() < 8 {
-        msg!("Error: Not enough data provided for deposit amount");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-
-    let deposit_amount: u64 = u64::from_le_bytes(instruction_data[0..8].try_into().unwrap());
-
-    if deposit_amount == 0 {
-        msg!("Error: Deposit amount must be greater than zero");
-        return Err(ProgramError::InvalidInstructionData);
-    }
+    let balance_before = read_token_account(escrow_token_account)?.amount;
 
     // Execute token transfer
-    invoke(
-        &token_instruction::transfer(
-            &token_program.key,
-            &depositor_token_account.key,
-            &escrow_token_account.key,
-            &depositor.key,
-            &[],
-            deposit_amount,
-        )?,
-        &[
-            depositor.clone(),
-            depositor_token_account.clone(),
-            escrow_token_account.clone(),
-            token_program.clone(),
-        ]
+    transfer_from_signer(
+        token_program,
+        depositor_token_account,
+        mint_account,
+        escrow_token_account,
+        depositor,
+        deposit_amount,
+        mint.decimals,
     )?;
 
+    // Transfer-fee mints withhold part of the amount in the escrow account, only the rest is spendable
+    let credited = read_token_account(escrow_token_account)?.amount - balance_before;
+    emit(&DepinEvent::EscrowDeposited {
+        depositor: *depositor.key,
+        beneficiary,
+        mint: depositor_token_state.mint,
+        amount: deposit_amount,
+        credited,
+    });
+
+    msg!("Deposited {}, credited {} to escrow of {} (fee {})", deposit_amount, credited, beneficiary, deposit_amount - credited);
+
     Ok(())
 }
@@ -0,0 +1,10 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct EscrowWithdrawInput {
+    pub amount: u64,
+    /// Owner of the escrow a delegate withdraws from, None withdraws from the withdrawer's own escrow
+    pub escrow_owner: Option<Pubkey>,
+}
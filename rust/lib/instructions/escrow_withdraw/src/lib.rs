@@ -1,18 +1,27 @@
+pub mod input;
+mod notice;
+
+pub use notice::process_withdrawal_notice;
+
+use borsh::BorshDeserialize;
 use solana_program::{
     account_info::next_account_info, account_info::AccountInfo, entrypoint::ProgramResult, msg,
-    program::invoke_signed, program_error::ProgramError, pubkey::Pubkey,
+    program_error::ProgramError, pubkey::Pubkey,
 };
 
-use spl_token::{
-    instruction as token_instruction, solana_program::program_pack::Pack,
-    state::Account as TokenAccount,
+use shared::{
+    constants::seeds::{ESCROW_SEED, TOKEN_SEED},
+    events::{emit, DepinEvent},
+    features::escrow::{
+        accounts::EscrowState,
+        utils::{load_escrow_state, spend_escrow_allowance, validate_withdrawal_mint},
+    },
+    utils::{
+        account::write_account_data,
+        bmb::get_current_period,
+        token::{read_mint, read_token_account, transfer_signed, validate_token_program},
+    },
 };
-use std::convert::TryInto;
-
-use shared::constants::seeds::{ESCROW_SEED, TOKEN_SEED};
-
-#[cfg(not(feature = "test"))]
-use shared::constants::accounts::{BMB_MINT, USDC_MINT};
 
 pub fn process_withdrawal_request(
     program_id: &Pubkey,
@@ -20,11 +29,15 @@ pub fn process_withdrawal_request(
     instruction_data: &[u8],
 ) -> ProgramResult {
     // Expected Accounts:
-    // 0. [signer] Withdrawer (owner of the escrow)
-    // 1. [writable] Withdrawer's token account (must be either USDC or BMB token)
+    // 0. [signer] Withdrawer (owner of the escrow, or one of its delegates)
+    // 1. [writable] Withdrawer's token account
     // 2. [writable] Escrow token account (PDA, address calculated by client)
     // 3. [] Program account (needed since program is authority)
-    // 4. [] Token program account
+    // 4. [] Token program account (SPL token or Token-2022, must own the mint)
+    // 5. [] Mint account
+    // 6. [] AllowedMints PDA account
+    // 7. [writable] EscrowState PDA account of the escrow (may be empty)
+    // 8. [writable] EscrowAuthority PDA account of the escrow owner (only when a delegate withdraws)
 
     let account_info_iter = &mut accounts.iter();
     let withdrawer = next_account_info(account_info_iter)?;
@@ -32,6 +45,10 @@ pub fn process_withdrawal_request(
     let escrow_token_account = next_account_info(account_info_iter)?;
     let program_account = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+    let escrow_authority_account = account_info_iter.next();
 
     if !withdrawer.is_signer {
         msg!("Error: Withdrawer must sign the transaction");
@@ -43,34 +60,42 @@ pub fn process_withdrawal_request(
         return Err(ProgramError::InvalidArgument);
     }
 
+    validate_token_program(token_program)?;
+
     // Validate withdrawer token account
-    let withdrawer_token_state: TokenAccount;
-    {
-        // Drop the borrow of the `withdrawer_token_account` because we need to borrow it again later in the transfer instruction
-        let withdrawer_token_data = withdrawer_token_account.try_borrow_data()?;
-        withdrawer_token_state = TokenAccount::unpack(&withdrawer_token_data)
-            .map_err(|_| ProgramError::InvalidAccountData)?;
+    let withdrawer_token_state = read_token_account(withdrawer_token_account)?;
+
+    validate_withdrawal_mint(program_id, allowed_mints_account, &withdrawer_token_state.mint)?;
+
+    if *mint_account.key != withdrawer_token_state.mint {
+        msg!("Error: Mint account does not match token account mint");
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    #[cfg(feature = "test")]
-    {
-        msg!("[Test mode] Skipping token mint validation");
+    if mint_account.owner != token_program.key {
+        msg!("Error: Mint account is not owned by the given token program");
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    #[cfg(not(feature = "test"))]
-    {
-        if withdrawer_token_state.mint != USDC_MINT && withdrawer_token_state.mint != BMB_MINT {
-            msg!("Error: Token mint is not supported for withdrawals");
-            return Err(ProgramError::InvalidAccountData);
-        }
+    let mint = read_mint(mint_account)?;
+
+    let input = input::EscrowWithdrawInput::try_from_slice(instruction_data)?;
+    let withdrawal_amount = input.amount;
+
+    if withdrawal_amount == 0 {
+        msg!("Error: Withdrawal amount must be greater than zero");
+        return Err(ProgramError::InvalidInstructionData);
     }
 
+    // A delegate withdraws from its owner's escrow
+    let escrow_owner = input.escrow_owner.unwrap_or(*withdrawer.key);
+
     // Validate escrow PDA
     let (pda, bump_seed) = Pubkey::find_program_address(
         &[
             ESCROW_SEED,
             TOKEN_SEED,
-            withdrawer.key.as_ref(),
+            escrow_owner.as_ref(),
             withdrawer_token_state.mint.as_ref(),
         ],
         program_id,
@@ -87,54 +112,80 @@ pub fn process_withdrawal_request(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    // Parse withdrawal amount
-    if instruction_data.len() < 8 {
-        msg!("Error: Not enough data provided for withdrawal amount");
-        return Err(ProgramError::InvalidInstructionData);
+    // Check escrow token account balance, fees withheld in the account are not part of it
+    let escrow_token_state = read_token_account(escrow_token_account)?;
+    if escrow_token_state.amount < withdrawal_amount {
+        msg!("Error: Insufficient funds in escrow account");
+        return Err(ProgramError::InsufficientFunds);
     }
 
-    let withdrawal_amount: u64 = u64::from_le_bytes(instruction_data[0..8].try_into().unwrap());
+    // Encumbered funds back active commitments and need a matured withdrawal notice
+    let mut escrow_state = load_escrow_state(program_id, escrow_state_account, &escrow_owner, &withdrawer_token_state.mint)?;
+    let unencumbered = escrow_state.unencumbered(escrow_token_state.amount);
+    if withdrawal_amount > unencumbered {
+        let current_period = get_current_period()?;
+        if !escrow_state.is_notice_matured(withdrawal_amount, current_period) {
+            msg!(
+                "Error: Only {} is unencumbered, withdrawing {} needs a notice of {} periods",
+                unencumbered,
+                withdrawal_amount,
+                EscrowState::NOTICE_PERIODS,
+            );
+            return Err(ProgramError::InsufficientFunds);
+        }
 
-    if withdrawal_amount == 0 {
-        msg!("Error: Withdrawal amount must be greater than zero");
-        return Err(ProgramError::InvalidInstructionData);
+        escrow_state.pending_withdrawal -= withdrawal_amount;
+        let mut data = escrow_state_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, EscrowState::account_type(), &escrow_state)?;
+        msg!("Withdrawal notice used, {} still pending", escrow_state.pending_withdrawal);
     }
 
-    // Check escrow token account balance
-    let escrow_token_state = TokenAccount::unpack(&escrow_token_account.try_borrow_data()?)?;
-    if escrow_token_state.amount < withdrawal_amount {
-        msg!("Error: Insufficient funds in escrow account");
-        return Err(ProgramError::InsufficientFunds);
+    if escrow_owner != *withdrawer.key {
+        let Some(escrow_authority_account) = escrow_authority_account else {
+            msg!("Error: EscrowAuthority account is required to withdraw as a delegate");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        spend_escrow_allowance(
+            program_id,
+            escrow_authority_account,
+            &escrow_owner,
+            withdrawer.key,
+            &withdrawer_token_state.mint,
+            withdrawal_amount,
+        )?;
     }
 
     // Check that the escrow token account is owned by the PDA
     msg!("Escrow token account owner: {}", escrow_token_state.owner);
     msg!("Expected PDA: {}", pda);
 
-    // Execute token transfer from escrow to withdrawer
-    invoke_signed(
-        &token_instruction::transfer(
-            &token_program.key,
-            &escrow_token_account.key,
-            &withdrawer_token_account.key,
-            &pda,
-            &[],
-            withdrawal_amount,
-        )?,
+    // Execute token transfer from escrow to withdrawer, the withdrawer receives the amount minus any transfer fee
+    transfer_signed(
+        token_program,
+        escrow_token_account,
+        mint_account,
+        withdrawer_token_account,
+        escrow_token_account,
+        withdrawal_amount,
+        mint.decimals,
         &[
-            escrow_token_account.clone(),
-            withdrawer_token_account.clone(),
-            program_account.clone(),
-            token_program.clone()
-        ],
-        &[&[
             ESCROW_SEED,
             TOKEN_SEED,
-            withdrawer.key.as_ref(),
+            escrow_owner.as_ref(),
             withdrawer_token_state.mint.as_ref(),
             &[bump_seed],
-        ]],
+        ],
     )?;
 
+    emit(&DepinEvent::EscrowWithdrawn {
+        owner: escrow_owner,
+        withdrawer: *withdrawer.key,
+        mint: withdrawer_token_state.mint,
+        amount: withdrawal_amount,
+    });
+
+    msg!("Withdrew {} from escrow of {} (fee {})", withdrawal_amount, escrow_owner, mint.transfer_fee(withdrawal_amount)?);
+
     Ok(())
 }
@@ -0,0 +1,72 @@
+use solana_program::{
+    account_info::next_account_info, account_info::AccountInfo, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+use std::convert::TryInto;
+
+use shared::{
+    events::{emit, DepinEvent},
+    features::escrow::{
+        accounts::EscrowState,
+        utils::{load_escrow_state, store_escrow_state},
+    },
+    utils::bmb::get_current_period,
+};
+
+pub fn process_withdrawal_notice<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Escrow owner (pays for account creation)
+    // 1. [writable] EscrowState PDA account of the escrow (will be created if doesn't exist)
+    // 2. [] Mint account of the escrow
+    // 3. [] System program account (for account creation if needed)
+
+    let account_info_iter = &mut accounts.iter();
+    let owner = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !owner.is_signer {
+        msg!("Error: Escrow owner must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Parse notice amount, zero cancels a pending notice
+    if instruction_data.len() < 8 {
+        msg!("Error: Not enough data provided for withdrawal amount");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let notice_amount: u64 = u64::from_le_bytes(instruction_data[0..8].try_into().unwrap());
+
+    let mut escrow_state = load_escrow_state(program_id, escrow_state_account, owner.key, mint_account.key)?;
+
+    // A new notice replaces the previous one and restarts its clock
+    let current_period = get_current_period()?;
+    escrow_state.pending_withdrawal = notice_amount;
+    escrow_state.unlock_period = current_period.checked_add(EscrowState::NOTICE_PERIODS).ok_or_else(|| {
+        msg!("Error: Notice would end after period u16::MAX");
+        ProgramError::ArithmeticOverflow
+    })?;
+
+    store_escrow_state(program_id, owner, escrow_state_account, system_program, &escrow_state)?;
+
+    emit(&DepinEvent::EscrowWithdrawalNoticed {
+        owner: *owner.key,
+        mint: *mint_account.key,
+        amount: notice_amount,
+        unlock_period: escrow_state.unlock_period,
+    });
+
+    if notice_amount == 0 {
+        msg!("Withdrawal notice cancelled");
+    } else {
+        msg!("Withdrawal of {} announced, executable from period {}", notice_amount, escrow_state.unlock_period);
+    }
+
+    Ok(())
+}
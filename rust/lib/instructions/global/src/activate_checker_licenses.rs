@@ -5,12 +5,13 @@ use solana_program::{
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
-    system_instruction,
     sysvar::{rent::Rent, Sysvar},
     program::invoke_signed,
 };
+use solana_system_interface::instruction as system_instruction;
 use shared::{
     constants::seeds::{GLOBAL_SEED, STATE_SEED},
+    events::{emit, DepinEvent},
     features::global::accounts::BMBState,
     types::account::DepinAccountType,
     utils::{account::{read_account_data, write_account_data}, bmb::get_current_period},
@@ -59,57 +60,49 @@ pub fn process_activate_checker_licenses<'info>(
         return Err(ProgramError::InvalidArgument);
     }
 
-    // Validate period constraints
-    let current_period = get_current_period();
-    if input.period <= current_period {
-        msg!("Error: New period must be greater than current period ({})", current_period);
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    let account_exists = !bmb_state_account.data_is_empty();
-    let mut bmb_state = if account_exists {
-        // Load existing state
-        let existing_state: BMBState = read_account_data(
+    // Load existing state, the account is only created once the input is validated
+    let existing_state: Option<BMBState> = if bmb_state_account.data_is_empty() {
+        None
+    } else {
+        Some(read_account_data(
             &bmb_state_account.try_borrow_data()?,
             DepinAccountType::BMBState,
-        )?;
-
-        // Validate that new period is larger than the last period in buffer
-        if let Some(entries) = existing_state.get_all_entries().last() {
-            let (last_period, _) = entries;
-            if input.period <= *last_period {
-                msg!("Error: New period ({}) must be greater than last period in buffer ({})", 
-                     input.period, last_period);
-                return Err(ProgramError::InvalidArgument);
-            }
-        }
+        )?)
+    };
 
-        existing_state
-    } else {
-        // Create new account
-        let rent = Rent::get()?;
-        let space = BMBState::LEN;
-        let rent_lamports = rent.minimum_balance(space);
-
-        msg!("Creating new BMBState account with space: {}", space);
-
-        invoke_signed(
-            &system_instruction::create_account(
-                admin_account.key,
-                &bmb_state_pda,
-                rent_lamports,
-                space as u64,
-                program_id,
-            ),
-            &[
-                admin_account.clone(),
-                bmb_state_account.clone(),
-                system_program.clone(),
-            ],
-            &[&[GLOBAL_SEED, STATE_SEED, &[bump_seed]]],
-        )?;
-        msg!("Created new BMBState account: {}", bmb_state_pda);
-        BMBState::new()
+    let last_period = existing_state
+        .as_ref()
+        .and_then(|state| state.get_all_entries().last().map(|&(period, _)| period));
+    validate_period(input.period, get_current_period()?, last_period)?;
+
+    let mut bmb_state = match existing_state {
+        Some(state) => state,
+        None => {
+            // Create new account
+            let rent = Rent::get()?;
+            let space = BMBState::LEN;
+            let rent_lamports = rent.minimum_balance(space);
+
+            msg!("Creating new BMBState account with space: {}", space);
+
+            invoke_signed(
+                &system_instruction::create_account(
+                    admin_account.key,
+                    &bmb_state_pda,
+                    rent_lamports,
+                    space as u64,
+                    program_id,
+                ),
+                &[
+                    admin_account.clone(),
+                    bmb_state_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[GLOBAL_SEED, STATE_SEED, &[bump_seed]]],
+            )?;
+            msg!("Created new BMBState account: {}", bmb_state_pda);
+            BMBState::new()
+        },
     };
 
     // Add the new period entry
@@ -119,6 +112,11 @@ pub fn process_activate_checker_licenses<'info>(
     let mut data = bmb_state_account.try_borrow_mut_data()?;
     write_account_data(&mut data, BMBState::account_type(), &bmb_state)?;
 
+    emit(&DepinEvent::CheckerLicensesActivated {
+        period: input.period,
+        checker_count: input.checker_count,
+    });
+
     msg!(
         "BMB state updated successfully: period {} with {} checkers",
         input.period,
@@ -126,4 +124,43 @@ pub fn process_activate_checker_licenses<'info>(
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// A checker count can only be scheduled for a future period, after the last scheduled one
+fn validate_period(period: u16, current_period: u16, last_period: Option<u16>) -> ProgramResult {
+    if period <= current_period {
+        msg!("Error: New period must be greater than current period ({})", current_period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if let Some(last_period) = last_period {
+        if period <= last_period {
+            msg!("Error: New period ({}) must be greater than last period in buffer ({})",
+                 period, last_period);
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn period_must_be_in_the_future() {
+        assert_eq!(validate_period(10, 10, None), Err(ProgramError::InvalidArgument));
+        assert_eq!(validate_period(9, 10, None), Err(ProgramError::InvalidArgument));
+        assert_eq!(validate_period(11, 10, None), Ok(()));
+    }
+
+    #[test]
+    fn period_must_follow_the_last_scheduled_one() {
+        assert_eq!(validate_period(20, 10, Some(20)), Err(ProgramError::InvalidArgument));
+        assert_eq!(validate_period(15, 10, Some(20)), Err(ProgramError::InvalidArgument));
+        assert_eq!(validate_period(21, 10, Some(20)), Ok(()));
+        // A schedule entry in the past does not constrain beyond the current period
+        assert_eq!(validate_period(11, 10, Some(5)), Ok(()));
+    }
+}
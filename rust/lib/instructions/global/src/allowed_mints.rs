@@ -0,0 +1,141 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{GLOBAL_SEED, MINTS_SEED},
+    events::{emit, DepinEvent},
+    features::global::accounts::{AllowedMint, AllowedMints},
+    utils::account::{create_pda_account, read_account_data, write_account_data},
+};
+#[cfg(not(feature = "test"))]
+use shared::constants::accounts::BMB_LICENSE_ADMIN;
+use crate::input::{RemoveAllowedMintInput, SetAllowedMintInput};
+
+pub fn process_set_allowed_mint<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] BMB License Admin
+    // 1. [writable] AllowedMints PDA account (will be created if doesn't exist)
+    // 2. [readonly] System program account (for account creation)
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let input = SetAllowedMintInput::try_from_slice(instruction_data)?;
+
+    validate_admin(admin_account)?;
+
+    let (allowed_mints_pda, bump_seed) = AllowedMints::find_pda(program_id);
+    if *allowed_mints_account.key != allowed_mints_pda {
+        msg!("Error: AllowedMints account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut allowed_mints = if allowed_mints_account.data_is_empty() {
+        create_pda_account(
+            admin_account,
+            allowed_mints_account,
+            system_program,
+            program_id,
+            AllowedMints::LEN,
+            &[GLOBAL_SEED, MINTS_SEED, &[bump_seed]],
+        )?;
+        msg!("Created new AllowedMints account: {}", allowed_mints_pda);
+        AllowedMints::default()
+    } else {
+        read_account_data(&allowed_mints_account.try_borrow_data()?, AllowedMints::account_type())?
+    };
+
+    let entry = AllowedMint {
+        mint: input.mint,
+        max_deposit: input.max_deposit,
+        paused: input.paused,
+    };
+
+    if !allowed_mints.upsert(entry) {
+        msg!("Error: AllowedMints is full, at most {} mints can be listed", AllowedMints::MAX_MINTS);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut data = allowed_mints_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, AllowedMints::account_type(), &allowed_mints)?;
+
+    emit(&DepinEvent::AllowedMintSet {
+        mint: input.mint,
+        max_deposit: input.max_deposit,
+        paused: input.paused,
+    });
+
+    msg!("Mint {} allowed with max deposit {}, paused: {}", input.mint, input.max_deposit, input.paused);
+    Ok(())
+}
+
+pub fn process_remove_allowed_mint<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] BMB License Admin
+    // 1. [writable] AllowedMints PDA account
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
+
+    let input = RemoveAllowedMintInput::try_from_slice(instruction_data)?;
+
+    validate_admin(admin_account)?;
+
+    let (allowed_mints_pda, _) = AllowedMints::find_pda(program_id);
+    if *allowed_mints_account.key != allowed_mints_pda {
+        msg!("Error: AllowedMints account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut allowed_mints: AllowedMints = read_account_data(
+        &allowed_mints_account.try_borrow_data()?,
+        AllowedMints::account_type(),
+    )?;
+
+    // Existing escrow balances of a removed mint stay withdrawable
+    if !allowed_mints.remove(&input.mint) {
+        msg!("Error: Mint {} is not in AllowedMints", input.mint);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The list shrank, clear the stale tail before writing
+    let mut data = allowed_mints_account.try_borrow_mut_data()?;
+    data.fill(0);
+    write_account_data(&mut data, AllowedMints::account_type(), &allowed_mints)?;
+
+    emit(&DepinEvent::AllowedMintRemoved { mint: input.mint });
+
+    msg!("Mint {} removed from AllowedMints", input.mint);
+    Ok(())
+}
+
+pub(crate) fn validate_admin(admin_account: &AccountInfo) -> ProgramResult {
+    // Verify admin is signer
+    if !admin_account.is_signer {
+        msg!("Error: BMB License Admin must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Verify admin authority (skip in test builds)
+    #[cfg(not(feature = "test"))]
+    if *admin_account.key != BMB_LICENSE_ADMIN {
+        msg!("Error: Only BMB License Admin can update AllowedMints");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(())
+}
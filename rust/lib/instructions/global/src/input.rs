@@ -1,7 +1,37 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct ActivateCheckersInput {
     pub period: u16,
     pub checker_count: u32,
 }
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetAllowedMintInput {
+    pub mint: Pubkey,
+    pub max_deposit: u64,
+    pub paused: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct RemoveAllowedMintInput {
+    pub mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetPauseFlagsInput {
+    /// Full set of `PauseState` flags to apply
+    pub flags: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetInstantPayoutHaircutInput {
+    /// Share of instant checker payouts kept by the treasury, in basis points
+    pub haircut_bps: u16,
+}
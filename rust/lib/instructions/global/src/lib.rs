@@ -1,4 +1,10 @@
 pub mod activate_checker_licenses;
+pub mod allowed_mints;
 pub mod input;
+pub mod pause;
+pub mod treasury_config;
 
-pub use activate_checker_licenses::process_activate_checker_licenses;
\ No newline at end of file
+pub use activate_checker_licenses::process_activate_checker_licenses;
+pub use allowed_mints::{process_set_allowed_mint, process_remove_allowed_mint};
+pub use pause::process_set_pause_flags;
+pub use treasury_config::process_set_instant_payout_haircut;
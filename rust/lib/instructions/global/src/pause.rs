@@ -0,0 +1,191 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use shared::{
+    constants::seeds::{GLOBAL_SEED, PAUSE_SEED},
+    events::{emit, DepinEvent},
+    features::global::accounts::PauseState,
+    utils::account::{create_pda_account, read_account_data, write_account_data},
+};
+use crate::{allowed_mints::validate_admin, input::SetPauseFlagsInput};
+
+pub fn process_set_pause_flags<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] BMB License Admin
+    // 1. [writable] PauseState PDA account (will be created if doesn't exist)
+    // 2. [readonly] System program account (for account creation)
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let pause_state_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let input = SetPauseFlagsInput::try_from_slice(instruction_data)?;
+
+    validate_admin(admin_account)?;
+
+    let (pause_state_pda, bump_seed) = PauseState::find_pda(program_id);
+    if *pause_state_account.key != pause_state_pda {
+        msg!("Error: PauseState account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut state = if pause_state_account.data_is_empty() {
+        create_pda_account(
+            admin_account,
+            pause_state_account,
+            system_program,
+            program_id,
+            PauseState::LEN,
+            &[GLOBAL_SEED, PAUSE_SEED, &[bump_seed]],
+        )?;
+        msg!("Created new PauseState account: {}", pause_state_pda);
+        PauseState::default()
+    } else {
+        read_account_data(&pause_state_account.try_borrow_data()?, PauseState::account_type())?
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let scheduled = update_flags(&mut state, input.flags, now)?;
+
+    let mut data = pause_state_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, PauseState::account_type(), &state)?;
+
+    if scheduled {
+        emit(&DepinEvent::UnpauseScheduled { flags: state.pending_flags, unpause_at: state.unpause_at });
+        msg!("Unpause to flags {:#04x} scheduled, apply it again from {}", state.pending_flags, state.unpause_at);
+    } else {
+        emit(&DepinEvent::PauseFlagsSet { flags: state.flags });
+        msg!("Pause flags set to {:#04x}", state.flags);
+    }
+    Ok(())
+}
+
+/// Applies `flags` to `state`, returns true when clearing a flag was only scheduled.
+/// New flags take effect at once, cleared ones after `PauseState::UNPAUSE_DELAY` and a second call with the same flags.
+fn update_flags(state: &mut PauseState, flags: u8, now: i64) -> Result<bool, ProgramError> {
+    if flags & !PauseState::ALL != 0 {
+        msg!("Error: Unknown pause flags {:#04x}", flags & !PauseState::ALL);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let added = flags & !state.flags;
+    let cleared = state.flags & !flags;
+    state.flags |= added;
+
+    // Pausing more cancels any unpause still waiting
+    if added != 0 || cleared == 0 {
+        state.pending_flags = 0;
+        state.unpause_at = 0;
+    }
+
+    if cleared == 0 {
+        return Ok(false);
+    }
+
+    if state.unpause_at != 0 && state.pending_flags == flags {
+        if now < state.unpause_at {
+            msg!("Error: Unpause is scheduled for {}, it is now {}", state.unpause_at, now);
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        state.flags = flags;
+        state.pending_flags = 0;
+        state.unpause_at = 0;
+        return Ok(false);
+    }
+
+    state.pending_flags = flags;
+    state.unpause_at = now.checked_add(PauseState::UNPAUSE_DELAY).ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(true)
+}
+
+/// Rejects an instruction whose group is paused. `pause_state_account` must be the PauseState PDA,
+/// which may not exist yet when the network was never paused.
+pub fn check_not_paused(program_id: &Pubkey, pause_state_account: &AccountInfo, flag: u8) -> ProgramResult {
+    // The program only ever creates PauseState at its PDA, so a program owned one is the real one
+    if pause_state_account.owner != program_id {
+        let (pause_state_pda, _) = PauseState::find_pda(program_id);
+        if *pause_state_account.key != pause_state_pda || !pause_state_account.data_is_empty() {
+            msg!("Error: Last account must be the PauseState PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+        return Ok(());
+    }
+
+    let state: PauseState = read_account_data(&pause_state_account.try_borrow_data()?, PauseState::account_type())?;
+    if state.is_paused(flag) {
+        msg!("Error: Instruction is paused (flags {:#04x})", state.flags);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000_000;
+
+    fn paused(flags: u8) -> PauseState {
+        PauseState { flags, ..PauseState::default() }
+    }
+
+    #[test]
+    fn pausing_applies_at_once() {
+        let mut state = PauseState::default();
+        assert_eq!(update_flags(&mut state, PauseState::PAYOUTS | PauseState::ESCROW, NOW), Ok(false));
+        assert_eq!(state, paused(PauseState::PAYOUTS | PauseState::ESCROW));
+
+        assert_eq!(update_flags(&mut state, 1 << 7, NOW), Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn unpausing_waits_for_the_delay() {
+        let mut state = paused(PauseState::ALL);
+        let flags = PauseState::ALL & !PauseState::UNLOCKS;
+
+        assert_eq!(update_flags(&mut state, flags, NOW), Ok(true));
+        assert_eq!(state.flags, PauseState::ALL);
+        assert_eq!((state.pending_flags, state.unpause_at), (flags, NOW + PauseState::UNPAUSE_DELAY));
+
+        let ready = NOW + PauseState::UNPAUSE_DELAY;
+        assert_eq!(update_flags(&mut state, flags, ready - 1), Err(ProgramError::InvalidArgument));
+        assert_eq!(update_flags(&mut state, flags, ready), Ok(false));
+        assert_eq!(state, paused(flags));
+    }
+
+    #[test]
+    fn a_different_unpause_restarts_the_delay() {
+        let mut state = paused(PauseState::PROOFS | PauseState::PAYOUTS);
+        assert_eq!(update_flags(&mut state, PauseState::PROOFS, NOW), Ok(true));
+
+        let later = NOW + PauseState::UNPAUSE_DELAY;
+        assert_eq!(update_flags(&mut state, 0, later), Ok(true));
+        assert_eq!((state.pending_flags, state.unpause_at), (0, later + PauseState::UNPAUSE_DELAY));
+    }
+
+    #[test]
+    fn pausing_more_cancels_a_scheduled_unpause() {
+        let mut state = paused(PauseState::PROOFS);
+        assert_eq!(update_flags(&mut state, 0, NOW), Ok(true));
+        assert_eq!(update_flags(&mut state, PauseState::PROOFS | PauseState::ESCROW, NOW + 1), Ok(false));
+        assert_eq!(state, paused(PauseState::PROOFS | PauseState::ESCROW));
+
+        // Swapping flags pauses the new group at once and schedules the rest
+        assert_eq!(update_flags(&mut state, PauseState::UNLOCKS, NOW + 2), Ok(true));
+        assert_eq!(state.flags, PauseState::PROOFS | PauseState::ESCROW | PauseState::UNLOCKS);
+        assert_eq!(state.pending_flags, PauseState::UNLOCKS);
+    }
+}
@@ -0,0 +1,66 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use shared::{
+    events::{emit, DepinEvent},
+    features::treasury::accounts::TreasuryConfig,
+    utils::account::{read_account_data, reallocate_account_if_needed, write_account_data},
+};
+use crate::{allowed_mints::validate_admin, input::SetInstantPayoutHaircutInput};
+
+pub fn process_set_instant_payout_haircut<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] BMB License Admin
+    // 1. [writable] TreasuryConfig PDA account (must exist)
+    // 2. [readonly] System program account (for reallocation if needed)
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let treasury_config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let input = SetInstantPayoutHaircutInput::try_from_slice(instruction_data)?;
+
+    validate_admin(admin_account)?;
+
+    if input.haircut_bps > TreasuryConfig::MAX_INSTANT_PAYOUT_HAIRCUT_BPS {
+        msg!("Error: Haircut must be at most {} bps", TreasuryConfig::MAX_INSTANT_PAYOUT_HAIRCUT_BPS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (treasury_config_pda, _) = TreasuryConfig::find_pda(program_id);
+    if *treasury_config_account.key != treasury_config_pda {
+        msg!("Error: TreasuryConfig account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if treasury_config_account.data_is_empty() {
+        msg!("Error: TreasuryConfig account is not initialized");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let mut config: TreasuryConfig = read_account_data(&treasury_config_account.try_borrow_data()?, TreasuryConfig::account_type())?;
+    config.instant_payout_haircut_bps = input.haircut_bps;
+
+    // A version 0 config is too small for the haircut
+    if treasury_config_account.data_len() < TreasuryConfig::LEN {
+        reallocate_account_if_needed(admin_account, treasury_config_account, system_program, &Rent::get()?, TreasuryConfig::LEN)?;
+    }
+
+    let mut data = treasury_config_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, TreasuryConfig::account_type(), &config)?;
+
+    emit(&DepinEvent::InstantPayoutHaircutSet { haircut_bps: config.instant_payout_haircut_bps });
+    msg!("Instant payout haircut set to {} bps", config.instant_payout_haircut_bps);
+    Ok(())
+}
@@ -1,54 +1,28 @@
-// use shared::utils::account::{write_account_data, read_account_data};
-// use shared::types::account::DepinAccountType;
-// use solana_program::{
-//     account_info::{next_account_info, AccountInfo},
-//     entrypoint::ProgramResult,
-//     msg,
-//     program_error::ProgramError,
-//     pubkey::Pubkey,
-// };
-
-// use crate::state;
-
-// pub fn process_close_infra_offer(
-//     program_id: &Pubkey,
-//     accounts: &[AccountInfo],
-//     _instruction_data: &[u8],
-// ) -> ProgramResult {
-//     // Expected Accounts:
-//     // 0. [signer] Provider
-//     // 1. [writable] Offer account (must be owned by the program)
-//     let account_info_iter = &mut accounts.iter();
-//     let provider_account = next_account_info(account_info_iter)?;
-//     let offer_account = next_account_info(account_info_iter)?;
-
-//     if !provider_account.is_signer {
-//         msg!("Error: Provider must sign the transaction");
-//         return Err(ProgramError::MissingRequiredSignature);
-//     }
-
-//     if offer_account.owner != program_id {
-//         msg!("Error: Offer account is not owned by the program");
-//         return Err(ProgramError::IncorrectProgramId);
-//     }
-
-//     if !offer_account.is_writable {
-//         msg!("Error: Offer account must be writable");
-//         return Err(ProgramError::InvalidAccountData);
-//     }
-
-//     let mut data = offer_account.try_borrow_mut_data()?;
-
-//     let mut offer: state::InfraOffer = read_account_data(&data, DepinAccountType::InfraOffer)?;
-
-//     if offer.provider != *provider_account.key {
-//         msg!("Error: Only the provider can close the offer");
-//         return Err(ProgramError::IllegalOwner);
-//     }
-
-//     offer.is_active = false;
-
-//     write_account_data(&mut data, DepinAccountType::InfraOffer, &offer)?;
-
-//     Ok(())
-// }
\ No newline at end of file
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+use shared::utils::account::close_account;
+use crate::utils::load_provider_offer;
+
+pub fn process_close_infra_offer<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Provider (receives the rent of the offer account)
+    // 1. [writable] InfraOffer PDA account (will be closed)
+    let account_info_iter = &mut accounts.iter();
+    let provider_account = next_account_info(account_info_iter)?;
+    let offer_account = next_account_info(account_info_iter)?;
+
+    let offer = load_provider_offer(program_id, provider_account, offer_account)?;
+
+    close_account(offer_account, provider_account)?;
+
+    msg!("Infra offer {} closed", offer.offer_id);
+    Ok(())
+}
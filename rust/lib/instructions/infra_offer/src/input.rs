@@ -0,0 +1,37 @@
+use solana_program::pubkey::Pubkey;
+use borsh::{BorshDeserialize, BorshSerialize};
+use shared::features::{bubblegum::cnft_context::CnftContext, infra_offer::accounts::{InfraOfferStatus, SlaTerms}};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct RegisterInfraOfferInput {
+    pub license_context: CnftContext,
+    pub offer_id: u16,
+    pub cpu: u64,
+    pub memory: u64,
+    pub region: String,
+    pub price_mint: Pubkey,
+    pub price_per_period: u64,
+    pub name: String,
+    pub description: String,
+    pub sla: Option<SlaTerms>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct UpdateInfraOfferInput {
+    pub cpu: u64,
+    pub memory: u64,
+    pub region: String,
+    pub price_mint: Pubkey,
+    pub price_per_period: u64,
+    pub name: String,
+    pub description: String,
+    pub sla: Option<SlaTerms>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetInfraOfferStatusInput {
+    pub status: InfraOfferStatus,
+}
@@ -1,6 +1,11 @@
-// mod state;
-// mod register;
-// mod close;
+pub mod input;
+mod utils;
+mod register;
+mod update;
+mod status;
+mod close;
 
-// pub use register::process_register_infra_offer;
-// pub use close::process_close_infra_offer;
\ No newline at end of file
+pub use register::process_register_infra_offer;
+pub use update::process_update_infra_offer;
+pub use status::process_set_infra_offer_status;
+pub use close::process_close_infra_offer;
@@ -1,62 +1,161 @@
-// use borsh::BorshDeserialize;
-// use shared::utils::account::write_account_data;
-// use shared::types::account::DepinAccountType;
-// use solana_program::{
-//     account_info::{next_account_info, AccountInfo},
-//     entrypoint::ProgramResult,
-//     msg,
-//     program_error::ProgramError,
-//     pubkey::Pubkey,
-// };
-
-// use crate::state;
-
-// pub fn process_register_infra_offer(
-//     program_id: &Pubkey,
-//     accounts: &[AccountInfo],
-//     instruction_data: &[u8],
-// ) -> ProgramResult {
-//     // Expected Accounts:
-//     // 0. [signer] Provider
-//     // 1. [writable] Offer account (must be owned by the program)
-//     let account_info_iter = &mut accounts.iter();
-//     let provider_account = next_account_info(account_info_iter)?;
-//     let offer_account = next_account_info(account_info_iter)?;
-
-//     if !provider_account.is_signer {
-//         msg!("Error: Provider must sign the transaction");
-//         return Err(ProgramError::MissingRequiredSignature);
-//     }
-
-//     if offer_account.owner != program_id {
-//         msg!("Error: Offer account is not owned by the program");
-//         return Err(ProgramError::IncorrectProgramId);
-//     }
-
-//     if !offer_account.is_writable {
-//         msg!("Error: Offer account must be writable");
-//         return Err(ProgramError::InvalidAccountData);
-//     }
-
-//     let input = state::InfraOfferInput::try_from_slice(instruction_data)?;
-
-//     let offer = state::InfraOffer {
-//         provider: *provider_account.key,
-//         name: input.name,
-//         description: input.description,
-//         cpu: input.cpu,
-//         memory: input.memory,
-//         price: input.price,
-//         is_active: true,
-//     };
-
-//     let mut data = offer_account.try_borrow_mut_data()?;
-
-//     if data[0] > 0 {
-//         msg!("Error: Offer account is already initialized");
-//         return Err(ProgramError::AccountAlreadyInitialized);
-//     }
-
-//     write_account_data(&mut data, DepinAccountType::InfraOffer, &offer)?;
-//     Ok(())
-// }
\ No newline at end of file
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{INFRA_SEED, OFFER_SEED},
+    features::{
+        infra_offer::accounts::{InfraOffer, InfraOfferStatus},
+        worker::utils::validate_active_worker,
+    },
+    utils::{
+        account::{create_pda_account, read_account_data, write_account_data},
+        bgum::verify_license_and_owner,
+        bmb::validate_worker_tree,
+    },
+};
+use crate::{
+    input,
+    utils::validate_price_mint,
+};
+
+pub fn process_register_infra_offer<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Provider (worker license owner, pays for the offer account)
+    // 1. [writable] InfraOffer PDA account (will be created, or taken over from a previous license owner)
+    // 2. [readonly] WorkerMetadata PDA account of the provider
+    // 3. [readonly] WorkerLicenseMetadata PDA account
+    // 4. [readonly] mpl_account_compression program
+    // 5. [readonly] Merkle tree account
+    // 6. [readonly] System program account (for account creation)
+    // 7. [readonly] AllowedMints PDA account
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let provider_account = next_account_info(account_info_iter)?;
+    let offer_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    let input = input::RegisterInfraOfferInput::try_from_slice(instruction_data)?;
+    let license = &input.license_context;
+
+    // Calculate the leaf PDA (worker license)
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    // Verify tree, leaf and that the provider currently owns the license
+    validate_worker_tree(merkle_tree_account.key)?;
+    verify_license_and_owner(
+        merkle_tree_account,
+        &proof_accounts,
+        license,
+        license_leaf.hash(),
+        provider_account,
+    )?;
+
+    validate_active_worker(
+        program_id,
+        worker_metadata_account,
+        worker_license_metadata_account,
+        &leaf_asset_id,
+        provider_account.key,
+    )?;
+
+    if input.offer_id >= InfraOffer::MAX_OFFERS_PER_LICENSE {
+        msg!("Error: Offer id must be below {}", InfraOffer::MAX_OFFERS_PER_LICENSE);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    InfraOffer::validate_terms(
+        input.cpu,
+        input.memory,
+        &input.region,
+        input.price_per_period,
+        &input.name,
+        &input.description,
+    )?;
+    validate_price_mint(program_id, allowed_mints_account, &input.price_mint)?;
+    if let Some(sla) = &input.sla {
+        sla.validate()?;
+    }
+
+    let (offer_pda, bump_seed) = InfraOffer::find_pda(program_id, &leaf_asset_id, input.offer_id);
+
+    // Validate InfraOffer PDA
+    if *offer_account.key != offer_pda {
+        msg!("Error: InfraOffer account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if offer_account.data_is_empty() {
+        create_pda_account(
+            provider_account,
+            offer_account,
+            system_program,
+            program_id,
+            InfraOffer::LEN,
+            &[INFRA_SEED, OFFER_SEED, leaf_asset_id.as_ref(), &input.offer_id.to_le_bytes(), &[bump_seed]],
+        )?;
+    } else {
+        // Offers of a previous license owner are taken over with their rent, the provider proved
+        // above that it owns the license now
+        let existing: InfraOffer = read_account_data(
+            &offer_account.try_borrow_data()?,
+            InfraOffer::account_type(),
+        )?;
+        if existing.provider == *provider_account.key {
+            msg!("Error: Offer {} is already registered for this license", input.offer_id);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        msg!("Taking over offer {} of the previous license owner {}", input.offer_id, existing.provider);
+    }
+
+    let offer = InfraOffer {
+        provider: *provider_account.key,
+        worker_license: leaf_asset_id,
+        offer_id: input.offer_id,
+        status: InfraOfferStatus::Active,
+        cpu: input.cpu,
+        memory: input.memory,
+        region: input.region,
+        price_mint: input.price_mint,
+        price_per_period: input.price_per_period,
+        name: input.name,
+        description: input.description,
+        sla: input.sla,
+    };
+
+    let mut data = offer_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, InfraOffer::account_type(), &offer)?;
+
+    msg!("Infra offer {} registered at {}", offer.offer_id, offer_pda);
+    Ok(())
+}
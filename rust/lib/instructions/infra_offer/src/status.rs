@@ -0,0 +1,63 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::{
+        infra_offer::accounts::{InfraOffer, InfraOfferStatus},
+        worker::utils::validate_active_worker,
+    },
+    utils::account::write_account_data,
+};
+use crate::{
+    input,
+    utils::load_provider_offer,
+};
+
+pub fn process_set_infra_offer_status(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Provider
+    // 1. [writable] InfraOffer PDA account
+    // 2. [readonly] WorkerMetadata PDA account of the provider (checked when resuming)
+    // 3. [readonly] WorkerLicenseMetadata PDA account (checked when resuming)
+    let account_info_iter = &mut accounts.iter();
+    let provider_account = next_account_info(account_info_iter)?;
+    let offer_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+
+    let input = input::SetInfraOfferStatusInput::try_from_slice(instruction_data)?;
+
+    let mut offer = load_provider_offer(program_id, provider_account, offer_account)?;
+
+    if offer.status == input.status {
+        msg!("Infra offer {} already has status {:?}", offer.offer_id, offer.status);
+        return Ok(());
+    }
+
+    // A suspended worker can still pause its offers, but not put them back on the market
+    if input.status == InfraOfferStatus::Active {
+        validate_active_worker(
+            program_id,
+            worker_metadata_account,
+            worker_license_metadata_account,
+            &offer.worker_license,
+            provider_account.key,
+        )?;
+    }
+
+    offer.status = input.status;
+
+    let mut data = offer_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, InfraOffer::account_type(), &offer)?;
+
+    msg!("Infra offer {} status set to {:?}", offer.offer_id, offer.status);
+    Ok(())
+}
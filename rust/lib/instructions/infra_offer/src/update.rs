@@ -0,0 +1,66 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::infra_offer::accounts::InfraOffer,
+    utils::account::write_account_data,
+};
+use crate::{
+    input,
+    utils::{load_provider_offer, validate_price_mint},
+};
+
+pub fn process_update_infra_offer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Provider
+    // 1. [writable] InfraOffer PDA account
+    // 2. [readonly] AllowedMints PDA account
+    let account_info_iter = &mut accounts.iter();
+    let provider_account = next_account_info(account_info_iter)?;
+    let offer_account = next_account_info(account_info_iter)?;
+    let allowed_mints_account = next_account_info(account_info_iter)?;
+
+    let input = input::UpdateInfraOfferInput::try_from_slice(instruction_data)?;
+
+    let mut offer = load_provider_offer(program_id, provider_account, offer_account)?;
+
+    InfraOffer::validate_terms(
+        input.cpu,
+        input.memory,
+        &input.region,
+        input.price_per_period,
+        &input.name,
+        &input.description,
+    )?;
+    validate_price_mint(program_id, allowed_mints_account, &input.price_mint)?;
+    if let Some(sla) = &input.sla {
+        sla.validate()?;
+    }
+
+    // Identity and status are kept, only the advertised terms change.
+    // Open leases keep the SLA they were opened with.
+    offer.cpu = input.cpu;
+    offer.memory = input.memory;
+    offer.region = input.region;
+    offer.price_mint = input.price_mint;
+    offer.price_per_period = input.price_per_period;
+    offer.name = input.name;
+    offer.description = input.description;
+    offer.sla = input.sla;
+
+    // The account is sized for the longest strings, so stale bytes are cleared before writing
+    let mut data = offer_account.try_borrow_mut_data()?;
+    data.fill(0);
+    write_account_data(&mut data, InfraOffer::account_type(), &offer)?;
+
+    msg!("Infra offer {} updated", offer.offer_id);
+    Ok(())
+}
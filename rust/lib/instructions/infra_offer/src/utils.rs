@@ -0,0 +1,56 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::{escrow::utils::load_allowed_mints, infra_offer::accounts::InfraOffer},
+    utils::account::read_account_data,
+};
+
+/// Offers are priced in the same mints the escrow accepts, a paused mint can still be quoted
+pub(crate) fn validate_price_mint(
+    program_id: &Pubkey,
+    allowed_mints_account: &AccountInfo,
+    price_mint: &Pubkey,
+) -> ProgramResult {
+    let allowed_mints = load_allowed_mints(program_id, allowed_mints_account)?;
+
+    if allowed_mints.get(price_mint).is_none() {
+        msg!("Error: Offers must be priced in a mint the escrow accepts, {} is not allowed", price_mint);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Loads an offer owned by this program after checking the signer is its provider
+pub(crate) fn load_provider_offer(
+    program_id: &Pubkey,
+    provider_account: &AccountInfo,
+    offer_account: &AccountInfo,
+) -> Result<InfraOffer, ProgramError> {
+    if !provider_account.is_signer {
+        msg!("Error: Provider must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if offer_account.owner != program_id {
+        msg!("Error: Offer account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let offer: InfraOffer = read_account_data(
+        &offer_account.try_borrow_data()?,
+        InfraOffer::account_type(),
+    )?;
+
+    if offer.provider != *provider_account.key {
+        msg!("Error: Only the provider can manage the offer");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(offer)
+}
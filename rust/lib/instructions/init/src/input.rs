@@ -1,5 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 
+#[allow(dead_code)]
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct InitInput {
 }
\ No newline at end of file
@@ -1,11 +1,12 @@
 use shared::constants::seeds::{GLOBAL_REWARDS_SEED, GLOBAL_SEED, TREASURY_SEED, STATE_SEED};
+use shared::events::{emit, DepinEvent};
 use shared::features::rewards::accounts::GlobalRewards;
 use shared::features::treasury::accounts::{TreasuryState, TreasuryConfig};
 use shared::types::account::DepinAccountType;
 use shared::utils::account::write_account_data;
 use solana_program::program::invoke_signed;
 use solana_program::rent::Rent;
-use solana_program::{system_instruction};
+use solana_system_interface::instruction as system_instruction;
 use solana_program::sysvar::Sysvar;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -128,7 +129,7 @@ fn init_global_rewards<'a>(
         }
         
         // Realloc to target size
-        global_rewards_account.realloc(target_len, false)?;
+        global_rewards_account.resize(target_len)?;
         
         // If we've reached final size, initialize
         if target_len == GlobalRewards::LEN {
@@ -137,7 +138,7 @@ fn init_global_rewards<'a>(
             msg!("Initialization done");
         } else {
             // Need more calls to reach final size
-            let remaining_calls = ((GlobalRewards::LEN - target_len) + MAX_INCREASE - 1) / MAX_INCREASE;
+            let remaining_calls = (GlobalRewards::LEN - target_len).div_ceil(MAX_INCREASE);
             msg!("Call {} more time(s) to complete resizing", remaining_calls);
         }
     } else {
@@ -263,6 +264,10 @@ fn init_treasury_config<'a>(
     let mut data = treasury_config_account.try_borrow_mut_data()?;
     write_account_data(&mut data, TreasuryConfig::account_type(), &config)?;
 
+    emit(&DepinEvent::TreasuryConfigInitialized {
+        checker_rewards_lock_days: config.checker_rewards_lock_days,
+    });
+
     msg!("TreasuryConfig created and initialized successfully");
     Ok(())
 }
@@ -0,0 +1,104 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{LEASE_SEED, VAULT_SEED},
+    events::{emit, DepinEvent},
+    features::{
+        escrow::utils::validate_escrow_token_account,
+        lease::accounts::{Lease, LeaseStatus},
+    },
+    utils::{
+        account::write_account_data,
+        bmb::get_current_period,
+        token::transfer_signed,
+    },
+};
+use crate::claim::{load_lease, read_lease_mint, validate_lease_vault};
+
+pub fn process_cancel_lease<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Buyer
+    // 1. [writable] Lease PDA account
+    // 2. [writable] Lease vault token account
+    // 3. [writable] Buyer's escrow token account (receives the refund)
+    // 4. [readonly] Token program account
+    // 5. [readonly] Mint account of the lease
+    let account_info_iter = &mut accounts.iter();
+    let buyer_account = next_account_info(account_info_iter)?;
+    let lease_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+
+    if !buyer_account.is_signer {
+        msg!("Error: Buyer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut lease = load_lease(program_id, lease_account)?;
+
+    if lease.buyer != *buyer_account.key {
+        msg!("Error: Only the buyer can cancel the lease");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    if lease.status == LeaseStatus::Cancelled {
+        msg!("Error: Lease {} is already cancelled", lease.lease_id);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    validate_escrow_token_account(program_id, escrow_token_account, &lease.buyer, &lease.mint)?;
+    let vault_bump = validate_lease_vault(program_id, lease_account.key, vault_account)?;
+    let mint = read_lease_mint(&lease, mint_account)?;
+
+    // Periods that already started stay payable to the provider, later ones go back to the buyer
+    let current_period = get_current_period()?;
+    let first_refunded = lease.start_period.max(current_period.saturating_add(1));
+
+    let mut refunded_periods: u64 = 0;
+    for period in first_refunded..lease.end_period() {
+        if !lease.is_settled(period) {
+            lease.mark_settled(period);
+            refunded_periods += 1;
+        }
+    }
+
+    let refund = lease.price_per_period * refunded_periods;
+    lease.status = LeaseStatus::Cancelled;
+
+    if refund > 0 {
+        transfer_signed(
+            token_program,
+            vault_account,
+            mint_account,
+            escrow_token_account,
+            vault_account,
+            refund,
+            mint.decimals,
+            &[LEASE_SEED, VAULT_SEED, lease_account.key.as_ref(), &[vault_bump]],
+        )?;
+
+        emit(&DepinEvent::EscrowRefunded {
+            owner: lease.buyer,
+            mint: lease.mint,
+            lease: *lease_account.key,
+            amount: refund,
+        });
+    }
+
+    let mut data = lease_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, Lease::account_type(), &lease)?;
+
+    msg!("Lease {} cancelled, {} periods refunded ({})", lease.lease_id, refunded_periods, refund);
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{LEASE_SEED, VAULT_SEED},
+    features::{
+        lease::accounts::{Lease, LeaseVault},
+        worker::accounts::{WorkerMetadata, WorkerProof},
+    },
+    utils::{
+        account::{read_account_data, write_account_data},
+        bgum::verify_license_and_owner,
+        bmb::validate_worker_tree,
+        token::{read_mint, read_token_account, transfer_signed, MintDetails, validate_token_account_owner},
+    },
+};
+use crate::input;
+
+pub fn process_claim_lease_payment<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Current owner of the leased worker license
+    // 1. [writable] Lease PDA account
+    // 2. [writable] Lease vault token account
+    // 3. [writable] Token account of the worker's reward recipient (owner or beneficiary) for the lease mint
+    // 4. [readonly] WorkerMetadata PDA account of the license owner
+    // 5. [readonly] WorkerProof PDA account of the leased worker for the claimed period
+    // 6. [readonly] Token program account
+    // 7. [readonly] Mint account of the lease
+    // 8. [readonly] mpl_account_compression program
+    // 9. [readonly] Merkle tree account of the worker license
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let lease_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_proof_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    if !owner_account.is_signer {
+        msg!("Error: License owner must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::ClaimLeasePaymentInput::try_from_slice(instruction_data)?;
+
+    let mut lease = load_lease(program_id, lease_account)?;
+
+    // Payments follow the license, whoever owns it when claiming is paid through its WorkerMetadata
+    let license = &input.license_context;
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+    if leaf_asset_id != lease.worker_license {
+        msg!("Error: License does not match the lease worker license");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    validate_worker_tree(merkle_tree_account.key)?;
+    verify_license_and_owner(
+        merkle_tree_account,
+        &proof_accounts,
+        license,
+        license_leaf.hash(),
+        owner_account,
+    )?;
+
+    if !lease.covers(input.period) {
+        msg!("Error: Period {} is outside of the lease", input.period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if lease.is_settled(input.period) {
+        msg!("Error: Period {} has already been settled", input.period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Payment for a period is only released once the worker proved it was serving
+    let Some(worker_proof) = load_worker_proof(program_id, worker_proof_account, &lease.worker_license, input.period)? else {
+        msg!("Error: No worker proof was submitted for period {}", input.period);
+        return Err(ProgramError::UninitializedAccount);
+    };
+
+    // A breached period pays out only what is left after the crank refunded the penalty
+    let payment = match lease.sla {
+        Some(sla) if lease.is_penalized(input.period) => lease.price_per_period - sla.penalty(lease.price_per_period),
+        Some(sla) if sla.is_breached_by(&worker_proof) => {
+            msg!("Error: Period {} breached the SLA, the penalty must be enforced before claiming", input.period);
+            return Err(ProgramError::InvalidAccountData);
+        },
+        _ => lease.price_per_period,
+    };
+
+    let (worker_metadata_pda, _) = WorkerMetadata::find_pda(program_id, &lease.worker_license, owner_account.key);
+    if *worker_metadata_account.key != worker_metadata_pda || worker_metadata_account.owner != program_id {
+        msg!("Error: WorkerMetadata account does not match the license owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let worker_metadata: WorkerMetadata = read_account_data(
+        &worker_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    let recipient_token_state = read_token_account(recipient_token_account)?;
+    validate_token_account_owner(&recipient_token_state, &worker_metadata.reward_recipient(), &lease.mint)?;
+
+    let vault_bump = validate_lease_vault(program_id, lease_account.key, vault_account)?;
+    let mint = read_lease_mint(&lease, mint_account)?;
+
+    lease.mark_settled(input.period);
+
+    transfer_signed(
+        token_program,
+        vault_account,
+        mint_account,
+        recipient_token_account,
+        vault_account,
+        payment,
+        mint.decimals,
+        &[LEASE_SEED, VAULT_SEED, lease_account.key.as_ref(), &[vault_bump]],
+    )?;
+
+    let mut data = lease_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, Lease::account_type(), &lease)?;
+
+    msg!("Lease {} paid {} for period {}", lease.lease_id, payment, input.period);
+    Ok(())
+}
+
+/// Reads the WorkerProof of the leased worker for `period`, `None` if none was submitted
+pub(crate) fn load_worker_proof(
+    program_id: &Pubkey,
+    worker_proof_account: &AccountInfo,
+    worker_license: &Pubkey,
+    period: u16,
+) -> Result<Option<WorkerProof>, ProgramError> {
+    let (worker_proof_pda, _) = WorkerProof::find_pda(program_id, worker_license, period);
+    if *worker_proof_account.key != worker_proof_pda {
+        msg!("Error: WorkerProof account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if worker_proof_account.data_is_empty() {
+        return Ok(None);
+    }
+
+    let worker_proof: WorkerProof = read_account_data(
+        &worker_proof_account.try_borrow_data()?,
+        WorkerProof::account_type(),
+    )?;
+
+    if worker_proof.period != period {
+        msg!("Error: WorkerProof period does not match the requested period");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(Some(worker_proof))
+}
+
+/// Reads the lease mint after checking the account matches it
+pub(crate) fn read_lease_mint(lease: &Lease, mint_account: &AccountInfo) -> Result<MintDetails, ProgramError> {
+    if *mint_account.key != lease.mint {
+        msg!("Error: Mint account does not match the lease mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+    read_mint(mint_account)
+}
+
+/// Reads a Lease after checking it is owned by the program
+pub(crate) fn load_lease(program_id: &Pubkey, lease_account: &AccountInfo) -> Result<Lease, ProgramError> {
+    if lease_account.owner != program_id {
+        msg!("Error: Lease account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    read_account_data(&lease_account.try_borrow_data()?, Lease::account_type())
+}
+
+/// Checks the vault is the token PDA of the lease and returns its bump
+pub(crate) fn validate_lease_vault(
+    program_id: &Pubkey,
+    lease: &Pubkey,
+    vault_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (vault_pda, vault_bump) = LeaseVault::find_pda(program_id, lease);
+    if *vault_account.key != vault_pda {
+        msg!("Error: Lease vault account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(vault_bump)
+}
@@ -0,0 +1,24 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shared::features::bubblegum::cnft_context::CnftContext;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct OpenLeaseInput {
+    pub license_context: CnftContext,
+    pub lease_id: u64,
+    pub start_period: u16,
+    pub periods: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct ClaimLeasePaymentInput {
+    pub license_context: CnftContext,
+    pub period: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct EnforceLeaseSlaInput {
+    pub period: u16,
+}
@@ -0,0 +1,10 @@
+pub mod input;
+mod open;
+mod claim;
+mod cancel;
+mod sla;
+
+pub use open::process_open_lease;
+pub use claim::process_claim_lease_payment;
+pub use cancel::process_cancel_lease;
+pub use sla::process_enforce_lease_sla;
@@ -0,0 +1,246 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{LEASE_SEED, VAULT_SEED},
+    features::{
+        escrow::utils::{load_escrow_state, transfer_from_escrow, validate_escrow_token_account},
+        infra_offer::accounts::InfraOffer,
+        lease::accounts::{Lease, LeaseStatus, LeaseVault},
+        worker::utils::validate_active_worker,
+    },
+    utils::{
+        account::{create_pda_account, read_account_data, write_account_data},
+        bgum::verify_license,
+        bmb::{get_current_period, validate_worker_tree},
+        token::{create_pda_token_account, read_mint, read_token_account},
+    },
+};
+use crate::input;
+
+pub fn process_open_lease<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Buyer (pays for the lease accounts)
+    // 1. [readonly] InfraOffer PDA account
+    // 2. [writable] Lease PDA account (will be created)
+    // 3. [writable] Buyer's escrow token account (ESCROW_SEED, TOKEN_SEED, buyer, mint)
+    // 4. [writable] Lease vault token account (will be created)
+    // 5. [readonly] Mint account (must be the offer's price mint)
+    // 6. [readonly] Token program account
+    // 7. [readonly] System program account (for account creation)
+    // 8. [readonly] Buyer's EscrowState PDA account for the mint (may be empty)
+    // 9. [readonly] WorkerMetadata PDA account of the offer provider
+    // 10. [readonly] WorkerLicenseMetadata PDA account
+    // 11. [readonly] mpl_account_compression program
+    // 12. [readonly] Merkle tree account of the worker license
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let buyer_account = next_account_info(account_info_iter)?;
+    let offer_account = next_account_info(account_info_iter)?;
+    let lease_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    if !buyer_account.is_signer {
+        msg!("Error: Buyer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::OpenLeaseInput::try_from_slice(instruction_data)?;
+
+    if offer_account.owner != program_id {
+        msg!("Error: Offer account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let offer: InfraOffer = read_account_data(
+        &offer_account.try_borrow_data()?,
+        InfraOffer::account_type(),
+    )?;
+
+    if !offer.is_active() {
+        msg!("Error: Infra offer {} is paused", offer.offer_id);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The offer stays with the key that registered it, so it is only sold while that key
+    // still owns the license and runs an active worker for it
+    let license = &input.license_context;
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+    if leaf_asset_id != offer.worker_license {
+        msg!("Error: License does not match the offer worker license");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    validate_worker_tree(merkle_tree_account.key)?;
+    verify_license(
+        merkle_tree_account,
+        &proof_accounts,
+        license.root,
+        license_leaf.hash(),
+        license.index,
+    )?;
+
+    if license.owner != offer.provider {
+        msg!("Error: The offer provider no longer owns the worker license");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    validate_active_worker(
+        program_id,
+        worker_metadata_account,
+        worker_license_metadata_account,
+        &offer.worker_license,
+        &offer.provider,
+    )?;
+
+    if *mint_account.key != offer.price_mint {
+        msg!("Error: Mint account does not match the offer price mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if input.periods == 0 || input.periods > Lease::MAX_PERIODS {
+        msg!("Error: Lease must cover between 1 and {} periods", Lease::MAX_PERIODS);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Leases can start now or later, never in the past
+    let current_period = get_current_period()?;
+    if input.start_period < current_period {
+        msg!("Error: Lease cannot start before the current period {}", current_period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if input.start_period.checked_add(input.periods).is_none() {
+        msg!("Error: Lease end period overflows");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let total_price = offer.price_per_period
+        .checked_mul(input.periods as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let escrow_bump = validate_escrow_token_account(
+        program_id,
+        escrow_token_account,
+        buyer_account.key,
+        &offer.price_mint,
+    )?;
+
+    // With a transfer-fee mint the buyer also pays the fee, so the vault holds the full price
+    let mint = read_mint(mint_account)?;
+    let transfer_amount = mint.gross_up(total_price)?;
+
+    // Funds backing payment streams cannot be moved into a lease vault
+    let escrow_state = read_token_account(escrow_token_account)?;
+    let commitments = load_escrow_state(program_id, escrow_state_account, buyer_account.key, &offer.price_mint)?;
+    let available = commitments.unencumbered(escrow_state.amount);
+    if available < transfer_amount {
+        msg!("Error: Insufficient escrow balance. Available: {}, Required: {}", available, transfer_amount);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Validate Lease PDA
+    let (lease_pda, lease_bump) = Lease::find_pda(program_id, offer_account.key, buyer_account.key, input.lease_id);
+    if *lease_account.key != lease_pda {
+        msg!("Error: Lease account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !lease_account.data_is_empty() {
+        msg!("Error: Lease {} already exists", input.lease_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // Validate lease vault PDA
+    let (vault_pda, vault_bump) = LeaseVault::find_pda(program_id, &lease_pda);
+    if *vault_account.key != vault_pda {
+        msg!("Error: Lease vault account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    create_pda_account(
+        buyer_account,
+        lease_account,
+        system_program,
+        program_id,
+        Lease::LEN,
+        &[LEASE_SEED, offer_account.key.as_ref(), buyer_account.key.as_ref(), &input.lease_id.to_le_bytes(), &[lease_bump]],
+    )?;
+
+    create_pda_token_account(
+        buyer_account,
+        vault_account,
+        mint_account,
+        system_program,
+        token_program,
+        &[LEASE_SEED, VAULT_SEED, lease_pda.as_ref(), &[vault_bump]],
+    )?;
+
+    transfer_from_escrow(
+        token_program,
+        escrow_token_account,
+        mint_account,
+        vault_account,
+        buyer_account.key,
+        escrow_bump,
+        transfer_amount,
+        mint.decimals,
+    )?;
+
+    let lease = Lease {
+        offer: *offer_account.key,
+        provider: offer.provider,
+        worker_license: offer.worker_license,
+        buyer: *buyer_account.key,
+        mint: offer.price_mint,
+        lease_id: input.lease_id,
+        price_per_period: offer.price_per_period,
+        start_period: input.start_period,
+        periods: input.periods,
+        settled_mask: 0,
+        penalized_mask: 0,
+        status: LeaseStatus::Active,
+        sla: offer.sla,
+    };
+
+    let mut data = lease_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, Lease::account_type(), &lease)?;
+
+    msg!("Lease {} opened for periods {}..{}, {} locked", input.lease_id, lease.start_period, lease.end_period(), total_price);
+    Ok(())
+}
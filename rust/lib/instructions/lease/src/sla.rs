@@ -0,0 +1,121 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::{LEASE_SEED, VAULT_SEED},
+    events::{emit, DepinEvent},
+    features::{escrow::utils::validate_escrow_token_account, lease::accounts::Lease},
+    utils::{
+        account::write_account_data,
+        bmb::get_current_period,
+        token::transfer_signed,
+    },
+};
+use crate::{
+    claim::{load_lease, load_worker_proof, read_lease_mint, validate_lease_vault},
+    input,
+};
+
+pub fn process_enforce_lease_sla<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Cranker (permissionless)
+    // 1. [writable] Lease PDA account
+    // 2. [writable] Lease vault token account
+    // 3. [writable] Buyer's escrow token account (receives the refund)
+    // 4. [readonly] WorkerProof PDA account of the leased worker for the enforced period (may be empty)
+    // 5. [readonly] Token program account
+    // 6. [readonly] Mint account of the lease
+    let account_info_iter = &mut accounts.iter();
+    let cranker_account = next_account_info(account_info_iter)?;
+    let lease_account = next_account_info(account_info_iter)?;
+    let vault_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let worker_proof_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+
+    if !cranker_account.is_signer {
+        msg!("Error: Cranker must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::EnforceLeaseSlaInput::try_from_slice(instruction_data)?;
+
+    let mut lease = load_lease(program_id, lease_account)?;
+
+    if !lease.covers(input.period) {
+        msg!("Error: Period {} is outside of the lease", input.period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if lease.is_settled(input.period) || lease.is_penalized(input.period) {
+        msg!("Error: Period {} has already been settled", input.period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    validate_escrow_token_account(program_id, escrow_token_account, &lease.buyer, &lease.mint)?;
+    let vault_bump = validate_lease_vault(program_id, lease_account.key, vault_account)?;
+    let mint = read_lease_mint(&lease, mint_account)?;
+
+    let refund = match load_worker_proof(program_id, worker_proof_account, &lease.worker_license, input.period)? {
+        None => {
+            // Proofs for a period are only accepted during the next one, after that the period is lost
+            let current_period = get_current_period()?;
+            if current_period <= input.period.saturating_add(1) {
+                msg!("Error: The proof window for period {} is still open", input.period);
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            lease.mark_settled(input.period);
+            msg!("No proof for period {}, refunding the full period", input.period);
+            lease.price_per_period
+        },
+        Some(worker_proof) => {
+            let breached = lease.sla.filter(|sla| sla.is_breached_by(&worker_proof));
+            let Some(sla) = breached else {
+                msg!("Error: Period {} met the lease SLA, nothing to enforce", input.period);
+                return Err(ProgramError::InvalidArgument);
+            };
+
+            // The rest of the period stays claimable by the provider
+            lease.mark_penalized(input.period);
+            msg!("Period {} breached the SLA (uptime {}, latency {})", input.period, worker_proof.uptime, worker_proof.latency);
+            sla.penalty(lease.price_per_period)
+        },
+    };
+
+    if refund > 0 {
+        transfer_signed(
+            token_program,
+            vault_account,
+            mint_account,
+            escrow_token_account,
+            vault_account,
+            refund,
+            mint.decimals,
+            &[LEASE_SEED, VAULT_SEED, lease_account.key.as_ref(), &[vault_bump]],
+        )?;
+
+        emit(&DepinEvent::EscrowRefunded {
+            owner: lease.buyer,
+            mint: lease.mint,
+            lease: *lease_account.key,
+            amount: refund,
+        });
+    }
+
+    let mut data = lease_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, Lease::account_type(), &lease)?;
+
+    msg!("Lease {} refunded {} to the buyer for period {}", lease.lease_id, refund, input.period);
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod migrate_account;
+
+pub use migrate_account::process_migrate_account;
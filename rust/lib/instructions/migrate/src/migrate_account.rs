@@ -0,0 +1,110 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use shared::{
+    events::{emit, DepinEvent},
+    features::{
+        checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
+        escrow::accounts::{EscrowAuthority, EscrowState},
+        global::accounts::{AllowedMints, BMBState, PauseState},
+        infra_offer::accounts::InfraOffer,
+        lease::accounts::Lease,
+        payment_stream::accounts::PaymentStream,
+        treasury::accounts::{LockedTokens, TreasuryConfig, TreasuryState},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+    },
+    types::account::{DepinAccount, DepinAccountType},
+    utils::account::{read_account_data, reallocate_account_if_needed, write_account_data, AccountHeader},
+};
+
+pub fn process_migrate_account<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Payer (permissionless, pays for any growth of the account)
+    // 1. [writable] Program account to migrate
+    // 2. [readonly] System program account (for the rent transfer)
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?;
+    let target_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !payer_account.is_signer {
+        msg!("Error: Payer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if target_account.owner != program_id {
+        msg!("Error: Account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let header = AccountHeader::read(&target_account.try_borrow_data()?)?;
+    let Ok(account_type) = DepinAccountType::try_from(header.account_type) else {
+        msg!("Error: Unknown account type {}", header.account_type);
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    let to_version = match account_type {
+        DepinAccountType::WorkerMetadata => migrate::<WorkerMetadata>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::WorkerLicenseMetadata => migrate::<WorkerLicenseMetadata>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::WorkerProof => migrate::<WorkerProof>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::BMBState => migrate::<BMBState>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::CheckerMetadata => migrate::<CheckerMetadata>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::CheckerLicenseMetadata => migrate::<CheckerLicenseMetadata>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::TreasuryState => migrate::<TreasuryState>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::LockedTokens => migrate::<LockedTokens>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::TreasuryConfig => migrate::<TreasuryConfig>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::InfraOffer => migrate::<InfraOffer>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::Lease => migrate::<Lease>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::PaymentStream => migrate::<PaymentStream>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::AllowedMints => migrate::<AllowedMints>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::EscrowAuthority => migrate::<EscrowAuthority>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::EscrowState => migrate::<EscrowState>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::PauseState => migrate::<PauseState>(payer_account, target_account, system_program, header)?,
+        DepinAccountType::GlobalRewards => {
+            msg!("Error: GlobalRewards is a fixed zero-copy layout and has no versions");
+            return Err(ProgramError::InvalidArgument);
+        }
+    };
+
+    emit(&DepinEvent::AccountMigrated {
+        account: *target_account.key,
+        account_type: header.account_type,
+        from_version: header.version,
+        to_version,
+    });
+
+    msg!("Account {} migrated from version {} to {}", target_account.key, header.version, to_version);
+    Ok(())
+}
+
+/// Rewrites the account at `T::VERSION`, growing it first when the new layout needs more space
+fn migrate<'info, T: DepinAccount>(
+    payer_account: &'info AccountInfo<'info>,
+    target_account: &'info AccountInfo<'info>,
+    system_program: &'info AccountInfo<'info>,
+    header: AccountHeader,
+) -> Result<u8, ProgramError> {
+    if header.version == T::VERSION {
+        msg!("Error: Account is already at version {}", T::VERSION);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let account_data: T = read_account_data(&target_account.try_borrow_data()?, T::ACCOUNT_TYPE)?;
+
+    // Never shrink, the refund of a smaller account would go to the permissionless payer
+    let required_space = account_data.space().max(target_account.data_len());
+    reallocate_account_if_needed(payer_account, target_account, system_program, &Rent::get()?, required_space)?;
+
+    let mut data = target_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, T::ACCOUNT_TYPE, &account_data)?;
+    Ok(T::VERSION)
+}
@@ -0,0 +1,145 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::{
+        escrow::{accounts::EscrowState, utils::load_escrow_state},
+        payment_stream::accounts::PaymentStream,
+    },
+    utils::{
+        account::{close_account, read_account_data, write_account_data},
+        bmb::get_current_period,
+    },
+};
+use crate::{
+    input,
+    settle::{load_stream_worker, settle_next_period, stop_stream, PaymentAccounts, Settlement},
+};
+
+pub fn process_close_payment_stream<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Payer (receives the rent of the stream account)
+    // 1. [writable] PaymentStream PDA account (will be closed)
+    // 2. [writable] Payer's EscrowState PDA account for the stream mint
+    // 3. [writable] Payer's escrow token account
+    // 4. [readonly] WorkerMetadata PDA account of the current worker owner
+    // 5. [readonly] WorkerLicenseMetadata PDA account
+    // 6. [writable] Token account of the worker's reward recipient (owner or beneficiary) for the stream mint
+    // 7. [readonly] Token program account
+    // 8. [readonly] Mint account of the stream
+    // 9. [readonly] mpl_account_compression program
+    // 10. [readonly] Merkle tree account of the worker license
+    // N. [readonly] WorkerProof PDA accounts of the finished periods not settled yet, in order
+    // M. [readonly] Proof accounts of the worker license as remaining accounts
+    // Accounts 3 to 10 and the license proof are only checked when finished periods are settled
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?;
+    let stream_account = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+
+    if !payer_account.is_signer {
+        msg!("Error: Payer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if stream_account.owner != program_id {
+        msg!("Error: PaymentStream account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut stream: PaymentStream = read_account_data(
+        &stream_account.try_borrow_data()?,
+        PaymentStream::account_type(),
+    )?;
+
+    if stream.payer != *payer_account.key {
+        msg!("Error: Only the payer can close the payment stream");
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    // Finished periods are owed to the worker under the crank rules before the stream can go away
+    let current_period = get_current_period()?;
+    let finished = if stream.is_active() {
+        current_period.min(stream.end_period).saturating_sub(stream.next_period) as usize
+    } else {
+        0
+    };
+
+    if finished > 0 {
+        let remaining: Vec<AccountInfo> = account_info_iter.cloned().collect();
+        if remaining.len() < finished {
+            msg!("Error: Expected a WorkerProof account for each of the {} finished periods", finished);
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (worker_proof_accounts, proof_accounts) = remaining.split_at(finished);
+
+        let input = input::ClosePaymentStreamInput::try_from_slice(instruction_data)?;
+        let worker = load_stream_worker(
+            program_id,
+            &stream,
+            &input.license_context,
+            merkle_tree_account,
+            proof_accounts,
+            worker_metadata_account,
+            worker_license_metadata_account,
+        )?;
+
+        if worker.suspended {
+            stop_stream(program_id, escrow_state_account, &mut stream)?;
+            msg!("Worker is suspended, payment stream {} stopped", stream.stream_id);
+        } else {
+            let payment = PaymentAccounts {
+                escrow_token_account,
+                recipient_token_account,
+                token_program,
+                mint_account,
+                escrow_state_account,
+            };
+            for worker_proof_account in worker_proof_accounts {
+                let settlement = settle_next_period(program_id, &mut stream, current_period, worker_proof_account, &worker, &payment)?;
+                if matches!(settlement, Settlement::Stopped) {
+                    break;
+                }
+            }
+        }
+    }
+
+    // The periods not started yet back the stream like any commitment, so the payer needs a
+    // matured withdrawal notice to take them back, which spends that much of the notice
+    let remainder = stream.remaining_commitment()?;
+    if remainder > 0 {
+        let mut escrow_state = load_escrow_state(program_id, escrow_state_account, &stream.payer, &stream.mint)?;
+        if !escrow_state.is_notice_matured(remainder, current_period) {
+            msg!("Error: Closing releases {} of commitments and needs a matured withdrawal notice for it", remainder);
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        escrow_state.release(remainder);
+        escrow_state.pending_withdrawal -= remainder;
+        let mut data = escrow_state_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, EscrowState::account_type(), &escrow_state)?;
+        msg!("Withdrawal notice used, {} still pending", escrow_state.pending_withdrawal);
+    }
+
+    close_account(stream_account, payer_account)?;
+
+    msg!("Payment stream {} closed at period {}", stream.stream_id, stream.next_period);
+    Ok(())
+}
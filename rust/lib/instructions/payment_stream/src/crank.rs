@@ -0,0 +1,112 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::payment_stream::accounts::PaymentStream,
+    utils::{
+        account::{read_account_data, write_account_data},
+        bmb::get_current_period,
+    },
+};
+use crate::{
+    input,
+    settle::{load_stream_worker, settle_next_period, stop_stream, PaymentAccounts},
+};
+
+pub fn process_crank_payment_stream<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Cranker (permissionless)
+    // 1. [writable] PaymentStream PDA account
+    // 2. [writable] Payer's escrow token account
+    // 3. [readonly] WorkerMetadata PDA account of the current worker owner
+    // 4. [readonly] WorkerLicenseMetadata PDA account
+    // 5. [writable] Token account of the worker's reward recipient (owner or beneficiary) for the stream mint
+    // 6. [readonly] WorkerProof PDA account for the stream's next period (may be empty)
+    // 7. [readonly] Token program account
+    // 8. [readonly] Mint account of the stream
+    // 9. [writable] Payer's EscrowState PDA account for the stream mint
+    // 10. [readonly] mpl_account_compression program
+    // 11. [readonly] Merkle tree account of the worker license
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let cranker_account = next_account_info(account_info_iter)?;
+    let stream_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+    let recipient_token_account = next_account_info(account_info_iter)?;
+    let worker_proof_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    if !cranker_account.is_signer {
+        msg!("Error: Cranker must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if stream_account.owner != program_id {
+        msg!("Error: PaymentStream account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut stream: PaymentStream = read_account_data(
+        &stream_account.try_borrow_data()?,
+        PaymentStream::account_type(),
+    )?;
+
+    if !stream.is_active() {
+        msg!("Error: Payment stream {} is stopped", stream.stream_id);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Proofs for a period arrive during the next one, so only finished periods are settled
+    let current_period = get_current_period()?;
+    if stream.next_period >= current_period {
+        msg!("Error: Period {} has not finished yet", stream.next_period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let input = input::CrankPaymentStreamInput::try_from_slice(instruction_data)?;
+    let worker = load_stream_worker(
+        program_id,
+        &stream,
+        &input.license_context,
+        merkle_tree_account,
+        &proof_accounts,
+        worker_metadata_account,
+        worker_license_metadata_account,
+    )?;
+
+    if worker.suspended {
+        stop_stream(program_id, escrow_state_account, &mut stream)?;
+        msg!("Worker is suspended, payment stream {} stopped", stream.stream_id);
+    } else {
+        let payment = PaymentAccounts {
+            escrow_token_account,
+            recipient_token_account,
+            token_program,
+            mint_account,
+            escrow_state_account,
+        };
+        settle_next_period(program_id, &mut stream, current_period, worker_proof_account, &worker, &payment)?;
+    }
+
+    let mut data = stream_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, PaymentStream::account_type(), &stream)?;
+    Ok(())
+}
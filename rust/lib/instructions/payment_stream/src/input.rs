@@ -0,0 +1,26 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shared::features::bubblegum::cnft_context::CnftContext;
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct OpenPaymentStreamInput {
+    pub stream_id: u64,
+    pub worker_license: Pubkey,
+    pub worker_owner: Pubkey,
+    pub rate_per_period: u64,
+    pub start_period: u16,
+    pub end_period: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct CrankPaymentStreamInput {
+    pub license_context: CnftContext,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct ClosePaymentStreamInput {
+    pub license_context: CnftContext,
+}
@@ -0,0 +1,9 @@
+pub mod input;
+mod open;
+mod crank;
+mod close;
+mod settle;
+
+pub use open::process_open_payment_stream;
+pub use crank::process_crank_payment_stream;
+pub use close::process_close_payment_stream;
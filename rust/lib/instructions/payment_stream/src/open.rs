@@ -0,0 +1,139 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    constants::seeds::STREAM_SEED,
+    features::{
+        escrow::utils::{load_escrow_state, store_escrow_state, validate_escrow_token_account},
+        payment_stream::accounts::{PaymentStream, PaymentStreamStatus},
+        worker::accounts::WorkerMetadata,
+    },
+    utils::{
+        account::{create_pda_account, read_account_data, write_account_data},
+        bmb::get_current_period,
+        token::read_token_account,
+    },
+};
+use crate::input;
+
+pub fn process_open_payment_stream<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Payer (escrow owner, pays for the stream account)
+    // 1. [writable] PaymentStream PDA account (will be created)
+    // 2. [readonly] Payer's escrow token account (its mint is the stream mint)
+    // 3. [readonly] WorkerMetadata PDA account of the worker owner
+    // 4. [readonly] System program account (for account creation)
+    // 5. [writable] Payer's EscrowState PDA account for the stream mint (will be created if doesn't exist)
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?;
+    let stream_account = next_account_info(account_info_iter)?;
+    let escrow_token_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let escrow_state_account = next_account_info(account_info_iter)?;
+
+    if !payer_account.is_signer {
+        msg!("Error: Payer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::OpenPaymentStreamInput::try_from_slice(instruction_data)?;
+
+    if input.rate_per_period == 0 {
+        msg!("Error: Stream rate must be greater than zero");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let current_period = get_current_period()?;
+    if input.start_period < current_period || input.end_period <= input.start_period {
+        msg!("Error: Stream must cover at least one period starting from the current period {}", current_period);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Streams are only opened for activated workers that are not suspended
+    let (worker_metadata_pda, _) = WorkerMetadata::find_pda(program_id, &input.worker_license, &input.worker_owner);
+    if *worker_metadata_account.key != worker_metadata_pda {
+        msg!("Error: WorkerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if worker_metadata_account.data_is_empty() {
+        msg!("Error: WorkerMetadata account does not exist. Worker must be activated first.");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let worker_metadata: WorkerMetadata = read_account_data(
+        &worker_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    if worker_metadata.suspended_at.is_some() {
+        msg!("Error: Worker is currently suspended");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let escrow_state = read_token_account(escrow_token_account)?;
+    validate_escrow_token_account(program_id, escrow_token_account, payer_account.key, &escrow_state.mint)?;
+
+    // The whole stream is encumbered up front so the payer cannot withdraw it without notice
+    let commitment = PaymentStream::commitment(input.end_period - input.start_period, input.rate_per_period)?;
+    let mut commitments = load_escrow_state(program_id, escrow_state_account, payer_account.key, &escrow_state.mint)?;
+    let available = commitments.unencumbered(escrow_state.amount);
+    if available < commitment {
+        msg!("Error: Insufficient escrow balance. Available: {}, Required: {}", available, commitment);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Validate PaymentStream PDA
+    let (stream_pda, stream_bump) = PaymentStream::find_pda(program_id, payer_account.key, &input.worker_license, input.stream_id);
+    if *stream_account.key != stream_pda {
+        msg!("Error: PaymentStream account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !stream_account.data_is_empty() {
+        msg!("Error: Payment stream {} already exists", input.stream_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer_account,
+        stream_account,
+        system_program,
+        program_id,
+        PaymentStream::LEN,
+        &[STREAM_SEED, payer_account.key.as_ref(), input.worker_license.as_ref(), &input.stream_id.to_le_bytes(), &[stream_bump]],
+    )?;
+
+    let stream = PaymentStream {
+        payer: *payer_account.key,
+        worker_license: input.worker_license,
+        mint: escrow_state.mint,
+        stream_id: input.stream_id,
+        rate_per_period: input.rate_per_period,
+        start_period: input.start_period,
+        end_period: input.end_period,
+        next_period: input.start_period,
+        status: PaymentStreamStatus::Active,
+    };
+
+    {
+        let mut data = stream_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, PaymentStream::account_type(), &stream)?;
+    }
+
+    commitments.encumber(commitment)?;
+    store_escrow_state(program_id, payer_account, escrow_state_account, system_program, &commitments)?;
+
+    msg!("Payment stream {} opened for periods {}..{} at {} per period", input.stream_id, input.start_period, input.end_period, input.rate_per_period);
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+use shared::{
+    features::{
+        bubblegum::cnft_context::CnftContext,
+        escrow::utils::{release_escrow, transfer_from_escrow, validate_escrow_token_account},
+        payment_stream::accounts::{PaymentStream, PaymentStreamStatus},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+    },
+    utils::{
+        account::read_account_data,
+        bgum::verify_license,
+        bmb::validate_worker_tree,
+        token::{read_mint, read_token_account, validate_token_account_owner},
+    },
+};
+
+/// Accounts a stream pays its periods through
+pub(crate) struct PaymentAccounts<'a, 'info> {
+    pub escrow_token_account: &'a AccountInfo<'info>,
+    pub recipient_token_account: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub mint_account: &'a AccountInfo<'info>,
+    pub escrow_state_account: &'a AccountInfo<'info>,
+}
+
+pub(crate) enum Settlement {
+    Paid,
+    Skipped,
+    Stopped,
+}
+
+/// Metadata of the worker's current owner, and whether the worker or its license is suspended
+pub(crate) struct StreamWorker {
+    pub metadata: WorkerMetadata,
+    pub suspended: bool,
+}
+
+/// Verifies the license leaf of the stream's worker and loads the metadata of its current owner.
+/// Metadata left behind by a previous owner does not match the leaf, so it never receives funds.
+pub(crate) fn load_stream_worker<'info>(
+    program_id: &Pubkey,
+    stream: &PaymentStream,
+    license: &CnftContext,
+    merkle_tree_account: &AccountInfo<'info>,
+    proof_accounts: &[AccountInfo<'info>],
+    worker_metadata_account: &AccountInfo<'info>,
+    worker_license_metadata_account: &AccountInfo<'info>,
+) -> Result<StreamWorker, ProgramError> {
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+    if leaf_asset_id != stream.worker_license {
+        msg!("Error: License does not match the stream worker license");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    validate_worker_tree(merkle_tree_account.key)?;
+    verify_license(
+        merkle_tree_account,
+        proof_accounts,
+        license.root,
+        license_leaf.hash(),
+        license.index,
+    )?;
+
+    let (worker_metadata_pda, _) = WorkerMetadata::find_pda(program_id, &stream.worker_license, &license.owner);
+    if *worker_metadata_account.key != worker_metadata_pda || worker_metadata_account.owner != program_id {
+        msg!("Error: WorkerMetadata account does not match the current license owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let metadata: WorkerMetadata = read_account_data(
+        &worker_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    let (worker_license_metadata_pda, _) = WorkerLicenseMetadata::find_pda(program_id, &stream.worker_license);
+    if *worker_license_metadata_account.key != worker_license_metadata_pda {
+        msg!("Error: WorkerLicenseMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let license_suspended = if worker_license_metadata_account.data_is_empty() {
+        false
+    } else {
+        let worker_license_metadata: WorkerLicenseMetadata = read_account_data(
+            &worker_license_metadata_account.try_borrow_data()?,
+            WorkerLicenseMetadata::account_type(),
+        )?;
+        worker_license_metadata.suspended_at.is_some()
+    };
+
+    Ok(StreamWorker {
+        suspended: metadata.suspended_at.is_some() || license_suspended,
+        metadata,
+    })
+}
+
+/// Settles the finished period `stream.next_period`: pays it when it has a WorkerProof,
+/// skips it once its proof window closed, and stops the stream when the escrow ran dry
+pub(crate) fn settle_next_period(
+    program_id: &Pubkey,
+    stream: &mut PaymentStream,
+    current_period: u16,
+    worker_proof_account: &AccountInfo,
+    worker: &StreamWorker,
+    payment: &PaymentAccounts,
+) -> Result<Settlement, ProgramError> {
+    let period = stream.next_period;
+
+    let (worker_proof_pda, _) = WorkerProof::find_pda(program_id, &stream.worker_license, period);
+    if *worker_proof_account.key != worker_proof_pda {
+        msg!("Error: WorkerProof account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let escrow_bump = validate_escrow_token_account(program_id, payment.escrow_token_account, &stream.payer, &stream.mint)?;
+
+    let settlement = if worker_proof_account.data_is_empty() {
+        if current_period <= period.saturating_add(1) {
+            msg!("Error: The proof window for period {} is still open", period);
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // No proof can arrive anymore, the period is skipped without payment
+        msg!("No proof for period {}, skipping", period);
+        Settlement::Skipped
+    } else {
+        let worker_proof: WorkerProof = read_account_data(
+            &worker_proof_account.try_borrow_data()?,
+            WorkerProof::account_type(),
+        )?;
+
+        if worker_proof.period != period {
+            msg!("Error: WorkerProof period does not match the stream period");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let escrow_state = read_token_account(payment.escrow_token_account)?;
+        if escrow_state.amount < stream.rate_per_period {
+            stop_stream(program_id, payment.escrow_state_account, stream)?;
+            msg!("Escrow ran dry, payment stream {} stopped", stream.stream_id);
+            return Ok(Settlement::Stopped);
+        }
+
+        if *payment.mint_account.key != stream.mint {
+            msg!("Error: Mint account does not match the stream mint");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let mint = read_mint(payment.mint_account)?;
+
+        let recipient_state = read_token_account(payment.recipient_token_account)?;
+        validate_token_account_owner(&recipient_state, &worker.metadata.reward_recipient(), &stream.mint)?;
+
+        transfer_from_escrow(
+            payment.token_program,
+            payment.escrow_token_account,
+            payment.mint_account,
+            payment.recipient_token_account,
+            &stream.payer,
+            escrow_bump,
+            stream.rate_per_period,
+            mint.decimals,
+        )?;
+
+        msg!("Payment stream {} paid {} for period {}", stream.stream_id, stream.rate_per_period, period);
+        Settlement::Paid
+    };
+
+    // Paid or skipped, the period is no longer owed
+    release_escrow(program_id, payment.escrow_state_account, &stream.payer, &stream.mint, stream.rate_per_period)?;
+
+    stream.next_period = period + 1;
+    if stream.next_period >= stream.end_period {
+        stream.status = PaymentStreamStatus::Stopped;
+        msg!("Payment stream {} completed", stream.stream_id);
+    }
+
+    Ok(settlement)
+}
+
+/// Stops the stream and releases everything it still had encumbered, a stopped stream never pays again
+pub(crate) fn stop_stream(
+    program_id: &Pubkey,
+    escrow_state_account: &AccountInfo,
+    stream: &mut PaymentStream,
+) -> Result<(), ProgramError> {
+    release_escrow(program_id, escrow_state_account, &stream.payer, &stream.mint, stream.remaining_commitment()?)?;
+    stream.status = PaymentStreamStatus::Stopped;
+    Ok(())
+}
@@ -8,7 +8,7 @@ use solana_program::{
 };
 use shared::{
     features::treasury::{accounts::{TreasuryState, LockedTokens}, utils::unlock as unlock_tokens},
-    utils::account::read_account_data
+    utils::{account::read_account_data, bmb::SysvarClock},
 };
 use crate::input;
 
@@ -89,6 +89,7 @@ pub fn process_unlock<'info>(
         locked_tokens_account,
         owner_token_account,
         token_program,
+        &SysvarClock,
     )?;
 
     msg!("Successfully processed unlock instruction for period {}", input.lock_period);
@@ -7,11 +7,12 @@ use solana_program::{
     msg, 
     program_error::ProgramError, 
     pubkey::Pubkey, 
-    system_instruction, 
     sysvar::{rent::Rent, Sysvar}, 
     program::invoke_signed
 };
+use solana_system_interface::instruction as system_instruction;
 use shared::{
+    events::{emit, DepinEvent},
     features::worker::accounts::WorkerMetadata, types::account::DepinAccountType, utils::{account::{read_account_data, reallocate_account_if_needed, write_account_data}, bgum::verify_license_and_owner, bmb::validate_worker_tree}
 };
 use crate::input;
@@ -70,6 +71,10 @@ pub fn process_activate_worker<'info>(
         worker_owner_account,
     )?;
 
+    if let Some(discovery) = &input.discovery {
+        discovery.validate()?;
+    }
+
     let (worker_metadata_pda, bump_seed) = WorkerMetadata::find_pda(program_id, &leaf_asset_id, &license.owner);
 
     // Validate WorkerMetadata PDA
@@ -78,12 +83,14 @@ pub fn process_activate_worker<'info>(
         return Err(ProgramError::InvalidArgument);
     }
 
-    let metadata = WorkerMetadata {
+    let mut metadata = WorkerMetadata {
         suspended_at: None,
         delegated_to: input.delegated_to,
         discovery_uri: input.discovery_uri,
+        discovery: input.discovery,
         license: leaf_asset_id,
         owner: license.owner,
+        reward_beneficiary: None,
     };
 
     // Check if WorkerMetadata already exists (upsert logic)
@@ -101,6 +108,9 @@ pub fn process_activate_worker<'info>(
             msg!("Error: Worker is currently suspended and cannot be activated");
             return Err(ProgramError::InvalidAccountData);
         }
+
+        // Re-activating keeps the payment wallet the owner chose
+        metadata.reward_beneficiary = existing_metadata.reward_beneficiary;
         
         // Handle account reallocation if needed
         reallocate_account_if_needed(
@@ -143,6 +153,12 @@ pub fn process_activate_worker<'info>(
     let mut data = worker_metadata_account.try_borrow_mut_data()?;
     write_account_data(&mut data, WorkerMetadata::account_type(), &metadata)?;
 
+    emit(&DepinEvent::WorkerActivated {
+        license: leaf_asset_id,
+        owner: license.owner,
+        delegate: metadata.delegated_to,
+    });
+
     msg!("Worker activated successfully");
     Ok(())
 }
\ No newline at end of file
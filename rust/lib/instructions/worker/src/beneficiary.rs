@@ -0,0 +1,66 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use shared::{
+    events::{emit, DepinEvent},
+    features::worker::accounts::WorkerMetadata,
+    utils::account::{reallocate_account_if_needed, write_account_data},
+};
+use crate::{delegate::load_owned_worker_metadata, input};
+
+pub fn process_set_worker_reward_beneficiary<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer, writable] Worker license owner (pays for growing metadata written at an older version)
+    // 1. [writable] WorkerMetadata PDA account (must exist)
+    // 2. [readonly] System program account (for the rent transfer)
+    let account_info_iter = &mut accounts.iter();
+    let worker_owner_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let input = input::SetWorkerRewardBeneficiaryInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_worker_metadata(
+        program_id,
+        worker_owner_account,
+        worker_metadata_account,
+        &input.license,
+    )?;
+
+    // Naming the owner itself is the same as clearing the beneficiary
+    metadata.reward_beneficiary = input.beneficiary.filter(|beneficiary| beneficiary != worker_owner_account.key);
+
+    if worker_metadata_account.data_len() < metadata.len() {
+        reallocate_account_if_needed(
+            worker_owner_account,
+            worker_metadata_account,
+            system_program,
+            &Rent::get()?,
+            metadata.len(),
+        )?;
+    }
+
+    let mut data = worker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, WorkerMetadata::account_type(), &metadata)?;
+
+    emit(&DepinEvent::WorkerRewardBeneficiarySet {
+        license: input.license,
+        owner: *worker_owner_account.key,
+        beneficiary: metadata.reward_beneficiary,
+    });
+
+    match metadata.reward_beneficiary {
+        Some(beneficiary) => msg!("Worker rewards now go to {}", beneficiary),
+        None => msg!("Worker rewards now go to the owner"),
+    }
+    Ok(())
+}
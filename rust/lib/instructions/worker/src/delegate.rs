@@ -0,0 +1,121 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use shared::{
+    features::worker::accounts::WorkerMetadata,
+    utils::account::{read_account_data, write_account_data},
+};
+use crate::input;
+
+pub fn process_set_worker_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Worker license owner
+    // 1. [writable] WorkerMetadata PDA account (must exist)
+    let account_info_iter = &mut accounts.iter();
+    let worker_owner_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+
+    let input = input::SetWorkerDelegateInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_worker_metadata(
+        program_id,
+        worker_owner_account,
+        worker_metadata_account,
+        &input.license,
+    )?;
+
+    metadata.delegated_to = input.delegated_to;
+
+    let mut data = worker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, WorkerMetadata::account_type(), &metadata)?;
+
+    msg!("Worker delegate set to {}", input.delegated_to);
+    Ok(())
+}
+
+pub fn process_revoke_worker_delegate<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Worker license owner
+    // 1. [writable] WorkerMetadata PDA account (must exist)
+    let account_info_iter = &mut accounts.iter();
+    let worker_owner_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+
+    let input = input::RevokeWorkerDelegateInput::try_from_slice(instruction_data)?;
+
+    let mut metadata = load_owned_worker_metadata(
+        program_id,
+        worker_owner_account,
+        worker_metadata_account,
+        &input.license,
+    )?;
+
+    if metadata.delegated_to == metadata.owner {
+        msg!("Worker has no delegate to revoke");
+        return Ok(());
+    }
+
+    // Authority returns to the owner, the old delegate can no longer sign for this worker
+    metadata.delegated_to = metadata.owner;
+
+    let mut data = worker_metadata_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, WorkerMetadata::account_type(), &metadata)?;
+
+    msg!("Worker delegate revoked");
+    Ok(())
+}
+
+/// Loads WorkerMetadata after checking it is the PDA of the signing owner.
+/// The PDA is derived from the owner key, so no cNFT proof is needed here:
+/// metadata left behind by a previous owner is never read by proofs or payouts.
+pub(crate) fn load_owned_worker_metadata(
+    program_id: &Pubkey,
+    worker_owner_account: &AccountInfo,
+    worker_metadata_account: &AccountInfo,
+    license: &Pubkey,
+) -> Result<WorkerMetadata, ProgramError> {
+    if !worker_owner_account.is_signer {
+        msg!("Error: Worker license owner must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (worker_metadata_pda, _) = WorkerMetadata::find_pda(program_id, license, worker_owner_account.key);
+
+    // Validate WorkerMetadata PDA
+    if *worker_metadata_account.key != worker_metadata_pda {
+        msg!("Error: WorkerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Check that WorkerMetadata account exists
+    if worker_metadata_account.data_is_empty() {
+        msg!("Error: WorkerMetadata account does not exist. Worker must be activated first.");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let metadata: WorkerMetadata = read_account_data(
+        &worker_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    // Check if worker is suspended
+    if metadata.suspended_at.is_some() {
+        msg!("Error: Worker is currently suspended and its metadata cannot be changed");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(metadata)
+}
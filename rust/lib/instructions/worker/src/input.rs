@@ -1,8 +1,9 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use shared::features::bubblegum::cnft_context::CnftContext;
+use shared::features::{bubblegum::cnft_context::CnftContext, worker::accounts::WorkerDiscovery};
 use solana_program::pubkey::Pubkey;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct SubmitWorkerProofInput {
     pub license_context: CnftContext,
     pub proof_root: [u8; 32],
@@ -13,14 +14,63 @@ pub struct SubmitWorkerProofInput {
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct ActivateWorkerInput {
     pub license_context: CnftContext,
     pub delegated_to: Pubkey,
     pub discovery_uri: String,
+    pub discovery: Option<WorkerDiscovery>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct UpdateWorkerUriInput {
     pub license_context: CnftContext,
     pub discovery_uri: String,
-}
\ No newline at end of file
+    pub discovery: Option<WorkerDiscovery>,
+}
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct WorkerProofBatchEntry {
+    pub license_context: CnftContext,
+    pub proof_root: [u8; 32],
+    pub checkers: [u64; 8],
+    pub uptime: u32,
+    pub latency: u32,
+    pub proof_accounts_len: u8
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SubmitWorkerProofBatchInput {
+    pub period: u16,
+    pub entries: Vec<WorkerProofBatchEntry>
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct MigrateWorkerMetadataInput {
+    pub license_context: CnftContext,
+    pub previous_owner: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetWorkerDelegateInput {
+    pub license: Pubkey,
+    pub delegated_to: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct RevokeWorkerDelegateInput {
+    pub license: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SetWorkerRewardBeneficiaryInput {
+    pub license: Pubkey,
+    /// None sends payouts back to the owner
+    pub beneficiary: Option<Pubkey>,
+}
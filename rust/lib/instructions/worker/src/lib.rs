@@ -1,8 +1,16 @@
-mod input;
+pub mod input;
 mod submit;
+mod submit_batch;
 mod activate;
 mod update_worker_uri;
+mod migrate_metadata;
+mod delegate;
+mod beneficiary;
 
 pub use submit::process_submit_worker_proof;
+pub use submit_batch::process_submit_worker_proof_batch;
 pub use activate::process_activate_worker;
-pub use update_worker_uri::process_update_worker_uri;
\ No newline at end of file
+pub use update_worker_uri::process_update_worker_uri;
+pub use migrate_metadata::process_migrate_worker_metadata;
+pub use delegate::{process_set_worker_delegate, process_revoke_worker_delegate};
+pub use beneficiary::process_set_worker_reward_beneficiary;
\ No newline at end of file
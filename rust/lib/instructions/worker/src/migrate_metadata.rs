@@ -0,0 +1,195 @@
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use shared::{
+    constants::seeds::{LICENSE_SEED, METADATA_SEED, WORKER_SEED},
+    features::worker::accounts::{WorkerLicenseMetadata, WorkerMetadata},
+    utils::{
+        account::{close_account, create_pda_account, read_account_data, reallocate_account_if_needed, write_account_data},
+        bgum::verify_license,
+        bmb::validate_worker_tree,
+    },
+};
+use crate::input;
+
+pub fn process_migrate_worker_metadata<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Payer (permissionless, usually the new license owner)
+    // 1. [writable] Stale WorkerMetadata PDA account of the previous owner (will be closed)
+    // 2. [writable] WorkerMetadata PDA account of the current owner (will be created or updated)
+    // 3. [writable] WorkerLicenseMetadata PDA account (will be created if a suspension must be kept)
+    // 4. [writable] Previous owner account (receives the rent of the stale account)
+    // 5. [readonly] mpl_account_compression program
+    // 6. [readonly] Merkle tree account
+    // 7. [readonly] System program account (for account creation)
+    // N. [readonly] Proof accounts as remaining accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_account = next_account_info(account_info_iter)?;
+    let stale_metadata_account = next_account_info(account_info_iter)?;
+    let worker_metadata_account = next_account_info(account_info_iter)?;
+    let worker_license_metadata_account = next_account_info(account_info_iter)?;
+    let previous_owner_account = next_account_info(account_info_iter)?;
+    let _mpl_account_compression_program_account = next_account_info(account_info_iter)?;
+    let merkle_tree_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Collect remaining accounts as proof accounts
+    let proof_accounts: Vec<AccountInfo> = account_info_iter.cloned().collect();
+
+    if !payer_account.is_signer {
+        msg!("Error: Payer must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::MigrateWorkerMetadataInput::try_from_slice(instruction_data)?;
+    let license = input.license_context;
+
+    // Calculate the leaf PDA (worker license)
+    let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+
+    let license_leaf = LeafSchema::V2 {
+        id: leaf_asset_id,
+        owner: license.owner,
+        delegate: license.delegate,
+        nonce: license.nonce,
+        data_hash: license.data_hash,
+        creator_hash: license.creator_hash,
+        collection_hash: license.collection_hash,
+        asset_data_hash: license.asset_data_hash,
+        flags: license.flags,
+    };
+
+    // Verify tree and leaf, this proves who the current owner is
+    validate_worker_tree(merkle_tree_account.key)?;
+    verify_license(
+        merkle_tree_account,
+        &proof_accounts,
+        license.root,
+        license_leaf.hash(),
+        license.index,
+    )?;
+
+    if input.previous_owner == license.owner {
+        msg!("Error: License has not changed owner, nothing to migrate");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if *previous_owner_account.key != input.previous_owner {
+        msg!("Error: Previous owner account does not match the stale metadata owner");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate stale WorkerMetadata PDA
+    let (stale_metadata_pda, _) = WorkerMetadata::find_pda(program_id, &leaf_asset_id, &input.previous_owner);
+    if *stale_metadata_account.key != stale_metadata_pda {
+        msg!("Error: Stale WorkerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if stale_metadata_account.data_is_empty() {
+        msg!("Error: Stale WorkerMetadata account does not exist");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let stale_metadata: WorkerMetadata = read_account_data(
+        &stale_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    // Validate current owner's WorkerMetadata PDA
+    let (worker_metadata_pda, worker_metadata_bump) = WorkerMetadata::find_pda(program_id, &leaf_asset_id, &license.owner);
+    if *worker_metadata_account.key != worker_metadata_pda {
+        msg!("Error: WorkerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate WorkerLicenseMetadata PDA
+    let (worker_license_metadata_pda, worker_license_metadata_bump) = WorkerLicenseMetadata::find_pda(program_id, &leaf_asset_id);
+    if *worker_license_metadata_account.key != worker_license_metadata_pda {
+        msg!("Error: WorkerLicenseMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Move a suspension onto the license itself so it survives any further transfer
+    if let Some(suspended_at) = stale_metadata.suspended_at {
+        let license_metadata = if worker_license_metadata_account.data_is_empty() {
+            create_pda_account(
+                payer_account,
+                worker_license_metadata_account,
+                system_program,
+                program_id,
+                WorkerLicenseMetadata::LEN,
+                &[WORKER_SEED, LICENSE_SEED, leaf_asset_id.as_ref(), &[worker_license_metadata_bump]],
+            )?;
+            WorkerLicenseMetadata { suspended_at: Some(suspended_at) }
+        } else {
+            let existing: WorkerLicenseMetadata = read_account_data(
+                &worker_license_metadata_account.try_borrow_data()?,
+                WorkerLicenseMetadata::account_type(),
+            )?;
+            WorkerLicenseMetadata { suspended_at: existing.suspended_at.or(Some(suspended_at)) }
+        };
+
+        let mut data = worker_license_metadata_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, WorkerLicenseMetadata::account_type(), &license_metadata)?;
+        msg!("Worker suspension moved to the license");
+    }
+
+    // The previous owner's delegate and reward beneficiary are never carried over, control returns to the new owner
+    let metadata = if worker_metadata_account.data_is_empty() {
+        let metadata = WorkerMetadata {
+            suspended_at: stale_metadata.suspended_at,
+            delegated_to: license.owner,
+            license: leaf_asset_id,
+            owner: license.owner,
+            discovery_uri: stale_metadata.discovery_uri,
+            discovery: stale_metadata.discovery,
+            reward_beneficiary: None,
+        };
+
+        create_pda_account(
+            payer_account,
+            worker_metadata_account,
+            system_program,
+            program_id,
+            metadata.len(),
+            &[WORKER_SEED, METADATA_SEED, leaf_asset_id.as_ref(), license.owner.as_ref(), &[worker_metadata_bump]],
+        )?;
+        metadata
+    } else {
+        // The new owner already activated, only carry the suspension over
+        let mut existing: WorkerMetadata = read_account_data(
+            &worker_metadata_account.try_borrow_data()?,
+            WorkerMetadata::account_type(),
+        )?;
+        existing.suspended_at = existing.suspended_at.or(stale_metadata.suspended_at);
+
+        // Metadata activated before the current layout version has to grow before it is rewritten
+        if worker_metadata_account.data_len() < existing.len() {
+            reallocate_account_if_needed(payer_account, worker_metadata_account, system_program, &Rent::get()?, existing.len())?;
+        }
+        existing
+    };
+
+    {
+        let mut data = worker_metadata_account.try_borrow_mut_data()?;
+        write_account_data(&mut data, WorkerMetadata::account_type(), &metadata)?;
+    }
+
+    close_account(stale_metadata_account, previous_owner_account)?;
+
+    msg!("Worker metadata migrated from {} to {}", input.previous_owner, license.owner);
+    Ok(())
+}
@@ -3,18 +3,21 @@ use borsh::BorshDeserialize;
 use mpl_bubblegum::types::LeafSchema;
 use mpl_bubblegum::utils::get_asset_id;
 use shared::{
+    events::{emit, DepinEvent},
     features::{
-        global::accounts::BMBState,
-        rewards::accounts::GlobalRewards,
-        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+        global::accounts::{BMBState, BMBStateView},
+        rewards::accounts::{GlobalRewards, GlobalRewardsView},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof, WorkerProofView},
     }, utils::{
-        account::{read_account_data, write_account_data},
+        account::read_account_data,
         bgum::verify_license, bmb::validate_worker_tree,
+        zero_copy::{init_account, load_account, load_account_mut},
     }
 };
 use solana_program::{
-    account_info::{next_account_info, AccountInfo}, entrypoint::ProgramResult, msg, program::invoke_signed, program_error::ProgramError, pubkey::Pubkey, system_instruction, sysvar::{rent::Rent, Sysvar}
+    account_info::{next_account_info, AccountInfo}, entrypoint::ProgramResult, msg, program::invoke_signed, program_error::ProgramError, pubkey::Pubkey, sysvar::{rent::Rent, Sysvar}
 };
+use solana_system_interface::instruction as system_instruction;
 
 pub fn process_submit_worker_proof(
     program_id: &Pubkey,
@@ -100,16 +103,64 @@ pub fn process_submit_worker_proof(
         &leaf_asset_id
     )?;
 
-    let current_period = shared::utils::bmb::get_current_period();
+    validate_submission_period(input.period)?;
 
-    // Workers can only submit for the previous period (current_period - 1)
-    // This gives them 24h from period end to submit
-    if input.period != current_period - 1 {
-        msg!("Error: Can only submit proof for the previous period, current period is {}, given period is {}", current_period, input.period);
+    let proof_data = WorkerProof {
+        period: input.period,
+        proof_root: input.proof_root,
+        checkers: input.checkers,
+        latency: input.latency,
+        uptime: input.uptime,
+    };
+
+    create_worker_proof(
+        program_id,
+        worker_delegate_account,
+        worker_proof_account,
+        system_program,
+        &leaf_asset_id,
+        &proof_data,
+    )?;
+
+    update_checker_rewards(
+        program_id,
+        global_rewards_account,
+        bmb_state_account,
+        &leaf_asset_id,
+        input.period,
+        input.checkers,
+    )?;
+
+    Ok(())
+}
+
+/// Workers can only submit for the previous period (current_period - 1).
+/// This gives them 24h from period end to submit.
+pub(crate) fn validate_submission_period(period: u16) -> ProgramResult {
+    let current_period = shared::utils::bmb::get_current_period()?;
+
+    let Some(previous_period) = current_period.checked_sub(1) else {
+        msg!("Error: No period has finished yet, proofs can be submitted from period 1");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    if period != previous_period {
+        msg!("Error: Can only submit proof for the previous period, current period is {}, given period is {}", current_period, period);
         return Err(ProgramError::InvalidArgument);
     }
+    Ok(())
+}
 
-    let (worker_pda, bump_seed) = WorkerProof::find_pda(program_id, &leaf_asset_id, input.period);
+/// Creates the WorkerProof PDA for the given license and period and writes the proof into it
+pub(crate) fn create_worker_proof<'a>(
+    program_id: &Pubkey,
+    payer_account: &AccountInfo<'a>,
+    worker_proof_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    leaf_asset_id: &Pubkey,
+    proof_data: &WorkerProof,
+) -> ProgramResult {
+    let (worker_pda, bump_seed) = WorkerProof::find_pda(program_id, leaf_asset_id, proof_data.period);
 
     // Validate WorkerProof PDA
     if *worker_proof_account.key != worker_pda {
@@ -130,45 +181,28 @@ pub fn process_submit_worker_proof(
 
     invoke_signed(
         &system_instruction::create_account(
-            worker_delegate_account.key,
+            payer_account.key,
             &worker_pda,
             rent_lamports,
             space as u64,
             program_id,
         ),
         &[
-            worker_delegate_account.clone(),
+            payer_account.clone(),
             worker_proof_account.clone(),
             system_program.clone(),
         ],
         &[&[
             shared::constants::seeds::PROOF_SEED,
-            &input.period.to_le_bytes(),
+            &proof_data.period.to_le_bytes(),
             leaf_asset_id.as_ref(),
             &[bump_seed],
         ]],
     )?;
 
-    let proof_data = WorkerProof {
-        period: input.period,
-        proof_root: input.proof_root,
-        checkers: input.checkers,
-        latency: input.latency,
-        uptime: input.uptime,
-    };
-
     // Write proof data to the account
     let mut data = worker_proof_account.try_borrow_mut_data()?;
-    write_account_data(&mut data, WorkerProof::account_type(), &proof_data)?;
-
-    update_checker_rewards(
-        program_id,
-        global_rewards_account,
-        bmb_state_account,
-        &leaf_asset_id,
-        input.period,
-        input.checkers,
-    )?;
+    *init_account::<WorkerProofView>(&mut data)? = WorkerProofView::from(proof_data);
 
     Ok(())
 }
@@ -181,6 +215,20 @@ fn update_checker_rewards(
     period: u16,
     checkers: [u64; 8],
 ) -> ProgramResult {    
+    let checker_count = load_checker_count(program_id, bmb_state_account, period)?;
+
+    validate_global_rewards_account(program_id, global_rewards_account)?;
+
+    let mut global_rewards_data = global_rewards_account.try_borrow_mut_data()?;
+    credit_checkers(&mut global_rewards_data, leaf_asset_id, period, checker_count, checkers)
+}
+
+/// Fetches the number of activated checkers for the given period from BMBState
+pub(crate) fn load_checker_count(
+    program_id: &Pubkey,
+    bmb_state_account: &AccountInfo,
+    period: u16,
+) -> Result<u32, ProgramError> {
     let (bmb_state_pda, _) = BMBState::find_pda(program_id);
     if bmb_state_account.key != &bmb_state_pda {
         msg!("Error: BMBState account does not match expected PDA");
@@ -193,19 +241,36 @@ fn update_checker_rewards(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    // Fetch the number of activated checkers for this period from BMBState
-    let bmb_state: BMBState = read_account_data(
-        &bmb_state_account.try_borrow_data()?,
-        BMBState::account_type(),
-    )?;
-
-    let checker_count = bmb_state
+    let bmb_state_data = bmb_state_account.try_borrow_data()?;
+    load_account::<BMBStateView>(&bmb_state_data)?
         .get_checker_count_for_period(period)
         .ok_or_else(|| {
             msg!("Error: No checker count available for target period");
             ProgramError::InvalidAccountData
-        })?;
+        })
+}
+
+pub(crate) fn validate_global_rewards_account(
+    program_id: &Pubkey,
+    global_rewards_account: &AccountInfo,
+) -> ProgramResult {
+    let (global_rewards_pda, _) = GlobalRewards::find_pda(program_id);
 
+    if global_rewards_account.key != &global_rewards_pda {
+        msg!("Error: Global rewards account does not match expected PDA");
+        return Err(solana_program::program_error::ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Runs BRAND for the worker license and credits every checker selected in the proof bitmap
+pub(crate) fn credit_checkers(
+    global_rewards_data: &mut [u8],
+    leaf_asset_id: &Pubkey,
+    period: u16,
+    checker_count: u32,
+    checkers: [u64; 8],
+) -> ProgramResult {
     // Run BRAND
     let numbers = shared::utils::brand::generate_numbers(
         leaf_asset_id.as_ref(),
@@ -214,17 +279,10 @@ fn update_checker_rewards(
         checker_count as u64,
     );
 
-    // Fetch and modify checkers in the global rewards account
-    let (global_rewards_pda, _) = GlobalRewards::find_pda(program_id);
-
-    if global_rewards_account.key != &global_rewards_pda {
-        msg!("Error: Global rewards account does not match expected PDA");
-        return Err(solana_program::program_error::ProgramError::InvalidArgument);
-    }
-
-    let mut global_rewards_data = global_rewards_account.try_borrow_mut_data()?;
     let period_reward_tokens = GlobalRewards::get_checker_reward(period);
-    
+    let global_rewards = load_account_mut::<GlobalRewardsView>(global_rewards_data)?;
+    let mut credited_checkers: Vec<u32> = Vec::with_capacity(checkers.iter().map(|bits| bits.count_ones() as usize).sum());
+
     // Iterate through the bitmap and increment rewards for selected checkers
     for (array_index, &checker_bits) in checkers.iter().enumerate() {
         if checker_bits == 0 {
@@ -241,17 +299,25 @@ fn update_checker_rewards(
 
             let checker_index = numbers[bit_index] as usize;
 
-            GlobalRewards::add_checker_balance(&mut global_rewards_data, checker_index, period_reward_tokens as u32)?;
+            global_rewards.add_checker_balance(checker_index, period_reward_tokens)?;
+            credited_checkers.push(checker_index as u32);
 
             // Clear the processed bit
             bits &= bits - 1;
         }
     }
 
+    emit(&DepinEvent::WorkerProofSubmitted {
+        license: *leaf_asset_id,
+        period,
+        credited_checkers,
+        reward_per_checker: period_reward_tokens,
+    });
+
     Ok(())
 }
 
-fn validate_worker_metadata_and_delegate(
+pub(crate) fn validate_worker_metadata_and_delegate(
     program_id: &Pubkey,
     worker_metadata_account: &AccountInfo,
     worker_delegate_account: &AccountInfo,
@@ -289,7 +355,7 @@ fn validate_worker_metadata_and_delegate(
     Ok(())
 }
 
-fn validate_worker_license_metadata(
+pub(crate) fn validate_worker_license_metadata(
     program_id: &Pubkey,
     worker_license_metadata_account: &AccountInfo,
     leaf_asset_id: &Pubkey
@@ -0,0 +1,164 @@
+use crate::input;
+use crate::submit::{
+    create_worker_proof, credit_checkers, load_checker_count, validate_global_rewards_account,
+    validate_submission_period, validate_worker_license_metadata, validate_worker_metadata_and_delegate,
+};
+use borsh::BorshDeserialize;
+use mpl_bubblegum::types::LeafSchema;
+use mpl_bubblegum::utils::get_asset_id;
+use shared::{
+    features::worker::accounts::WorkerProof,
+    utils::{bgum::verify_license, bmb::validate_worker_tree},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey
+};
+
+/// Upper bound on licenses per batch, transaction size caps it well before this in practice
+pub const MAX_WORKER_PROOF_BATCH: usize = 32;
+
+const FIXED_ACCOUNTS: usize = 6;
+const ACCOUNTS_PER_ENTRY: usize = 3;
+
+pub fn process_submit_worker_proof_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // Expected Accounts:
+    // 0. [signer] Worker delegate (must be the delegate of every license in the batch)
+    // 1. [writable] Global rewards account
+    // 2. [readonly] BMBState account
+    // 3. [readonly] mpl_account_compression program
+    // 4. [readonly] Merkle tree account
+    // 5. [readonly] System program account (for account creation)
+    // Then for each entry, in input order:
+    //   [writable] WorkerProof PDA account (will be created)
+    //   [readonly] WorkerMetadata PDA account
+    //   [readonly] WorkerLicenseMetadata PDA account
+    //   [readonly] `proof_accounts_len` proof accounts (only the nodes above the tree canopy)
+    if accounts.len() < FIXED_ACCOUNTS {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let worker_delegate_account = &accounts[0];
+    let global_rewards_account = &accounts[1];
+    let bmb_state_account = &accounts[2];
+    let merkle_tree_account = &accounts[4];
+    let system_program = &accounts[5];
+
+    // Check worker delegate is signer
+    if !worker_delegate_account.is_signer {
+        msg!("Error: Worker delegate must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let input = input::SubmitWorkerProofBatchInput::try_from_slice(instruction_data)?;
+
+    if input.entries.is_empty() || input.entries.len() > MAX_WORKER_PROOF_BATCH {
+        msg!("Error: Batch must contain between 1 and {} proofs, got {}", MAX_WORKER_PROOF_BATCH, input.entries.len());
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Verify tree and period once for the whole batch
+    validate_worker_tree(merkle_tree_account.key)?;
+    validate_submission_period(input.period)?;
+
+    // All licenses in a batch are credited against the same checker set
+    let checker_count = load_checker_count(program_id, bmb_state_account, input.period)?;
+    validate_global_rewards_account(program_id, global_rewards_account)?;
+
+    // Verify every license and create its proof before touching GlobalRewards
+    let mut credits: Vec<(Pubkey, [u64; 8])> = Vec::with_capacity(input.entries.len());
+    let mut cursor = FIXED_ACCOUNTS;
+
+    for entry in input.entries {
+        let proof_accounts_len = entry.proof_accounts_len as usize;
+        let entry_end = cursor + ACCOUNTS_PER_ENTRY + proof_accounts_len;
+        if accounts.len() < entry_end {
+            msg!("Error: Not enough accounts for batch entry {}", credits.len());
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let worker_proof_account = &accounts[cursor];
+        let worker_metadata_account = &accounts[cursor + 1];
+        let worker_license_metadata_account = &accounts[cursor + 2];
+        let proof_accounts = &accounts[cursor + ACCOUNTS_PER_ENTRY..entry_end];
+        cursor = entry_end;
+
+        let license = entry.license_context;
+
+        // Calculate the leaf PDA
+        let leaf_asset_id = get_asset_id(merkle_tree_account.key, license.nonce);
+
+        let license_leaf = LeafSchema::V2 {
+            id: leaf_asset_id,
+            owner: license.owner,
+            delegate: license.delegate,
+            nonce: license.nonce,
+            data_hash: license.data_hash,
+            creator_hash: license.creator_hash,
+            collection_hash: license.collection_hash,
+            asset_data_hash: license.asset_data_hash,
+            flags: license.flags,
+        };
+
+        // Verify leaf
+        verify_license(
+            merkle_tree_account,
+            proof_accounts,
+            license.root,
+            license_leaf.hash(),
+            license.index,
+        )?;
+
+        // Validate worker metadata and delegate authorization
+        validate_worker_metadata_and_delegate(
+            program_id,
+            worker_metadata_account,
+            worker_delegate_account,
+            &leaf_asset_id,
+            &license.owner,
+        )?;
+
+        // Validate worker license metadata
+        validate_worker_license_metadata(
+            program_id,
+            worker_license_metadata_account,
+            &leaf_asset_id
+        )?;
+
+        let proof_data = WorkerProof {
+            period: input.period,
+            proof_root: entry.proof_root,
+            checkers: entry.checkers,
+            latency: entry.latency,
+            uptime: entry.uptime,
+        };
+
+        // Fails with AccountAlreadyInitialized if the same license appears twice
+        create_worker_proof(
+            program_id,
+            worker_delegate_account,
+            worker_proof_account,
+            system_program,
+            &leaf_asset_id,
+            &proof_data,
+        )?;
+
+        credits.push((leaf_asset_id, entry.checkers));
+    }
+
+    if cursor != accounts.len() {
+        msg!("Error: Unexpected trailing accounts in batch");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Credit all selected checkers in a single pass over GlobalRewards
+    let mut global_rewards_data = global_rewards_account.try_borrow_mut_data()?;
+    for (leaf_asset_id, checkers) in credits.iter() {
+        credit_checkers(&mut global_rewards_data, leaf_asset_id, input.period, checker_count, *checkers)?;
+    }
+
+    msg!("Submitted {} worker proofs for period {}", credits.len(), input.period);
+    Ok(())
+}
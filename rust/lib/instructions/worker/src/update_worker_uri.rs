@@ -104,8 +104,13 @@ pub fn process_update_worker_uri<'info>(
         license.index,
     )?;
 
-    // Update only the discovery URI, keep other fields unchanged
+    if let Some(discovery) = &input.discovery {
+        discovery.validate()?;
+    }
+
+    // Update only the discovery URI and record, keep other fields unchanged
     existing_metadata.discovery_uri = input.discovery_uri;
+    existing_metadata.discovery = input.discovery;
 
     // Handle account reallocation if needed
     let rent = Rent::get()?;
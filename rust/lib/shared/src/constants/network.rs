@@ -0,0 +1,52 @@
+// Period clock of the network, set per deployment at build time, e.g. hourly periods for staging:
+// DEPIN_PERIOD_ZERO=1767225600 DEPIN_PERIOD_SECONDS=3600 cargo build-sbf
+
+pub const DEFAULT_PERIOD_ZERO: i64 = 1748736000; // 2025-06-01 00:00:00 UTC
+pub const DEFAULT_SECONDS_PER_PERIOD: i64 = 86400;
+
+/// Unix timestamp at which period 0 starts
+pub const PERIOD_ZERO: i64 = parse_env(option_env!("DEPIN_PERIOD_ZERO"), DEFAULT_PERIOD_ZERO);
+
+/// Length of a period in seconds
+pub const SECONDS_PER_PERIOD: i64 = parse_env(option_env!("DEPIN_PERIOD_SECONDS"), DEFAULT_SECONDS_PER_PERIOD);
+
+const _: () = assert!(SECONDS_PER_PERIOD > 0, "DEPIN_PERIOD_SECONDS must be positive");
+
+pub const SECONDS_PER_DAY: i64 = 86400;
+
+/// Number of periods of `seconds_per_period` needed to cover `days`, rounded up
+pub const fn periods_covering_days(days: u16, seconds_per_period: i64) -> i64 {
+    (days as i64 * SECONDS_PER_DAY + seconds_per_period - 1) / seconds_per_period
+}
+
+/// Part of a daily amount that falls into one period of `seconds_per_period`, rounded down
+pub const fn daily_amount_per_period(daily_amount: u32, seconds_per_period: i64) -> u32 {
+    let amount = daily_amount as u128 * seconds_per_period as u128 / SECONDS_PER_DAY as u128;
+    if amount > u32::MAX as u128 { u32::MAX } else { amount as u32 }
+}
+
+/// Parses a decimal build-time variable, an invalid value fails the build
+const fn parse_env(value: Option<&str>, default: i64) -> i64 {
+    let bytes = match value {
+        Some(value) => value.as_bytes(),
+        None => return default,
+    };
+
+    let negative = !bytes.is_empty() && bytes[0] == b'-';
+    let mut i = negative as usize;
+    if i == bytes.len() {
+        panic!("Network period variables must be decimal integers");
+    }
+
+    let mut result: i64 = 0;
+    while i < bytes.len() {
+        let digit = bytes[i];
+        if !digit.is_ascii_digit() {
+            panic!("Network period variables must be decimal integers");
+        }
+        result = result * 10 + (digit - b'0') as i64;
+        i += 1;
+    }
+
+    if negative { -result } else { result }
+}
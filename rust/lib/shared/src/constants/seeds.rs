@@ -5,9 +5,12 @@ pub const METADATA_SEED: &[u8] = b"meta";
 
 pub const ESCROW_SEED: &[u8] = b"escrow";
 pub const TOKEN_SEED: &[u8] = b"token";
+pub const AUTHORITY_SEED: &[u8] = b"authority";
 pub const TREASURY_SEED: &[u8] = b"treasury";
 pub const LOCK_SEED: &[u8] = b"lock";
 pub const CONFIG_SEED: &[u8] = b"config";
+pub const MINTS_SEED: &[u8] = b"mints";
+pub const PAUSE_SEED: &[u8] = b"pause";
 
 pub const GLOBAL_REWARDS_SEED: &[u8] = b"rewards";
 
@@ -17,3 +20,11 @@ pub const WORKER_SEED: &[u8] = b"worker";
 pub const LICENSE_SEED: &[u8] = b"license";
 
 pub const CHECKER_SEED: &[u8] = b"checker";
+
+pub const INFRA_SEED: &[u8] = b"infra";
+pub const OFFER_SEED: &[u8] = b"offer";
+
+pub const LEASE_SEED: &[u8] = b"lease";
+pub const VAULT_SEED: &[u8] = b"vault";
+
+pub const STREAM_SEED: &[u8] = b"stream";
@@ -0,0 +1,152 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+/// Prefix of every event payload, lets indexers tell depin events apart from other `Program data:` logs
+pub const EVENT_DISCRIMINATOR: [u8; 8] = *b"depinevt";
+
+/// State changes logged through `sol_log_data` as `EVENT_DISCRIMINATOR` followed by the Borsh encoded event.
+/// New variants must only ever be appended, indexers decode by variant index.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum DepinEvent {
+    WorkerActivated {
+        license: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+    },
+    CheckerActivated {
+        license: Pubkey,
+        owner: Pubkey,
+        checker_index: u32,
+    },
+    CheckerLicensesActivated {
+        period: u16,
+        checker_count: u32,
+    },
+    WorkerProofSubmitted {
+        license: Pubkey,
+        period: u16,
+        /// Checker indices credited by this proof, in bitmap order. An index repeats when BRAND selected it twice.
+        credited_checkers: Vec<u32>,
+        reward_per_checker: u32,
+    },
+    CheckerRewardsPaid {
+        owner: Pubkey,
+        checker_indices: Vec<u32>,
+        amount: u64,
+    },
+    TokensLocked {
+        owner: Pubkey,
+        amount: u64,
+        lock_period: u16,
+        unlock_period: u16,
+    },
+    TokensUnlocked {
+        owner: Pubkey,
+        lock_period: u16,
+        payout: u64,
+        penalty: u64,
+    },
+    TreasuryConfigInitialized {
+        checker_rewards_lock_days: u16,
+    },
+    AllowedMintSet {
+        mint: Pubkey,
+        max_deposit: u64,
+        paused: bool,
+    },
+    AllowedMintRemoved {
+        mint: Pubkey,
+    },
+    EscrowDeposited {
+        depositor: Pubkey,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        /// Amount that reached the escrow after transfer fees
+        credited: u64,
+    },
+    EscrowWithdrawn {
+        owner: Pubkey,
+        withdrawer: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+    },
+    /// Funds the program moved out of an escrow, into a lease vault or to a worker
+    EscrowTransferred {
+        owner: Pubkey,
+        mint: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    },
+    /// Funds returned from a lease vault into the buyer's escrow
+    EscrowRefunded {
+        owner: Pubkey,
+        mint: Pubkey,
+        lease: Pubkey,
+        amount: u64,
+    },
+    EscrowWithdrawalNoticed {
+        owner: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        unlock_period: u16,
+    },
+    /// An account was rewritten at the current layout version of its type
+    AccountMigrated {
+        account: Pubkey,
+        account_type: u8,
+        from_version: u8,
+        to_version: u8,
+    },
+    PauseFlagsSet {
+        flags: u8,
+    },
+    UnpauseScheduled {
+        flags: u8,
+        unpause_at: i64,
+    },
+    /// `beneficiary` of None sends payouts back to the owner
+    CheckerRewardBeneficiarySet {
+        license: Pubkey,
+        owner: Pubkey,
+        beneficiary: Option<Pubkey>,
+    },
+    WorkerRewardBeneficiarySet {
+        license: Pubkey,
+        owner: Pubkey,
+        beneficiary: Option<Pubkey>,
+    },
+    /// Rewards paid straight from the treasury, the haircut stays in it
+    TokensPaidInstantly {
+        owner: Pubkey,
+        payout: u64,
+        haircut: u64,
+    },
+    InstantPayoutHaircutSet {
+        haircut_bps: u16,
+    },
+}
+
+impl DepinEvent {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = EVENT_DISCRIMINATOR.to_vec();
+        // Serializing into a Vec cannot fail
+        self.serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Decodes one `sol_log_data` field, returns None for data that is not a depin event
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let payload = data.strip_prefix(&EVENT_DISCRIMINATOR)?;
+        Self::try_from_slice(payload).ok()
+    }
+
+    /// Decodes all events among the base64-decoded fields of the `Program data:` lines of a transaction
+    pub fn decode_all<'a>(fields: impl IntoIterator<Item = &'a [u8]>) -> Vec<Self> {
+        fields.into_iter().filter_map(Self::decode).collect()
+    }
+}
+
+pub fn emit(event: &DepinEvent) {
+    sol_log_data(&[&event.encode()]);
+}
@@ -3,6 +3,7 @@ use solana_program::pubkey::Pubkey;
 
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
 pub struct CnftContext {
     pub owner: Pubkey,
     pub delegate: Pubkey,
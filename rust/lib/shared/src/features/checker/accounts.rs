@@ -1,15 +1,26 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
-use crate::{constants::seeds::{CHECKER_SEED, LICENSE_SEED, METADATA_SEED}, types::account::DepinAccountType};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    constants::seeds::{CHECKER_SEED, LICENSE_SEED, METADATA_SEED},
+    types::account::{DepinAccount, DepinAccountType},
+    utils::account::header_size,
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct CheckerMetadata {
     pub suspended_at: Option<u64>,
     pub delegated_to: Pubkey,
+    /// Wallet that payouts are locked for instead of the license owner, set by the owner
+    pub reward_beneficiary: Option<Pubkey>,
 }
 
 impl CheckerMetadata {
-    pub const LEN: usize = 1 + 9 + 32;
+    pub const LEN: usize = header_size(<Self as DepinAccount>::VERSION) + 9 + 32 + 33;
+
+    /// Wallet that receives the payouts of a checker owned by `owner`
+    pub fn reward_recipient(&self, owner: &Pubkey) -> Pubkey {
+        self.reward_beneficiary.unwrap_or(*owner)
+    }
 
     pub fn find_pda(program_id: &Pubkey, checker_license: &Pubkey, checker: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[CHECKER_SEED, METADATA_SEED, checker_license.as_ref(), checker.as_ref()], program_id)
@@ -20,6 +31,29 @@ impl CheckerMetadata {
     }
 }
 
+impl DepinAccount for CheckerMetadata {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::CheckerMetadata;
+    /// Version 1 added `reward_beneficiary`
+    const VERSION: u8 = 1;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+
+    fn upgrade(version: u8, mut body: &[u8]) -> Result<Self, ProgramError> {
+        if version != 0 {
+            msg!("Error: Unknown CheckerMetadata version {}", version);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (suspended_at, delegated_to) = <(Option<u64>, Pubkey)>::deserialize(&mut body).map_err(|e| {
+            msg!("Error deserializing CheckerMetadata v0: {:?}", e);
+            ProgramError::InvalidAccountData
+        })?;
+        Ok(Self { suspended_at, delegated_to, reward_beneficiary: None })
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct CheckerLicenseMetadata {
     pub suspended_at: Option<u64>
@@ -35,4 +69,12 @@ impl CheckerLicenseMetadata {
     pub fn account_type() -> DepinAccountType {
         DepinAccountType::CheckerLicenseMetadata
     }
+}
+
+impl DepinAccount for CheckerLicenseMetadata {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::CheckerLicenseMetadata;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
 }
\ No newline at end of file
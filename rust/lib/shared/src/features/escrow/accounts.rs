@@ -0,0 +1,162 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    constants::{
+        network::{periods_covering_days, SECONDS_PER_PERIOD},
+        seeds::{AUTHORITY_SEED, ESCROW_SEED, STATE_SEED, TOKEN_SEED},
+    },
+    types::account::{DepinAccount, DepinAccountType},
+};
+
+/// Per-user, per-mint token account that holds escrowed funds. The account is its own authority.
+pub struct EscrowToken;
+
+impl EscrowToken {
+    pub fn find_pda(program_id: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ESCROW_SEED, TOKEN_SEED, owner.as_ref(), mint.as_ref()], program_id)
+    }
+}
+
+/// Commitments against the escrow of `owner` for `mint`. Encumbered funds back active
+/// payment streams and can only be withdrawn after a notice of `NOTICE_PERIODS` periods.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct EscrowState {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    /// Amount still owed to active commitments
+    pub encumbered: u64,
+    /// Amount announced for withdrawal regardless of commitments
+    pub pending_withdrawal: u64,
+    /// First period in which the pending withdrawal can be executed
+    pub unlock_period: u16,
+}
+
+impl EscrowState {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 2;
+
+    /// Gives workers two days, and at least one full settled period, to notice a pending withdrawal
+    pub const NOTICE_PERIODS: u16 = {
+        let periods = periods_covering_days(2, SECONDS_PER_PERIOD);
+        assert!(periods <= u16::MAX as i64, "DEPIN_PERIOD_SECONDS is too short for the withdrawal notice");
+        if periods < 2 { 2 } else { periods as u16 }
+    };
+
+    pub fn new(owner: Pubkey, mint: Pubkey) -> Self {
+        Self {
+            owner,
+            mint,
+            encumbered: 0,
+            pending_withdrawal: 0,
+            unlock_period: 0,
+        }
+    }
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::EscrowState
+    }
+
+    pub fn find_pda(program_id: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ESCROW_SEED, STATE_SEED, owner.as_ref(), mint.as_ref()], program_id)
+    }
+
+    /// Part of `balance` that is free to withdraw or commit right away
+    pub fn unencumbered(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.encumbered)
+    }
+
+    pub fn encumber(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.encumbered = self.encumbered.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Releases a settled or cancelled commitment, escrows drained after a notice can already be below it
+    pub fn release(&mut self, amount: u64) {
+        self.encumbered = self.encumbered.saturating_sub(amount);
+    }
+
+    pub fn is_notice_matured(&self, amount: u64, current_period: u16) -> bool {
+        self.pending_withdrawal >= amount && current_period >= self.unlock_period
+    }
+}
+
+impl DepinAccount for EscrowState {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::EscrowState;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// A key allowed to withdraw up to `spend_limit` of `mint` from the beneficiary's escrow
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EscrowDelegate {
+    pub delegate: Pubkey,
+    pub mint: Pubkey,
+    pub spend_limit: u64,
+    /// Total withdrawn by the delegate since it was last set
+    pub spent: u64,
+}
+
+impl EscrowDelegate {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+
+    pub fn remaining(&self) -> u64 {
+        self.spend_limit.saturating_sub(self.spent)
+    }
+}
+
+/// Withdraw delegates of one escrow beneficiary, across all of its mints
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct EscrowAuthority {
+    pub beneficiary: Pubkey,
+    pub delegates: Vec<EscrowDelegate>,
+}
+
+impl EscrowAuthority {
+    pub const MAX_DELEGATES: usize = 8;
+
+    /// Sized for the full list so adding a delegate never needs a reallocation
+    pub const LEN: usize = 1 + 32 + 4 + Self::MAX_DELEGATES * EscrowDelegate::LEN;
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::EscrowAuthority
+    }
+
+    pub fn find_pda(program_id: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[ESCROW_SEED, AUTHORITY_SEED, beneficiary.as_ref()], program_id)
+    }
+
+    pub fn get_mut(&mut self, delegate: &Pubkey, mint: &Pubkey) -> Option<&mut EscrowDelegate> {
+        self.delegates.iter_mut().find(|entry| entry.delegate == *delegate && entry.mint == *mint)
+    }
+
+    /// Adds or replaces the entry of `entry.delegate` for `entry.mint`, returns false when the list is full
+    pub fn upsert(&mut self, entry: EscrowDelegate) -> bool {
+        if let Some(existing) = self.get_mut(&entry.delegate, &entry.mint) {
+            *existing = entry;
+            return true;
+        }
+
+        if self.delegates.len() >= Self::MAX_DELEGATES {
+            return false;
+        }
+
+        self.delegates.push(entry);
+        true
+    }
+
+    /// Removes the entry of `delegate` for `mint`, returns false when it was not listed
+    pub fn remove(&mut self, delegate: &Pubkey, mint: &Pubkey) -> bool {
+        let len = self.delegates.len();
+        self.delegates.retain(|entry| !(entry.delegate == *delegate && entry.mint == *mint));
+        self.delegates.len() != len
+    }
+}
+
+impl DepinAccount for EscrowAuthority {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::EscrowAuthority;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
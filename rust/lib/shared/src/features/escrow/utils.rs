@@ -0,0 +1,233 @@
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    constants::seeds::{ESCROW_SEED, STATE_SEED, TOKEN_SEED},
+    events::{emit, DepinEvent},
+    features::{escrow::accounts::{EscrowAuthority, EscrowState, EscrowToken}, global::accounts::AllowedMints},
+    utils::{account::{create_pda_account, read_account_data, write_account_data}, token::transfer_signed},
+};
+
+/// Checks the escrow token PDA of `owner` for `mint` exists and returns its bump
+pub fn validate_escrow_token_account(
+    program_id: &Pubkey,
+    escrow_token_account: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (escrow_pda, bump_seed) = EscrowToken::find_pda(program_id, owner, mint);
+
+    if *escrow_token_account.key != escrow_pda {
+        msg!("Error: Escrow token account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if escrow_token_account.data_is_empty() {
+        msg!("Error: Escrow token account does not exist");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    Ok(bump_seed)
+}
+
+/// Moves `amount` out of the escrow of `owner`, signing with the escrow PDA
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from_escrow<'a>(
+    token_program: &AccountInfo<'a>,
+    escrow_token_account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    owner: &Pubkey,
+    bump_seed: u8,
+    amount: u64,
+    decimals: u8,
+) -> Result<(), ProgramError> {
+    emit(&DepinEvent::EscrowTransferred {
+        owner: *owner,
+        mint: *mint.key,
+        destination: *destination.key,
+        amount,
+    });
+
+    transfer_signed(
+        token_program,
+        escrow_token_account,
+        mint,
+        destination,
+        escrow_token_account,
+        amount,
+        decimals,
+        &[ESCROW_SEED, TOKEN_SEED, owner.as_ref(), mint.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Reads the AllowedMints account after checking its PDA
+pub fn load_allowed_mints(program_id: &Pubkey, allowed_mints_account: &AccountInfo) -> Result<AllowedMints, ProgramError> {
+    let (allowed_mints_pda, _) = AllowedMints::find_pda(program_id);
+    if *allowed_mints_account.key != allowed_mints_pda {
+        msg!("Error: AllowedMints account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if allowed_mints_account.data_is_empty() {
+        msg!("Error: AllowedMints account does not exist, no mint is accepted yet");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    read_account_data(&allowed_mints_account.try_borrow_data()?, AllowedMints::account_type())
+}
+
+/// Deposits need a listed, unpaused mint and an amount within its limit
+pub fn validate_deposit_mint(
+    program_id: &Pubkey,
+    allowed_mints_account: &AccountInfo,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let allowed_mints = load_allowed_mints(program_id, allowed_mints_account)?;
+
+    let Some(entry) = allowed_mints.get(mint) else {
+        msg!("Error: Token mint {} is not supported for deposits", mint);
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    if entry.paused {
+        msg!("Error: Deposits of mint {} are paused", mint);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if amount > entry.max_deposit {
+        msg!("Error: Deposit of {} exceeds the limit of {} for mint {}", amount, entry.max_deposit, mint);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Withdrawals are only blocked while a listed mint is paused, delisted mints can always be withdrawn
+pub fn validate_withdrawal_mint(
+    program_id: &Pubkey,
+    allowed_mints_account: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<(), ProgramError> {
+    let (allowed_mints_pda, _) = AllowedMints::find_pda(program_id);
+    if *allowed_mints_account.key != allowed_mints_pda {
+        msg!("Error: AllowedMints account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if allowed_mints_account.data_is_empty() {
+        return Ok(());
+    }
+
+    let allowed_mints = load_allowed_mints(program_id, allowed_mints_account)?;
+
+    if allowed_mints.get(mint).is_some_and(|entry| entry.paused) {
+        msg!("Error: Withdrawals of mint {} are paused", mint);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Charges `amount` against the allowance `delegate` holds on the escrow of `owner` for `mint`
+pub fn spend_escrow_allowance(
+    program_id: &Pubkey,
+    escrow_authority_account: &AccountInfo,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let (escrow_authority_pda, _) = EscrowAuthority::find_pda(program_id, owner);
+    if *escrow_authority_account.key != escrow_authority_pda {
+        msg!("Error: EscrowAuthority account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if escrow_authority_account.data_is_empty() {
+        msg!("Error: Escrow of {} has no delegates", owner);
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let mut escrow_authority: EscrowAuthority = read_account_data(
+        &escrow_authority_account.try_borrow_data()?,
+        EscrowAuthority::account_type(),
+    )?;
+
+    let Some(entry) = escrow_authority.get_mut(delegate, mint) else {
+        msg!("Error: {} is not an escrow delegate of {} for mint {}", delegate, owner, mint);
+        return Err(ProgramError::IllegalOwner);
+    };
+
+    if amount > entry.remaining() {
+        msg!("Error: Withdrawal of {} exceeds the remaining delegate allowance of {}", amount, entry.remaining());
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    entry.spent += amount;
+
+    let mut data = escrow_authority_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, EscrowAuthority::account_type(), &escrow_authority)
+}
+
+/// Reads the EscrowState of `owner` for `mint` after checking its PDA, a missing account has no commitments
+pub fn load_escrow_state(
+    program_id: &Pubkey,
+    escrow_state_account: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<EscrowState, ProgramError> {
+    let (escrow_state_pda, _) = EscrowState::find_pda(program_id, owner, mint);
+    if *escrow_state_account.key != escrow_state_pda {
+        msg!("Error: EscrowState account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if escrow_state_account.data_is_empty() {
+        return Ok(EscrowState::new(*owner, *mint));
+    }
+
+    read_account_data(&escrow_state_account.try_borrow_data()?, EscrowState::account_type())
+}
+
+/// Writes the EscrowState, creating its account first if needed
+pub fn store_escrow_state<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    escrow_state_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    escrow_state: &EscrowState,
+) -> Result<(), ProgramError> {
+    if escrow_state_account.data_is_empty() {
+        let (_, bump_seed) = EscrowState::find_pda(program_id, &escrow_state.owner, &escrow_state.mint);
+        create_pda_account(
+            payer,
+            escrow_state_account,
+            system_program,
+            program_id,
+            EscrowState::LEN,
+            &[ESCROW_SEED, STATE_SEED, escrow_state.owner.as_ref(), escrow_state.mint.as_ref(), &[bump_seed]],
+        )?;
+    }
+
+    let mut data = escrow_state_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, EscrowState::account_type(), escrow_state)
+}
+
+/// Releases `amount` of the commitments on the escrow, a missing account has nothing to release
+pub fn release_escrow(
+    program_id: &Pubkey,
+    escrow_state_account: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let mut escrow_state = load_escrow_state(program_id, escrow_state_account, owner, mint)?;
+    if escrow_state_account.data_is_empty() {
+        return Ok(());
+    }
+
+    escrow_state.release(amount);
+
+    let mut data = escrow_state_account.try_borrow_mut_data()?;
+    write_account_data(&mut data, EscrowState::account_type(), &escrow_state)
+}
@@ -1,6 +1,11 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::pubkey::Pubkey;
-use crate::{constants::seeds::{GLOBAL_SEED, STATE_SEED}, types::{account::DepinAccountType, ring_buffer::RingBuffer}};
+use crate::{
+    constants::seeds::{GLOBAL_SEED, MINTS_SEED, PAUSE_SEED, STATE_SEED},
+    types::{account::{DepinAccount, DepinAccountType}, ring_buffer::RingBuffer},
+    utils::zero_copy::{PodU64, ZeroCopyAccount},
+};
 
 /// Encoded period-checker data as u64
 /// - Period: 16 bits (0-65535)
@@ -51,27 +56,8 @@ impl BMBState {
 
     /// This method walks backwards from the current write position to find the most recent period <= target_period
     pub fn get_checker_count_for_period(&self, target_period: u16) -> Option<u32> {
-        let current_idx = self.period_checkers_buffer.current_index() as usize;
-        
-        // Walk backwards from current position to find the most recent period <= target_period
-        for i in 0..16 {
-            let idx = if current_idx >= i { current_idx - i } else { 16 + current_idx - i };
-            let value = self.period_checkers_buffer.data[idx];
-            
-            if value == 0 {
-                continue; // Skip empty entries
-            }
-            
-            let (period, checker_count) = PeriodCheckersCodec::decode(value);
-            
-            // Since we're walking backwards and periods increase, 
-            // the first period <= target_period is our answer
-            if period <= target_period {
-                return Some(checker_count);
-            }
-        }
-        
-        None
+        let data = &self.period_checkers_buffer.data;
+        find_checker_count(self.current_index(), |idx| data[idx], target_period)
     }
 
     pub fn get_all_entries(&self) -> Vec<(u16, u32)> {
@@ -84,4 +70,173 @@ impl BMBState {
     pub fn current_index(&self) -> u8 {
         self.period_checkers_buffer.current_index()
     }
-}
\ No newline at end of file
+}
+
+impl DepinAccount for BMBState {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::BMBState;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+impl Default for BMBState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Zero-copy view of BMBState, read on every proof submission
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct BMBStateView {
+    pub period_checkers: [PodU64; 16],
+    pub current_index: u8,
+}
+
+impl ZeroCopyAccount for BMBStateView {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::BMBState;
+}
+
+impl BMBStateView {
+    pub fn get_checker_count_for_period(&self, target_period: u16) -> Option<u32> {
+        find_checker_count(self.current_index, |idx| self.period_checkers[idx].get(), target_period)
+    }
+}
+
+fn find_checker_count(current_index: u8, entry: impl Fn(usize) -> PeriodCheckersData, target_period: u16) -> Option<u32> {
+    let current_idx = current_index as usize;
+
+    // Walk backwards from current position to find the most recent period <= target_period
+    for i in 0..16 {
+        let idx = if current_idx >= i { current_idx - i } else { 16 + current_idx - i };
+        let value = entry(idx);
+
+        if value == 0 {
+            continue; // Skip empty entries
+        }
+
+        let (period, checker_count) = PeriodCheckersCodec::decode(value);
+
+        // Since we're walking backwards and periods increase,
+        // the first period <= target_period is our answer
+        if period <= target_period {
+            return Some(checker_count);
+        }
+    }
+
+    None
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+    /// Largest amount accepted by a single escrow deposit
+    pub max_deposit: u64,
+    /// Paused mints accept neither deposits nor withdrawals
+    pub paused: bool,
+}
+
+impl AllowedMint {
+    pub const LEN: usize = 32 + 8 + 1;
+}
+
+/// Admin managed list of mints the escrow accepts
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct AllowedMints {
+    pub mints: Vec<AllowedMint>,
+}
+
+impl AllowedMints {
+    pub const MAX_MINTS: usize = 16;
+
+    /// Sized for the full list so adding a mint never needs a reallocation
+    pub const LEN: usize = 1 + 4 + Self::MAX_MINTS * AllowedMint::LEN;
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::AllowedMints
+    }
+
+    pub fn find_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[GLOBAL_SEED, MINTS_SEED], program_id)
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<&AllowedMint> {
+        self.mints.iter().find(|entry| entry.mint == *mint)
+    }
+
+    /// Adds or replaces the entry of `entry.mint`, returns false when the list is full
+    pub fn upsert(&mut self, entry: AllowedMint) -> bool {
+        if let Some(existing) = self.mints.iter_mut().find(|existing| existing.mint == entry.mint) {
+            *existing = entry;
+            return true;
+        }
+
+        if self.mints.len() >= Self::MAX_MINTS {
+            return false;
+        }
+
+        self.mints.push(entry);
+        true
+    }
+
+    /// Removes the entry of `mint`, returns false when it was not listed
+    pub fn remove(&mut self, mint: &Pubkey) -> bool {
+        let len = self.mints.len();
+        self.mints.retain(|entry| entry.mint != *mint);
+        self.mints.len() != len
+    }
+}
+
+impl DepinAccount for AllowedMints {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::AllowedMints;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Admin controlled emergency switch, each bit stops one group of instructions before dispatch.
+/// Pausing applies at once, clearing bits is scheduled and takes effect after `UNPAUSE_DELAY`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PauseState {
+    pub flags: u8,
+    /// Flags requested by the last unpause, applied once `unpause_at` has passed
+    pub pending_flags: u8,
+    /// Unix timestamp from which the pending unpause can be applied, 0 when none is scheduled
+    pub unpause_at: i64,
+}
+
+impl PauseState {
+    pub const PROOFS: u8 = 1 << 0;
+    pub const PAYOUTS: u8 = 1 << 1;
+    pub const UNLOCKS: u8 = 1 << 2;
+    pub const ESCROW: u8 = 1 << 3;
+    pub const ACTIVATION: u8 = 1 << 4;
+    pub const ALL: u8 = Self::PROOFS | Self::PAYOUTS | Self::UNLOCKS | Self::ESCROW | Self::ACTIVATION;
+
+    /// Seconds between scheduling an unpause and applying it
+    pub const UNPAUSE_DELAY: i64 = 24 * 60 * 60;
+
+    pub const LEN: usize = 1 + 1 + 1 + 8;
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::PauseState
+    }
+
+    pub fn find_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[GLOBAL_SEED, PAUSE_SEED], program_id)
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+impl DepinAccount for PauseState {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::PauseState;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
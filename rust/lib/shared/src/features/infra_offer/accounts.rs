@@ -0,0 +1,136 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    constants::seeds::{INFRA_SEED, OFFER_SEED},
+    features::worker::accounts::{is_region_code, WorkerDiscovery, WorkerProof},
+    types::account::{DepinAccount, DepinAccountType},
+};
+
+/// Service levels a provider commits to, checked against each period's WorkerProof
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct SlaTerms {
+    pub min_uptime: u32,
+    pub max_latency: u32,
+    /// Share of a period's price returned to the buyer when the period breaches the terms
+    pub penalty_bps: u16,
+}
+
+impl SlaTerms {
+    pub const LEN: usize = 4 + 4 + 2;
+    pub const MAX_PENALTY_BPS: u16 = 10_000;
+
+    pub fn validate(&self) -> ProgramResult {
+        if self.penalty_bps == 0 || self.penalty_bps > Self::MAX_PENALTY_BPS {
+            msg!("Error: SLA penalty must be between 1 and {} bps", Self::MAX_PENALTY_BPS);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(())
+    }
+
+    pub fn is_breached_by(&self, proof: &WorkerProof) -> bool {
+        proof.uptime < self.min_uptime || proof.latency > self.max_latency
+    }
+
+    pub fn penalty(&self, price: u64) -> u64 {
+        (price as u128 * self.penalty_bps as u128 / Self::MAX_PENALTY_BPS as u128) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub enum InfraOfferStatus {
+    Active,
+    Paused,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct InfraOffer {
+    pub provider: Pubkey,
+    pub worker_license: Pubkey,
+    pub offer_id: u16,
+    pub status: InfraOfferStatus,
+    pub cpu: u64,
+    pub memory: u64,
+    pub region: String,
+    pub price_mint: Pubkey,
+    pub price_per_period: u64,
+    pub name: String,
+    pub description: String,
+    pub sla: Option<SlaTerms>,
+}
+
+impl InfraOffer {
+    /// Offer ids are dense per worker license so buyers can derive every offer PDA
+    pub const MAX_OFFERS_PER_LICENSE: u16 = 16;
+    pub const MAX_REGION_LEN: usize = WorkerDiscovery::MAX_REGION_LEN;
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_DESCRIPTION_LEN: usize = 128;
+
+    /// Sized for the longest strings so updates never need a reallocation
+    pub const LEN: usize = 1
+        + 32 + 32 + 2 + 1
+        + 8 + 8
+        + 4 + Self::MAX_REGION_LEN
+        + 32 + 8
+        + 4 + Self::MAX_NAME_LEN
+        + 4 + Self::MAX_DESCRIPTION_LEN
+        + 1 + SlaTerms::LEN;
+
+    pub fn find_pda(program_id: &Pubkey, worker_license: &Pubkey, offer_id: u16) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[INFRA_SEED, OFFER_SEED, worker_license.as_ref(), &offer_id.to_le_bytes()], program_id)
+    }
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::InfraOffer
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == InfraOfferStatus::Active
+    }
+
+    /// Checks the provider supplied fields shared by register and update
+    pub fn validate_terms(
+        cpu: u64,
+        memory: u64,
+        region: &str,
+        price_per_period: u64,
+        name: &str,
+        description: &str,
+    ) -> ProgramResult {
+        if cpu == 0 || memory == 0 {
+            msg!("Error: Offer must provide a non-zero amount of CPU and memory");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if !is_region_code(region) {
+            msg!("Error: Region must be 1-{} characters of a-z, 0-9 or '-'", Self::MAX_REGION_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if price_per_period == 0 {
+            msg!("Error: Offer price must be greater than zero");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if name.is_empty() || name.len() > Self::MAX_NAME_LEN {
+            msg!("Error: Offer name must be 1-{} bytes", Self::MAX_NAME_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if description.len() > Self::MAX_DESCRIPTION_LEN {
+            msg!("Error: Offer description must be at most {} bytes", Self::MAX_DESCRIPTION_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
+
+impl DepinAccount for InfraOffer {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::InfraOffer;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
@@ -0,0 +1,92 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use crate::{
+    constants::seeds::{LEASE_SEED, VAULT_SEED},
+    features::infra_offer::accounts::SlaTerms,
+    types::account::{DepinAccount, DepinAccountType},
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaseStatus {
+    Active,
+    Cancelled,
+}
+
+/// A buyer's order against an InfraOffer. The full price is held in the lease vault
+/// and released to the license owner one period at a time.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Lease {
+    pub offer: Pubkey,
+    /// Owner of the license when the lease was opened, payments go to its current owner
+    pub provider: Pubkey,
+    pub worker_license: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub lease_id: u64,
+    pub price_per_period: u64,
+    pub start_period: u16,
+    pub periods: u16,
+    /// Bit `i` is set once period `start_period + i` has been paid out or refunded
+    pub settled_mask: u64,
+    /// Bit `i` is set once the SLA penalty of period `start_period + i` has been refunded
+    pub penalized_mask: u64,
+    pub status: LeaseStatus,
+    pub sla: Option<SlaTerms>,
+}
+
+impl Lease {
+    /// Bounded by the width of `settled_mask`, so it stays a count of periods whatever their length
+    pub const MAX_PERIODS: u16 = 64;
+
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 2 + 2 + 8 + 8 + 1 + 1 + SlaTerms::LEN;
+
+    pub fn find_pda(program_id: &Pubkey, offer: &Pubkey, buyer: &Pubkey, lease_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[LEASE_SEED, offer.as_ref(), buyer.as_ref(), &lease_id.to_le_bytes()], program_id)
+    }
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::Lease
+    }
+
+    /// Exclusive upper bound of the leased periods
+    pub fn end_period(&self) -> u16 {
+        self.start_period + self.periods
+    }
+
+    pub fn covers(&self, period: u16) -> bool {
+        period >= self.start_period && period < self.end_period()
+    }
+
+    pub fn is_settled(&self, period: u16) -> bool {
+        self.settled_mask & (1u64 << (period - self.start_period)) != 0
+    }
+
+    pub fn mark_settled(&mut self, period: u16) {
+        self.settled_mask |= 1u64 << (period - self.start_period);
+    }
+
+    pub fn is_penalized(&self, period: u16) -> bool {
+        self.penalized_mask & (1u64 << (period - self.start_period)) != 0
+    }
+
+    pub fn mark_penalized(&mut self, period: u16) {
+        self.penalized_mask |= 1u64 << (period - self.start_period);
+    }
+}
+
+impl DepinAccount for Lease {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::Lease;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Token account holding the prepaid price of a lease. The account is its own authority.
+pub struct LeaseVault;
+
+impl LeaseVault {
+    pub fn find_pda(program_id: &Pubkey, lease: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[LEASE_SEED, VAULT_SEED, lease.as_ref()], program_id)
+    }
+}
@@ -0,0 +1,65 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{constants::seeds::STREAM_SEED, types::account::{DepinAccount, DepinAccountType}};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentStreamStatus {
+    Active,
+    Stopped,
+}
+
+/// Pays `rate_per_period` from the payer's escrow to the worker owner for every
+/// period in `start_period..end_period` that has a WorkerProof
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct PaymentStream {
+    pub payer: Pubkey,
+    pub worker_license: Pubkey,
+    pub mint: Pubkey,
+    pub stream_id: u64,
+    pub rate_per_period: u64,
+    pub start_period: u16,
+    pub end_period: u16,
+    /// Next period the crank will settle
+    pub next_period: u16,
+    pub status: PaymentStreamStatus,
+}
+
+impl PaymentStream {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 2 + 2 + 2 + 1;
+
+    pub fn find_pda(program_id: &Pubkey, payer: &Pubkey, worker_license: &Pubkey, stream_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[STREAM_SEED, payer.as_ref(), worker_license.as_ref(), &stream_id.to_le_bytes()], program_id)
+    }
+
+    pub fn account_type() -> DepinAccountType {
+        DepinAccountType::PaymentStream
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == PaymentStreamStatus::Active
+    }
+
+    /// Amount a stream paying `rate_per_period` for `periods` periods encumbers
+    pub fn commitment(periods: u16, rate_per_period: u64) -> Result<u64, ProgramError> {
+        (periods as u64).checked_mul(rate_per_period).ok_or_else(|| {
+            msg!("Error: Stream commitment overflows");
+            ProgramError::ArithmeticOverflow
+        })
+    }
+
+    /// Amount the stream still holds encumbered in the payer's escrow
+    pub fn remaining_commitment(&self) -> Result<u64, ProgramError> {
+        if !self.is_active() {
+            return Ok(0);
+        }
+        Self::commitment(self.end_period.saturating_sub(self.next_period), self.rate_per_period)
+    }
+}
+
+impl DepinAccount for PaymentStream {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::PaymentStream;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
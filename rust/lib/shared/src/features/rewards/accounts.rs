@@ -1,7 +1,15 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::{pubkey::Pubkey, program_error::ProgramError};
 
-use crate::constants::{seeds::{GLOBAL_REWARDS_SEED, GLOBAL_SEED}, accounts::DISC_SIZE};
+use crate::{
+    constants::{
+        network::{daily_amount_per_period, SECONDS_PER_PERIOD},
+        seeds::{GLOBAL_REWARDS_SEED, GLOBAL_SEED},
+    },
+    types::account::DepinAccountType,
+    utils::zero_copy::{load_account, load_account_mut, PodU32, ZeroCopyAccount},
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct GlobalRewards {
@@ -16,7 +24,14 @@ impl GlobalRewards {
         Pubkey::find_program_address(&[GLOBAL_SEED, GLOBAL_REWARDS_SEED], program_id)
     }
 
-    pub fn get_checker_reward(period: u16) -> u16 {
+    /// Reward per period, stepping down with the calendar months since PERIOD_ZERO.
+    /// The schedule is set per day and scaled to the configured period length.
+    pub fn get_checker_reward(period: u16) -> u32 {
+        daily_amount_per_period(Self::daily_checker_reward(period) as u32, SECONDS_PER_PERIOD)
+    }
+
+    /// Dates below are for the default origin of 2025-06-01
+    fn daily_checker_reward(period: u16) -> u16 {
         let month = crate::utils::bmb::get_month_from_period(period);
 
         match month {
@@ -56,50 +71,42 @@ impl GlobalRewards {
     }
 
     pub fn read_checker_balance(account_data: &[u8], checker_index: usize) -> Result<u32, ProgramError> {
-        if checker_index >= Self::ELEMENTS {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let checker_bytes = &account_data[DISC_SIZE..];
-        const ELEM_SIZE: usize = core::mem::size_of::<u32>();
-        
-        let start = checker_index * ELEM_SIZE;
-        let end = start + ELEM_SIZE;
-        
-        let balance = u32::from_le_bytes(checker_bytes[start..end].try_into().unwrap());
-        Ok(balance)
+        load_account::<GlobalRewardsView>(account_data)?.checker_balance(checker_index)
     }
 
     pub fn add_checker_balance(account_data: &mut [u8], checker_index: usize, reward_amount: u32) -> Result<(), ProgramError> {
-        if checker_index >= Self::ELEMENTS {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let checker_bytes = &mut account_data[DISC_SIZE..];
-        const ELEM_SIZE: usize = core::mem::size_of::<u32>();
-        
-        let start = checker_index * ELEM_SIZE;
-        let end = start + ELEM_SIZE;
-
-        let current_balance = u32::from_le_bytes(checker_bytes[start..end].try_into().unwrap());
-        let new_balance = current_balance.saturating_add(reward_amount);
-        
-        checker_bytes[start..end].copy_from_slice(&new_balance.to_le_bytes());
-        Ok(())
+        load_account_mut::<GlobalRewardsView>(account_data)?.add_checker_balance(checker_index, reward_amount)
     }
 
     pub fn reset_checker_balance(account_data: &mut [u8], checker_index: usize) -> Result<(), ProgramError> {
-        if checker_index >= Self::ELEMENTS {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        load_account_mut::<GlobalRewardsView>(account_data)?.reset_checker_balance(checker_index)
+    }
+}
+
+/// Zero-copy view of GlobalRewards, the account is too large to deserialize
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct GlobalRewardsView {
+    pub checkers: [PodU32; GlobalRewards::ELEMENTS],
+}
+
+impl ZeroCopyAccount for GlobalRewardsView {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::GlobalRewards;
+}
 
-        let checker_bytes = &mut account_data[DISC_SIZE..];
-        const ELEM_SIZE: usize = core::mem::size_of::<u32>();
-        
-        let start = checker_index * ELEM_SIZE;
-        let end = start + ELEM_SIZE;
+impl GlobalRewardsView {
+    pub fn checker_balance(&self, checker_index: usize) -> Result<u32, ProgramError> {
+        Ok(self.checkers.get(checker_index).ok_or(ProgramError::InvalidInstructionData)?.get())
+    }
+
+    pub fn add_checker_balance(&mut self, checker_index: usize, reward_amount: u32) -> Result<(), ProgramError> {
+        let balance = self.checkers.get_mut(checker_index).ok_or(ProgramError::InvalidInstructionData)?;
+        balance.set(balance.get().saturating_add(reward_amount));
+        Ok(())
+    }
 
-        checker_bytes[start..end].copy_from_slice(&0u32.to_le_bytes());
+    pub fn reset_checker_balance(&mut self, checker_index: usize) -> Result<(), ProgramError> {
+        self.checkers.get_mut(checker_index).ok_or(ProgramError::InvalidInstructionData)?.set(0);
         Ok(())
     }
 }
@@ -1,7 +1,12 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
+use bytemuck::{Pod, Zeroable};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::{constants::seeds::{LOCK_SEED, STATE_SEED, TREASURY_SEED, CONFIG_SEED}, types::account::DepinAccountType};
+use crate::{
+    constants::seeds::{LOCK_SEED, STATE_SEED, TREASURY_SEED, CONFIG_SEED},
+    types::account::{DepinAccount, DepinAccountType},
+    utils::{account::header_size, zero_copy::{PodU64, ZeroCopyAccount}},
+};
 
 pub struct TreasuryAuthority;
 
@@ -47,20 +52,78 @@ impl TreasuryState {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+impl DepinAccount for TreasuryState {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::TreasuryState;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+impl Default for TreasuryState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Zero-copy view of TreasuryState, updated on every lock and unlock
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TreasuryStateView {
+    pub locked_balance: PodU64,
+}
+
+impl ZeroCopyAccount for TreasuryStateView {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::TreasuryState;
+}
+
+impl TreasuryStateView {
+    pub fn add_locked_balance(&mut self, amount: u64) {
+        self.locked_balance.set(self.locked_balance.get().saturating_add(amount));
+    }
+
+    pub fn subtract_locked_balance(&mut self, amount: u64) {
+        self.locked_balance.set(self.locked_balance.get().saturating_sub(amount));
+    }
+
+    pub fn get_locked_balance(&self) -> u64 {
+        self.locked_balance.get()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
 pub struct TreasuryConfig {
     pub checker_rewards_lock_days: u16,
+    /// Share of an instant checker payout that stays in the treasury, in basis points
+    pub instant_payout_haircut_bps: u16,
 }
 
 impl TreasuryConfig {
-    pub const LEN: usize = 1 + 2;
+    pub const LEN: usize = header_size(<Self as DepinAccount>::VERSION) + 2 + 2;
+    pub const DEFAULT_INSTANT_PAYOUT_HAIRCUT_BPS: u16 = 5_000;
+    /// A haircut of 100% would pay nothing, so it must stay below this
+    pub const MAX_INSTANT_PAYOUT_HAIRCUT_BPS: u16 = 9_999;
+    /// Configs upgraded from version 0 never had a haircut set, they refuse instant payouts
+    /// until the admin sets one
+    pub const INSTANT_PAYOUTS_DISABLED: u16 = 10_000;
 
     pub fn new() -> Self {
         Self {
             checker_rewards_lock_days: 365,
+            instant_payout_haircut_bps: Self::DEFAULT_INSTANT_PAYOUT_HAIRCUT_BPS,
         }
     }
 
+    pub fn instant_payouts_enabled(&self) -> bool {
+        self.instant_payout_haircut_bps <= Self::MAX_INSTANT_PAYOUT_HAIRCUT_BPS
+    }
+
+    /// Splits `amount` into (payout, haircut) for an instant payout
+    pub fn instant_payout_split(&self, amount: u64) -> (u64, u64) {
+        let haircut = (amount as u128 * self.instant_payout_haircut_bps as u128 / 10_000) as u64;
+        (amount - haircut, haircut)
+    }
+
     pub fn find_pda(program_id: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(&[TREASURY_SEED, CONFIG_SEED], program_id)
     }
@@ -70,6 +133,35 @@ impl TreasuryConfig {
     }
 }
 
+impl DepinAccount for TreasuryConfig {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::TreasuryConfig;
+    /// Version 1 added `instant_payout_haircut_bps`
+    const VERSION: u8 = 1;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+
+    fn upgrade(version: u8, mut body: &[u8]) -> Result<Self, ProgramError> {
+        if version != 0 {
+            msg!("Error: Unknown TreasuryConfig version {}", version);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let checker_rewards_lock_days = u16::deserialize(&mut body).map_err(|e| {
+            msg!("Error deserializing TreasuryConfig v0: {:?}", e);
+            ProgramError::InvalidAccountData
+        })?;
+        Ok(Self { checker_rewards_lock_days, instant_payout_haircut_bps: Self::INSTANT_PAYOUTS_DISABLED })
+    }
+}
+
+impl Default for TreasuryConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct LockedTokens {
     pub owner: Pubkey,
@@ -113,3 +205,11 @@ impl LockedTokens {
         self.total_locked = self.total_locked.saturating_add(amount);
     }
 }
+
+impl DepinAccount for LockedTokens {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::LockedTokens;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
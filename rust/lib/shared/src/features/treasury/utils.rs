@@ -3,11 +3,10 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
     program::invoke_signed,
-    system_instruction,
     sysvar::{rent::Rent, Sysvar},
-    clock::Clock,
     msg,
 };
+use solana_system_interface::instruction as system_instruction;
 use spl_token::{
     instruction as token_instruction,
     solana_program::program_pack::Pack,
@@ -17,11 +16,17 @@ use spl_associated_token_account::get_associated_token_address;
 
 use crate::{
     constants::{accounts::BMB_MINT, seeds::{TREASURY_SEED, LOCK_SEED}},
-    features::treasury::accounts::{TreasuryState, TreasuryAuthority, LockedTokens},
-    utils::{account::{read_account_data, write_account_data}, bmb::get_current_period},
+    events::{emit, DepinEvent},
+    features::treasury::accounts::{TreasuryState, TreasuryStateView, TreasuryAuthority, LockedTokens},
+    utils::{
+        account::{read_account_data, write_account_data},
+        bmb::{days_to_periods, PeriodClock},
+        zero_copy::{load_account, load_account_mut},
+    },
 };
 
 /// Creates or adds to locked tokens for a user with period-based accumulation
+#[allow(clippy::too_many_arguments)]
 pub fn grant_locked<'a>(
     program_id: &Pubkey,
     payer_account: &AccountInfo<'a>,
@@ -32,10 +37,9 @@ pub fn grant_locked<'a>(
     owner: &Pubkey,
     amount: u64,
     lock_duration_days: u16,  // Duration in days (e.g., 365 for 12 months)
+    clock: &impl PeriodClock,
 ) -> Result<(), ProgramError> {
-    // Get current period and calculate unlock period
-    let current_period = get_current_period();
-    let unlock_period = current_period + lock_duration_days;
+    let (current_period, unlock_period) = lock_schedule(clock, lock_duration_days)?;
 
     // Calculate expected locked tokens PDA using current and unlock period
     let (locked_tokens_pda, bump_seed) = LockedTokens::find_pda(program_id, owner, current_period, unlock_period);
@@ -63,12 +67,9 @@ pub fn grant_locked<'a>(
 
     // Check treasury has sufficient available balance
     let treasury_account = TokenAccount::unpack(&treasury_ata_account.try_borrow_data()?)?;
-    let mut treasury_state: TreasuryState = read_account_data(
-        &treasury_state_account.try_borrow_data()?,
-        TreasuryState::account_type(),
-    )?;
+    let locked_balance = load_account::<TreasuryStateView>(&treasury_state_account.try_borrow_data()?)?.get_locked_balance();
 
-    let available_balance = treasury_account.amount.saturating_sub(treasury_state.locked_balance);
+    let available_balance = treasury_account.amount.saturating_sub(locked_balance);
     if available_balance < amount {
         msg!("Error: Insufficient available treasury balance. Available: {}, Required: {}", 
             available_balance, amount);
@@ -145,14 +146,115 @@ pub fn grant_locked<'a>(
     }
 
     // Update treasury state to reflect locked commitment
-    treasury_state.add_locked_balance(amount);
-    let mut treasury_state_data = treasury_state_account.try_borrow_mut_data()?;
-    write_account_data(&mut treasury_state_data, TreasuryState::account_type(), &treasury_state)?;
+    load_account_mut::<TreasuryStateView>(&mut treasury_state_account.try_borrow_mut_data()?)?.add_locked_balance(amount);
+
+    emit(&DepinEvent::TokensLocked {
+        owner: *owner,
+        amount,
+        lock_period: current_period,
+        unlock_period,
+    });
 
     msg!("Successfully created locked tokens: {} BMB locked until period {}", amount, unlock_period);
     Ok(())
 }
 
+/// Pays rewards straight from the treasury ATA to the recipient's BMB token account, the haircut stays in the treasury
+#[allow(clippy::too_many_arguments)]
+pub fn pay_instantly<'a>(
+    program_id: &Pubkey,
+    treasury_state_account: &AccountInfo<'a>,
+    treasury_ata_account: &AccountInfo<'a>,
+    treasury_authority_account: &AccountInfo<'a>,
+    recipient_token_account: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    recipient: &Pubkey,
+    payout_amount: u64,
+    haircut_amount: u64,
+) -> Result<(), ProgramError> {
+    // Validate treasury state account
+    let (treasury_state_pda, _) = TreasuryState::find_pda(program_id);
+    if *treasury_state_account.key != treasury_state_pda {
+        msg!("Error: TreasuryState account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate treasury authority account
+    let (treasury_authority_pda, treasury_authority_bump) = TreasuryAuthority::find_pda(program_id);
+    if *treasury_authority_account.key != treasury_authority_pda {
+        msg!("Error: Treasury authority account does not match expected PDA. Expected: {}, Provided: {}", 
+            treasury_authority_pda, treasury_authority_account.key);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Validate treasury ATA account
+    let expected_treasury_ata = get_associated_token_address(&treasury_authority_pda, &BMB_MINT);
+    if *treasury_ata_account.key != expected_treasury_ata {
+        msg!("Error: Treasury ATA account does not match expected address. Expected: {}, Provided: {}", 
+            expected_treasury_ata, treasury_ata_account.key);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The treasury authority signs the transfer, so it may only be handed to the token program
+    if *token_program.key != spl_token::id() {
+        msg!("Error: Token program account is not the SPL Token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Tokens promised to locks are not available for instant payouts
+    let treasury_account = TokenAccount::unpack(&treasury_ata_account.try_borrow_data()?)?;
+    let locked_balance = load_account::<TreasuryStateView>(&treasury_state_account.try_borrow_data()?)?.get_locked_balance();
+
+    let available_balance = treasury_account.amount.saturating_sub(locked_balance);
+    if available_balance < payout_amount {
+        msg!("Error: Insufficient available treasury balance. Available: {}, Required: {}", 
+            available_balance, payout_amount);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // Validate recipient token account
+    let recipient_token_state = TokenAccount::unpack(&recipient_token_account.try_borrow_data()?)?;
+    if recipient_token_state.mint != BMB_MINT {
+        msg!("Error: Recipient token account is not for BMB mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if recipient_token_state.owner != *recipient {
+        msg!("Error: Token account is not owned by the reward recipient");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    invoke_signed(
+        &token_instruction::transfer(
+            token_program.key,
+            treasury_ata_account.key,
+            recipient_token_account.key,
+            &treasury_authority_pda,
+            &[],
+            payout_amount,
+        )?,
+        &[
+            treasury_ata_account.clone(),
+            recipient_token_account.clone(),
+            treasury_authority_account.clone(),
+            token_program.clone(),
+        ],
+        &[&[
+            TREASURY_SEED,
+            &[treasury_authority_bump],
+        ]],
+    )?;
+
+    emit(&DepinEvent::TokensPaidInstantly {
+        owner: *recipient,
+        payout: payout_amount,
+        haircut: haircut_amount,
+    });
+
+    msg!("Successfully paid {} BMB instantly (haircut: {} BMB retained in treasury)", payout_amount, haircut_amount);
+    Ok(())
+}
+
 /// Calculate dynamic penalty rate based on periods elapsed
 fn calculate_penalty_rate(lock_period: u16, current_period: u16, unlock_period: u16) -> u16 {
     // Rates are expressed in basis points (bps): 10000 bps = 100%
@@ -174,7 +276,28 @@ fn calculate_penalty_rate(lock_period: u16, current_period: u16, unlock_period:
     rate as u16
 }
 
+/// Lock and unlock periods for tokens locked now for the given number of days
+pub fn lock_schedule(clock: &impl PeriodClock, lock_duration_days: u16) -> Result<(u16, u16), ProgramError> {
+    let current_period = clock.current_period()?;
+    let unlock_period = current_period.checked_add(days_to_periods(lock_duration_days)?).ok_or_else(|| {
+        msg!("Error: Unlock period exceeds u16::MAX");
+        ProgramError::ArithmeticOverflow
+    })?;
+    Ok((current_period, unlock_period))
+}
+
+/// Splits locked tokens into (payout, penalty) if they were unlocked now
+pub fn unlock_quote(locked_tokens: &LockedTokens, clock: &impl PeriodClock) -> Result<(u64, u64), ProgramError> {
+    const DENOMINATOR_BPS: u128 = 10_000; // 100% in basis points
+
+    let current_period = clock.current_period()?;
+    let penalty_rate = calculate_penalty_rate(locked_tokens.lock_period, current_period, locked_tokens.unlock_period);
+    let penalty_amount = (locked_tokens.total_locked as u128 * penalty_rate as u128 / DENOMINATOR_BPS) as u64;
+    Ok((locked_tokens.total_locked - penalty_amount, penalty_amount))
+}
+
 /// Unlocks tokens with dynamic penalty calculation
+#[allow(clippy::too_many_arguments)]
 pub fn unlock<'a>(
     program_id: &Pubkey,
     signer_account: &AccountInfo<'a>,
@@ -184,8 +307,8 @@ pub fn unlock<'a>(
     locked_tokens_account: &AccountInfo<'a>,
     owner_token_account: &AccountInfo<'a>,
     token_program: &AccountInfo<'a>,
+    clock: &impl PeriodClock,
 ) -> Result<(), ProgramError> {
-    const DENOMINATOR_BPS: u64 = 10_000; // 100% in basis points
     // Validate locked tokens account
     if locked_tokens_account.data_is_empty() {
         msg!("Error: LockedTokens account does not exist");
@@ -198,8 +321,8 @@ pub fn unlock<'a>(
     )?;
 
     // Check if tokens were already unlocked
-    if locked_tokens.unlocked_at.is_some() {
-        msg!("Error: Tokens were already unlocked at timestamp {}", locked_tokens.unlocked_at.unwrap());
+    if let Some(unlocked_at) = locked_tokens.unlocked_at {
+        msg!("Error: Tokens were already unlocked at timestamp {}", unlocked_at);
         return Err(ProgramError::InvalidAccountData);
     }
 
@@ -214,12 +337,8 @@ pub fn unlock<'a>(
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Get current period and calculate penalty
-    let current_period = get_current_period();
-
-    let penalty_rate = calculate_penalty_rate(locked_tokens.lock_period, current_period, locked_tokens.unlock_period);
-    let penalty_amount = (locked_tokens.total_locked * penalty_rate as u64) / DENOMINATOR_BPS;
-    let payout_amount = locked_tokens.total_locked - penalty_amount;
+    // Calculate penalty for the current period
+    let (payout_amount, penalty_amount) = unlock_quote(&locked_tokens, clock)?;
     
     // Validate treasury accounts
     let (treasury_state_pda, _) = TreasuryState::find_pda(program_id);
@@ -259,9 +378,9 @@ pub fn unlock<'a>(
     // Transfer tokens from treasury ATA to owner (minus penalty)
     invoke_signed(
         &token_instruction::transfer(
-            &token_program.key,
-            &treasury_ata_account.key,
-            &owner_token_account.key,
+            token_program.key,
+            treasury_ata_account.key,
+            owner_token_account.key,
             &treasury_authority_pda,
             &[],
             payout_amount,
@@ -279,24 +398,83 @@ pub fn unlock<'a>(
     )?;
 
     // Update treasury state to reduce locked balance
-    let mut treasury_state: TreasuryState = read_account_data(
-        &treasury_state_account.try_borrow_data()?,
-        TreasuryState::account_type(),
-    )?;
-    treasury_state.subtract_locked_balance(locked_tokens.total_locked);
-    let mut treasury_state_data = treasury_state_account.try_borrow_mut_data()?;
-    write_account_data(&mut treasury_state_data, TreasuryState::account_type(), &treasury_state)?;
+    load_account_mut::<TreasuryStateView>(&mut treasury_state_account.try_borrow_mut_data()?)?
+        .subtract_locked_balance(locked_tokens.total_locked);
 
     // Mark tokens as unlocked
-    let clock = Clock::get()?;
     let mut updated_locked_tokens = locked_tokens;
-    updated_locked_tokens.unlocked_at = Some(clock.unix_timestamp);
+    updated_locked_tokens.unlocked_at = Some(clock.unix_timestamp()?);
     let mut locked_tokens_data = locked_tokens_account.try_borrow_mut_data()?;
     write_account_data(&mut locked_tokens_data, LockedTokens::account_type(), &updated_locked_tokens)?;
 
+    emit(&DepinEvent::TokensUnlocked {
+        owner: updated_locked_tokens.owner,
+        lock_period: updated_locked_tokens.lock_period,
+        payout: payout_amount,
+        penalty: penalty_amount,
+    });
+
     // Note: Penalty amount stays in treasury, locked tokens account can be closed for rent recovery
     msg!("Successfully unlocked {} BMB tokens (penalty: {} BMB retained in treasury)", 
         payout_amount, penalty_amount);
     
     Ok(())
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bmb::FixedClock;
+
+    fn locked(total_locked: u64, lock_period: u16, unlock_period: u16) -> LockedTokens {
+        LockedTokens::new(Pubkey::new_unique(), total_locked, lock_period, unlock_period)
+    }
+
+    #[test]
+    fn penalty_decays_linearly_over_the_lock() {
+        assert_eq!(calculate_penalty_rate(100, 100, 465), 9000);
+        assert_eq!(calculate_penalty_rate(100, 173, 465), 7200);
+        assert_eq!(calculate_penalty_rate(100, 464, 465), 24);
+        assert_eq!(calculate_penalty_rate(100, 465, 465), 0);
+        assert_eq!(calculate_penalty_rate(100, 500, 465), 0);
+    }
+
+    #[test]
+    fn penalty_is_capped_before_the_lock_and_zero_for_empty_locks() {
+        assert_eq!(calculate_penalty_rate(100, 50, 465), 9000);
+        assert_eq!(calculate_penalty_rate(100, 100, 100), 0);
+        assert_eq!(calculate_penalty_rate(100, 100, 90), 0);
+    }
+
+    #[test]
+    fn unlock_quote_splits_payout_and_penalty() {
+        let tokens = locked(1_000_000, 100, 465);
+        assert_eq!(unlock_quote(&tokens, &FixedClock::at_period(100)), Ok((100_000, 900_000)));
+        assert_eq!(unlock_quote(&tokens, &FixedClock::at_period(173)), Ok((280_000, 720_000)));
+        assert_eq!(unlock_quote(&tokens, &FixedClock::at_period(465)), Ok((1_000_000, 0)));
+    }
+
+    #[test]
+    fn unlock_quote_does_not_overflow_large_locks() {
+        let tokens = locked(u64::MAX, 100, 465);
+        let (payout, penalty) = unlock_quote(&tokens, &FixedClock::at_period(100)).unwrap();
+        assert_eq!(payout + penalty, u64::MAX);
+        assert_eq!(penalty, (u64::MAX as u128 * 9 / 10) as u64);
+    }
+
+    #[test]
+    fn lock_schedule_starts_at_the_current_period() {
+        let year = days_to_periods(365).unwrap();
+        assert_eq!(lock_schedule(&FixedClock::at_period(42), 365), Ok((42, 42 + year)));
+        assert_eq!(lock_schedule(&FixedClock::at_period(42), 0), Ok((42, 42)));
+    }
+
+    #[test]
+    fn lock_schedule_rejects_unlock_period_overflow() {
+        let year = days_to_periods(365).unwrap();
+        assert_eq!(lock_schedule(&FixedClock::at_period(u16::MAX - year), 365), Ok((u16::MAX - year, u16::MAX)));
+        assert_eq!(lock_schedule(&FixedClock::at_period(u16::MAX - year + 1), 365), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(lock_schedule(&FixedClock(i64::MAX), 1), Err(ProgramError::ArithmeticOverflow));
+    }
+}
@@ -1,6 +1,14 @@
+use std::io::{ErrorKind, Read};
+
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::pubkey::Pubkey;
-use crate::{constants::seeds::{LICENSE_SEED, METADATA_SEED, PROOF_SEED, WORKER_SEED}, types::account::DepinAccountType};
+use bytemuck::{Pod, Zeroable};
+use solana_program::{entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    constants::seeds::{LICENSE_SEED, METADATA_SEED, PROOF_SEED, WORKER_SEED},
+    types::account::{DepinAccount, DepinAccountType},
+    utils::account::header_size,
+    utils::zero_copy::{PodU16, PodU32, PodU64, ZeroCopyAccount},
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct WorkerLicenseMetadata {
@@ -19,20 +27,106 @@ impl WorkerLicenseMetadata {
     }
 }
 
+impl DepinAccount for WorkerLicenseMetadata {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::WorkerLicenseMetadata;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Structured discovery record published next to `discovery_uri`, so matchmakers
+/// can read region and capacity without fetching the URI
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(borsh::BorshSchema))]
+pub struct WorkerDiscovery {
+    pub region: String,
+    pub runtimes: Vec<String>,
+    pub capacity_slots: u16,
+    pub protocol_version: u16,
+    pub endpoint: String,
+}
+
+impl WorkerDiscovery {
+    pub const MAX_REGION_LEN: usize = 16;
+    pub const MAX_RUNTIMES: usize = 8;
+    pub const MAX_RUNTIME_LEN: usize = 24;
+    pub const MAX_ENDPOINT_LEN: usize = 128;
+
+    /// Serialized size, bounded by the MAX_* limits once validated
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        4 + self.region.len()
+            + 4 + self.runtimes.iter().map(|runtime| 4 + runtime.len()).sum::<usize>()
+            + 2 + 2
+            + 4 + self.endpoint.len()
+    }
+
+    pub fn validate(&self) -> ProgramResult {
+        if !is_region_code(&self.region) {
+            msg!("Error: Region must be 1-{} characters of a-z, 0-9 or '-'", Self::MAX_REGION_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if self.runtimes.is_empty() || self.runtimes.len() > Self::MAX_RUNTIMES {
+            msg!("Error: Worker must list between 1 and {} runtimes", Self::MAX_RUNTIMES);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        for runtime in self.runtimes.iter() {
+            if runtime.is_empty()
+                || runtime.len() > Self::MAX_RUNTIME_LEN
+                || !runtime.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.')
+            {
+                msg!("Error: Runtime names must be 1-{} characters of a-z, A-Z, 0-9, '-', '_' or '.'", Self::MAX_RUNTIME_LEN);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+        }
+
+        if self.protocol_version == 0 {
+            msg!("Error: Protocol version must be greater than zero");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if self.endpoint.is_empty()
+            || self.endpoint.len() > Self::MAX_ENDPOINT_LEN
+            || !self.endpoint.bytes().all(|b| b.is_ascii_graphic())
+        {
+            msg!("Error: Endpoint must be 1-{} printable ASCII characters without spaces", Self::MAX_ENDPOINT_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
+
+/// Region codes are short lowercase identifiers such as `us-east` or `eu-west-2`
+pub fn is_region_code(region: &str) -> bool {
+    !region.is_empty()
+        && region.len() <= WorkerDiscovery::MAX_REGION_LEN
+        && region.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct WorkerMetadata {
     pub suspended_at: Option<u64>,
     pub delegated_to: Pubkey,
     pub license: Pubkey,
     pub owner: Pubkey,
-    pub discovery_uri: String
+    pub discovery_uri: String,
+    pub discovery: Option<WorkerDiscovery>,
+    /// Wallet that payment streams pay instead of the owner, set by the owner
+    pub reward_beneficiary: Option<Pubkey>,
 }
 
 impl WorkerMetadata {
-    const BASE_SIZE: usize = 1 + 9 + 32 + 32 + 32 + 4;
+    const BASE_SIZE: usize = header_size(<Self as DepinAccount>::VERSION) + 9 + 32 + 32 + 32 + 4 + 1 + 33;
     
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
-        Self::BASE_SIZE + self.discovery_uri.len()
+        Self::BASE_SIZE
+            + self.discovery_uri.len()
+            + self.discovery.as_ref().map_or(0, |discovery| discovery.len())
     }
     
     pub fn find_pda(program_id: &Pubkey, worker_license: &Pubkey, worker: &Pubkey) -> (Pubkey, u8) {
@@ -42,6 +136,63 @@ impl WorkerMetadata {
     pub fn account_type() -> DepinAccountType {
         DepinAccountType::WorkerMetadata
     }
+
+    /// Wallet that receives the worker's payments
+    pub fn reward_recipient(&self) -> Pubkey {
+        self.reward_beneficiary.unwrap_or(self.owner)
+    }
+
+    // Version 0 accounts written before `discovery` existed end right after the URI, read them as `None`
+    fn deserialize_v0<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let suspended_at = Option::<u64>::deserialize_reader(reader)?;
+        let delegated_to = Pubkey::deserialize_reader(reader)?;
+        let license = Pubkey::deserialize_reader(reader)?;
+        let owner = Pubkey::deserialize_reader(reader)?;
+        let discovery_uri = String::deserialize_reader(reader)?;
+
+        // Borsh reports a missing byte as InvalidData, so the end of the body is detected directly
+        let mut tag = [0u8; 1];
+        let discovery = match reader.read(&mut tag)? {
+            0 => None,
+            _ => match tag[0] {
+                0 => None,
+                1 => Some(WorkerDiscovery::deserialize_reader(reader)?),
+                _ => return Err(std::io::Error::new(ErrorKind::InvalidData, "Invalid Option tag")),
+            },
+        };
+
+        Ok(Self {
+            suspended_at,
+            delegated_to,
+            license,
+            owner,
+            discovery_uri,
+            discovery,
+            reward_beneficiary: None,
+        })
+    }
+}
+
+impl DepinAccount for WorkerMetadata {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::WorkerMetadata;
+    /// Version 1 added `reward_beneficiary`
+    const VERSION: u8 = 1;
+
+    fn space(&self) -> usize {
+        self.len()
+    }
+
+    fn upgrade(version: u8, mut body: &[u8]) -> Result<Self, ProgramError> {
+        if version != 0 {
+            msg!("Error: Unknown WorkerMetadata version {}", version);
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::deserialize_v0(&mut body).map_err(|e| {
+            msg!("Error deserializing WorkerMetadata v0: {:?}", e);
+            ProgramError::InvalidAccountData
+        })
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
@@ -63,4 +214,80 @@ impl WorkerProof {
     pub fn account_type() -> DepinAccountType {
         DepinAccountType::WorkerProof
     }
-}
\ No newline at end of file
+}
+
+impl DepinAccount for WorkerProof {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::WorkerProof;
+
+    fn space(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Zero-copy view of WorkerProof, written once per worker and period
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct WorkerProofView {
+    pub period: PodU16,
+    pub proof_root: [u8; 32],
+    pub checkers: [PodU64; 8],
+    pub uptime: PodU32,
+    pub latency: PodU32,
+}
+
+impl ZeroCopyAccount for WorkerProofView {
+    const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::WorkerProof;
+}
+
+impl WorkerProofView {
+    pub fn checkers(&self) -> [u64; 8] {
+        self.checkers.map(PodU64::get)
+    }
+}
+
+impl From<&WorkerProof> for WorkerProofView {
+    fn from(proof: &WorkerProof) -> Self {
+        Self {
+            period: proof.period.into(),
+            proof_root: proof.proof_root,
+            checkers: proof.checkers.map(PodU64::new),
+            uptime: proof.uptime.into(),
+            latency: proof.latency.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::account::read_account_data;
+
+    /// WorkerMetadata as it was written before `discovery`
+    #[derive(BorshSerialize)]
+    struct WorkerMetadataBeforeDiscovery {
+        suspended_at: Option<u64>,
+        delegated_to: Pubkey,
+        license: Pubkey,
+        owner: Pubkey,
+        discovery_uri: String,
+    }
+
+    #[test]
+    fn metadata_written_before_discovery_reads_as_none() {
+        let before = WorkerMetadataBeforeDiscovery {
+            suspended_at: Some(7),
+            delegated_to: Pubkey::new_from_array([3; 32]),
+            license: Pubkey::new_from_array([1; 32]),
+            owner: Pubkey::new_from_array([2; 32]),
+            discovery_uri: "https://worker.example".to_string(),
+        };
+        let mut data = vec![DepinAccountType::WorkerMetadata as u8];
+        data.extend(borsh::to_vec(&before).unwrap());
+
+        let metadata: WorkerMetadata = read_account_data(&data, WorkerMetadata::account_type()).unwrap();
+        assert_eq!(metadata.suspended_at, Some(7));
+        assert_eq!((metadata.delegated_to, metadata.license, metadata.owner), (before.delegated_to, before.license, before.owner));
+        assert_eq!(metadata.discovery_uri, before.discovery_uri);
+        assert_eq!(metadata.discovery, None);
+    }
+}
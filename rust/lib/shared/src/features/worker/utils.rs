@@ -0,0 +1,61 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    features::worker::accounts::{WorkerLicenseMetadata, WorkerMetadata},
+    utils::account::read_account_data,
+};
+
+/// Checks that `owner` has an activated, non-suspended worker for the license.
+/// The WorkerMetadata PDA is derived from the owner key, so it can only exist
+/// if the owner activated the worker while owning the license.
+pub fn validate_active_worker(
+    program_id: &Pubkey,
+    worker_metadata_account: &AccountInfo,
+    worker_license_metadata_account: &AccountInfo,
+    worker_license: &Pubkey,
+    owner: &Pubkey,
+) -> ProgramResult {
+    let (worker_metadata_pda, _) = WorkerMetadata::find_pda(program_id, worker_license, owner);
+
+    // Validate WorkerMetadata PDA
+    if *worker_metadata_account.key != worker_metadata_pda {
+        msg!("Error: WorkerMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if worker_metadata_account.data_is_empty() {
+        msg!("Error: WorkerMetadata account does not exist. Worker must be activated first.");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let worker_metadata: WorkerMetadata = read_account_data(
+        &worker_metadata_account.try_borrow_data()?,
+        WorkerMetadata::account_type(),
+    )?;
+
+    if worker_metadata.suspended_at.is_some() {
+        msg!("Error: Worker is currently suspended");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (worker_license_metadata_pda, _) = WorkerLicenseMetadata::find_pda(program_id, worker_license);
+
+    // Validate WorkerLicenseMetadata PDA
+    if *worker_license_metadata_account.key != worker_license_metadata_pda {
+        msg!("Error: WorkerLicenseMetadata account does not match expected PDA");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if !worker_license_metadata_account.data_is_empty() {
+        let worker_license_metadata: WorkerLicenseMetadata = read_account_data(
+            &worker_license_metadata_account.try_borrow_data()?,
+            WorkerLicenseMetadata::account_type(),
+        )?;
+
+        if worker_license_metadata.suspended_at.is_some() {
+            msg!("Error: WorkerLicense is suspended");
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
+    Ok(())
+}
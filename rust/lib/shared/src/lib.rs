@@ -1,3 +1,5 @@
+pub mod events;
+
 pub mod types {
     pub mod account;
     pub mod ring_buffer;
@@ -8,11 +10,14 @@ pub mod utils {
     pub mod bgum;
     pub mod bmb;
     pub mod brand;
+    pub mod token;
+    pub mod zero_copy;
 }
 
 pub mod constants {
     pub mod seeds;
     pub mod accounts;
+    pub mod network;
     pub mod programs;
 }
 
@@ -25,6 +30,7 @@ pub mod features {
     }
     pub mod worker {
         pub mod accounts;
+        pub mod utils;
     }
     pub mod global {
         pub mod accounts;
@@ -36,4 +42,17 @@ pub mod features {
         pub mod accounts;
         pub mod utils;
     }
+    pub mod infra_offer {
+        pub mod accounts;
+    }
+    pub mod escrow {
+        pub mod accounts;
+        pub mod utils;
+    }
+    pub mod lease {
+        pub mod accounts;
+    }
+    pub mod payment_stream {
+        pub mod accounts;
+    }
 }
\ No newline at end of file
@@ -1,5 +1,8 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, program_error::ProgramError};
+
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DepinAccountType {
     WorkerMetadata = 1,
     WorkerLicenseMetadata = 2,
@@ -11,4 +14,62 @@ pub enum DepinAccountType {
     TreasuryState = 8,
     LockedTokens = 9,
     TreasuryConfig = 10,
+    InfraOffer = 11,
+    Lease = 12,
+    PaymentStream = 13,
+    AllowedMints = 14,
+    EscrowAuthority = 15,
+    EscrowState = 16,
+    PauseState = 17,
+}
+
+impl TryFrom<u8> for DepinAccountType {
+    type Error = u8;
+
+    /// Maps a stored discriminator back to its account type, unknown values are returned as the error
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            1 => Self::WorkerMetadata,
+            2 => Self::WorkerLicenseMetadata,
+            3 => Self::GlobalRewards,
+            4 => Self::WorkerProof,
+            5 => Self::BMBState,
+            6 => Self::CheckerMetadata,
+            7 => Self::CheckerLicenseMetadata,
+            8 => Self::TreasuryState,
+            9 => Self::LockedTokens,
+            10 => Self::TreasuryConfig,
+            11 => Self::InfraOffer,
+            12 => Self::Lease,
+            13 => Self::PaymentStream,
+            14 => Self::AllowedMints,
+            15 => Self::EscrowAuthority,
+            16 => Self::EscrowState,
+            17 => Self::PauseState,
+            _ => return Err(value),
+        })
+    }
+}
+
+/// Borsh account stored behind a `DepinAccountType` header. Bumping `VERSION` changes the header of
+/// newly written accounts, older ones are read through `upgrade` until `MigrateAccount` rewrites them.
+pub trait DepinAccount: BorshSerialize + BorshDeserialize {
+    const ACCOUNT_TYPE: DepinAccountType;
+
+    /// Layout version written by this program, accounts without a version byte are version 0
+    const VERSION: u8 = 0;
+
+    /// Size of the account data for this value, header included
+    fn space(&self) -> usize;
+
+    /// Decodes the body of an account written at an older `version`
+    fn upgrade(version: u8, _body: &[u8]) -> Result<Self, ProgramError> {
+        msg!(
+            "Error: no upgrade from version {} to {} for account type {}",
+            version,
+            Self::VERSION,
+            Self::ACCOUNT_TYPE as u8
+        );
+        Err(ProgramError::InvalidAccountData)
+    }
 }
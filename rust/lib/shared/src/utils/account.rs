@@ -1,17 +1,78 @@
-use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     program_error::ProgramError, 
     msg,
     account_info::AccountInfo,
-    sysvar::rent::Rent,
-    system_instruction,
-    program::invoke,
+    sysvar::{rent::Rent, Sysvar},
+    program::{invoke, invoke_signed},
+    pubkey::Pubkey,
     entrypoint::ProgramResult,
 };
+use solana_system_interface::instruction as system_instruction;
 
-use crate::{constants::accounts::DISC_SIZE, types::account::DepinAccountType};
+use crate::{constants::accounts::DISC_SIZE, types::account::{DepinAccount, DepinAccountType}};
 
-pub fn write_account_data<T: BorshSerialize>(
+/// Set on the type byte of accounts written at `VERSION >= 1`, the version follows in the next byte.
+/// Accounts without it predate versioning and are version 0.
+pub const VERSIONED_FLAG: u8 = 0x80;
+
+/// Type and layout version at the start of every account
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AccountHeader {
+    pub account_type: u8,
+    pub version: u8,
+}
+
+impl AccountHeader {
+    pub fn new(account_type: DepinAccountType, version: u8) -> Self {
+        Self { account_type: account_type as u8, version }
+    }
+
+    pub fn read(data: &[u8]) -> Result<Self, ProgramError> {
+        let Some(&first) = data.first() else {
+            msg!("Error: account data buffer is empty");
+            return Err(ProgramError::AccountDataTooSmall);
+        };
+
+        if first & VERSIONED_FLAG == 0 {
+            return Ok(Self { account_type: first, version: 0 });
+        }
+
+        let Some(&version) = data.get(1) else {
+            msg!("Error: account header is missing its version");
+            return Err(ProgramError::AccountDataTooSmall);
+        };
+        Ok(Self { account_type: first & !VERSIONED_FLAG, version })
+    }
+
+    /// Bytes taken by the header, the body starts right after
+    pub const fn size(&self) -> usize {
+        header_size(self.version)
+    }
+
+    fn write(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        if data.len() < self.size() {
+            msg!("Error: account data is {} bytes, expected at least {}", data.len(), self.size());
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        if self.version == 0 {
+            data[0] = self.account_type;
+        } else {
+            data[0] = self.account_type | VERSIONED_FLAG;
+            data[1] = self.version;
+        }
+        Ok(())
+    }
+}
+
+/// Header size of an account at `version`, for the LEN of versioned account types
+pub const fn header_size(version: u8) -> usize {
+    if version == 0 { DISC_SIZE } else { DISC_SIZE + 1 }
+}
+
+/// Writes the header of `T::VERSION` and the account data. An account still at an older version
+/// is only rewritten when the new layout fits, otherwise it has to go through MigrateAccount first.
+pub fn write_account_data<T: DepinAccount>(
     data: &mut [u8],
     discriminator: DepinAccountType,
     account_data: &T,
@@ -20,30 +81,53 @@ pub fn write_account_data<T: BorshSerialize>(
         msg!("Error: account data buffer is empty");
         return Err(ProgramError::AccountDataTooSmall);
     }
-    data[0] = discriminator as u8;
-    account_data.serialize(&mut &mut data[DISC_SIZE..])?;
+
+    let stored = AccountHeader::read(data)?;
+    if stored.account_type == discriminator as u8 && stored.version < T::VERSION && data.len() < account_data.space() {
+        msg!(
+            "Error: account is at version {} and too small for version {}, run MigrateAccount first",
+            stored.version,
+            T::VERSION
+        );
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    let header = AccountHeader::new(discriminator, T::VERSION);
+    header.write(data)?;
+    account_data.serialize(&mut &mut data[header.size()..])?;
     Ok(())
 }
 
-pub fn read_account_data<'a, T: BorshDeserialize>(
-    data: &'a [u8],
+/// Reads account data of any version up to `T::VERSION`, older layouts go through `T::upgrade`
+pub fn read_account_data<T: DepinAccount>(
+    data: &[u8],
     expected_discriminator: DepinAccountType,
 ) -> Result<T, ProgramError> {
-    if data.is_empty() {
-        msg!("Error: account data buffer is empty");
-        return Err(ProgramError::AccountDataTooSmall);
-    }
+    let header = AccountHeader::read(data)?;
 
-    if data[0] != expected_discriminator as u8 {
+    if header.account_type != expected_discriminator as u8 {
         msg!(
             "Error: account type mismatch. Expected: {}, Found: {}",
             expected_discriminator as u8,
-            data[0]
+            header.account_type
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut data_slice = &data[header.size()..];
+    if header.version < T::VERSION {
+        return T::upgrade(header.version, data_slice);
+    }
+
+    if header.version > T::VERSION {
+        msg!(
+            "Error: account version {} is newer than the supported version {}",
+            header.version,
+            T::VERSION
         );
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let mut data_slice = &data[DISC_SIZE..];
     let account_data = T::deserialize(&mut data_slice)
         .map_err(|e| {
             msg!("Error deserializing account data: {:?}", e);
@@ -77,7 +161,7 @@ pub fn reallocate_account_if_needed<'a>(
         let required_rent = rent.minimum_balance(required_space);
         
         // Reallocate the account
-        target_account.realloc(required_space, false)?;
+        target_account.resize(required_space)?;
         
         // Handle rent difference
         if required_rent > current_rent {
@@ -104,4 +188,154 @@ pub fn reallocate_account_if_needed<'a>(
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Creates a program-owned PDA account funded to be rent exempt for `space` bytes
+///
+/// # Arguments
+/// * `payer` - The account that pays for the new account
+/// * `target_account` - The PDA account to create
+/// * `system_program` - The system program account
+/// * `program_id` - The owner of the new account
+/// * `space` - The size of the new account
+/// * `signer_seeds` - The PDA seeds of `target_account`, including the bump
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    target_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            target_account.key,
+            rent_lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer.clone(),
+            target_account.clone(),
+            system_program.clone(),
+        ],
+        &[signer_seeds],
+    )
+}
+
+/// Closes a program-owned account, moving all of its lamports to `destination`
+pub fn close_account<'a>(
+    target_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports = target_account.lamports();
+    **destination.try_borrow_mut_lamports()? += lamports;
+    **target_account.try_borrow_mut_lamports()? = 0;
+
+    target_account.try_borrow_mut_data()?.fill(0);
+    target_account.resize(0)?;
+    target_account.assign(&solana_system_interface::program::ID);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    use super::*;
+    use crate::features::{checker::accounts::CheckerMetadata, treasury::accounts::TreasuryConfig, worker::accounts::WorkerMetadata};
+    use solana_program::pubkey::Pubkey;
+
+    /// TreasuryConfig as version 0 wrote it, before the instant payout haircut
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct TreasuryConfigV0 {
+        checker_rewards_lock_days: u16,
+    }
+
+    impl DepinAccount for TreasuryConfigV0 {
+        const ACCOUNT_TYPE: DepinAccountType = DepinAccountType::TreasuryConfig;
+
+        fn space(&self) -> usize {
+            1 + 2
+        }
+    }
+
+    fn v0_config() -> Vec<u8> {
+        let mut data = vec![0; 3];
+        write_account_data(&mut data, DepinAccountType::TreasuryConfig, &TreasuryConfigV0 { checker_rewards_lock_days: 90 }).unwrap();
+        data
+    }
+
+    #[test]
+    fn version_zero_keeps_the_single_byte_header() {
+        let data = v0_config();
+        assert_eq!(data, [DepinAccountType::TreasuryConfig as u8, 90, 0]);
+        assert_eq!(AccountHeader::read(&data), Ok(AccountHeader::new(DepinAccountType::TreasuryConfig, 0)));
+
+        let config: TreasuryConfigV0 = read_account_data(&data, DepinAccountType::TreasuryConfig).unwrap();
+        assert_eq!(config.checker_rewards_lock_days, 90);
+    }
+
+    #[test]
+    fn older_versions_are_upgraded_on_read() {
+        let config: TreasuryConfig = read_account_data(&v0_config(), DepinAccountType::TreasuryConfig).unwrap();
+        assert_eq!(config, TreasuryConfig { checker_rewards_lock_days: 90, instant_payout_haircut_bps: TreasuryConfig::INSTANT_PAYOUTS_DISABLED });
+        assert!(!config.instant_payouts_enabled());
+    }
+
+    #[test]
+    fn older_accounts_must_grow_before_a_new_version_is_written() {
+        let mut data = v0_config();
+        let config: TreasuryConfig = read_account_data(&data, DepinAccountType::TreasuryConfig).unwrap();
+        assert_eq!(write_account_data(&mut data, DepinAccountType::TreasuryConfig, &config), Err(ProgramError::AccountDataTooSmall));
+        assert_eq!(data, v0_config());
+
+        data.resize(config.space(), 0);
+        write_account_data(&mut data, DepinAccountType::TreasuryConfig, &config).unwrap();
+        assert_eq!(data, [DepinAccountType::TreasuryConfig as u8 | VERSIONED_FLAG, 1, 90, 0, 0x10, 0x27]);
+        assert_eq!(read_account_data::<TreasuryConfig>(&data, DepinAccountType::TreasuryConfig), Ok(config));
+
+        // A program that only knows version 0 refuses the newer layout
+        let stale = read_account_data::<TreasuryConfigV0>(&data, DepinAccountType::TreasuryConfig);
+        assert_eq!(stale.err(), Some(ProgramError::InvalidAccountData));
+    }
+
+    #[test]
+    fn metadata_without_a_version_pays_its_owner() {
+        let (owner, delegate) = (Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32]));
+
+        let mut checker = vec![DepinAccountType::CheckerMetadata as u8];
+        (None::<u64>, delegate).serialize(&mut checker).unwrap();
+        let metadata: CheckerMetadata = read_account_data(&checker, DepinAccountType::CheckerMetadata).unwrap();
+        assert_eq!((metadata.delegated_to, metadata.reward_beneficiary), (delegate, None));
+        assert_eq!(metadata.reward_recipient(&owner), owner);
+
+        // Written before `discovery` existed, the body ends after the URI
+        let mut worker = vec![DepinAccountType::WorkerMetadata as u8];
+        (None::<u64>, delegate, Pubkey::new_from_array([1; 32]), owner, "https://w".to_string()).serialize(&mut worker).unwrap();
+        let metadata: WorkerMetadata = read_account_data(&worker, DepinAccountType::WorkerMetadata).unwrap();
+        assert_eq!((metadata.owner, metadata.discovery_uri.as_str()), (owner, "https://w"));
+        assert!(metadata.discovery.is_none() && metadata.reward_beneficiary.is_none());
+        assert_eq!(metadata.reward_recipient(), owner);
+
+        // Rewriting it in place needs the room MigrateAccount adds
+        assert_eq!(write_account_data(&mut worker, DepinAccountType::WorkerMetadata, &metadata), Err(ProgramError::AccountDataTooSmall));
+        worker.resize(metadata.len(), 0);
+        write_account_data(&mut worker, DepinAccountType::WorkerMetadata, &metadata).unwrap();
+        assert_eq!(AccountHeader::read(&worker), Ok(AccountHeader::new(DepinAccountType::WorkerMetadata, 1)));
+    }
+
+    #[test]
+    fn headers_are_checked() {
+        assert_eq!(AccountHeader::read(&[]), Err(ProgramError::AccountDataTooSmall));
+        assert_eq!(AccountHeader::read(&[VERSIONED_FLAG | 10]), Err(ProgramError::AccountDataTooSmall));
+
+        let data = v0_config();
+        let wrong_type = read_account_data::<TreasuryConfig>(&data, DepinAccountType::TreasuryState);
+        assert_eq!(wrong_type.err(), Some(ProgramError::InvalidAccountData));
+    }
+}
@@ -1,53 +1,86 @@
-use solana_program::{clock::Clock, entrypoint::ProgramResult, pubkey::Pubkey, sysvar::Sysvar};
-#[cfg(not(feature = "test"))]
-use solana_program::{msg, program_error::ProgramError};
+use solana_program::{clock::Clock, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar};
 
 #[cfg(not(feature = "test"))]
 use crate::constants::accounts::{CHECKER_TREE, WORKER_TREE};
-const PERIOD_ZERO: i64 = 1748736000; // 2025-06-01 00:00:00 UTC
+pub use crate::constants::network::{PERIOD_ZERO, SECONDS_PER_PERIOD};
+use crate::constants::network::{periods_covering_days, SECONDS_PER_DAY};
 
-#[inline(always)]
-pub fn get_current_period() -> u16 {
-    let clock = Clock::get().expect("Clock sysvar not found.");    
-    let now = clock.unix_timestamp;    
-    if now < PERIOD_ZERO {
-        return 0;
+/// Source of the current time for period computations
+pub trait PeriodClock {
+    fn unix_timestamp(&self) -> Result<i64, ProgramError>;
+
+    fn current_period(&self) -> Result<u16, ProgramError> {
+        timestamp_to_period(self.unix_timestamp()?)
+    }
+}
+
+/// Reads the time from the Clock sysvar, used on-chain
+pub struct SysvarClock;
+
+impl PeriodClock for SysvarClock {
+    fn unix_timestamp(&self) -> Result<i64, ProgramError> {
+        Ok(Clock::get()?.unix_timestamp)
+    }
+}
+
+/// Fixed point in time, for host-side computations and tests
+pub struct FixedClock(pub i64);
+
+impl FixedClock {
+    /// Clock set to the first second of the given period
+    pub fn at_period(period: u16) -> Self {
+        Self(PERIOD_ZERO + period as i64 * SECONDS_PER_PERIOD)
     }
-    
-    let seconds_since_start = now - PERIOD_ZERO;
-    let days_since_start = seconds_since_start / 86400;
+}
 
-    if days_since_start > u16::MAX as i64 {
-        panic!("Period exceeds u16::MAX");
+impl PeriodClock for FixedClock {
+    fn unix_timestamp(&self) -> Result<i64, ProgramError> {
+        Ok(self.0)
     }
-    
-    days_since_start as u16
 }
 
 #[inline(always)]
-pub fn timestamp_to_period(timestamp: i64) -> u16 {
+pub fn get_current_period() -> Result<u16, ProgramError> {
+    SysvarClock.current_period()
+}
+
+#[inline(always)]
+pub fn timestamp_to_period(timestamp: i64) -> Result<u16, ProgramError> {
     if timestamp < PERIOD_ZERO {
-        return 0;
+        return Ok(0);
     }
-    
-    let seconds_since_start = timestamp - PERIOD_ZERO;
-    let days_since_start = seconds_since_start / 86400;
 
-    if days_since_start > u16::MAX as i64 {
-        panic!("Period exceeds u16::MAX");
-    }
-    
-    days_since_start as u16
+    let periods_since_start = (timestamp - PERIOD_ZERO) / SECONDS_PER_PERIOD;
+    u16::try_from(periods_since_start).map_err(|_| {
+        msg!("Error: Period {} exceeds u16::MAX", periods_since_start);
+        ProgramError::ArithmeticOverflow
+    })
 }
 
+/// Number of periods covering `days`, so durations set in days keep their length whatever the period length
+pub fn days_to_periods(days: u16) -> Result<u16, ProgramError> {
+    let periods = periods_covering_days(days, SECONDS_PER_PERIOD);
+    u16::try_from(periods).map_err(|_| {
+        msg!("Error: {} days span {} periods, more than u16::MAX", days, periods);
+        ProgramError::ArithmeticOverflow
+    })
+}
+
+/// Calendar month of the period start, counted from the month PERIOD_ZERO falls in
 pub fn get_month_from_period(period: u16) -> u16 {
-    const DAYS_1970_TO_2025_06_01: i64 = 20_240;
+    months_between(PERIOD_ZERO, PERIOD_ZERO + period as i64 * SECONDS_PER_PERIOD) as u16
+}
 
-    // Convert "period" (days since 2025-06-01) into days since 1970-01-01
-    let mut z: i64 = DAYS_1970_TO_2025_06_01 + period as i64;
+fn months_between(from_timestamp: i64, to_timestamp: i64) -> i64 {
+    let (from_year, from_month) = civil_month(from_timestamp);
+    let (to_year, to_month) = civil_month(to_timestamp);
+    (to_year - from_year) * 12 + (to_month - from_month)
+}
 
+/// UTC (year, month) of a unix timestamp
+fn civil_month(timestamp: i64) -> (i64, i64) {
     // --- civil_from_days (proleptic Gregorian), all integer math ---
-    z += 719_468;
+    let z = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
     let era = if z >= 0 { z / 146_097 } else { (z - 146_096) / 146_097 };
     let doe = z - era * 146_097; // [0, 146096]
     let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
@@ -56,10 +89,7 @@ pub fn get_month_from_period(period: u16) -> u16 {
     let mp = (5 * doy + 2) / 153; // [0, 11]
     let m = mp + if mp < 10 { 3 } else { -9 }; // month [1..=12]
     if m <= 2 { y += 1; }
-
-    // Month index where 2025-06 => 0, 2025-07 => 1, ...
-    let month_index = (y - 2025) * 12 + (m as i64 - 6);
-    month_index as u16
+    (y, m)
 }
 
 pub fn validate_checker_tree(
@@ -82,4 +112,90 @@ pub fn validate_worker_tree(
         return Err(ProgramError::InvalidArgument);
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_before_period_zero_map_to_period_zero() {
+        assert_eq!(timestamp_to_period(0), Ok(0));
+        assert_eq!(timestamp_to_period(PERIOD_ZERO - 1), Ok(0));
+        assert_eq!(timestamp_to_period(PERIOD_ZERO), Ok(0));
+    }
+
+    #[test]
+    fn periods_roll_over_at_day_boundaries() {
+        assert_eq!(timestamp_to_period(PERIOD_ZERO + SECONDS_PER_PERIOD - 1), Ok(0));
+        assert_eq!(timestamp_to_period(PERIOD_ZERO + SECONDS_PER_PERIOD), Ok(1));
+        assert_eq!(FixedClock::at_period(365).current_period(), Ok(365));
+        assert_eq!(FixedClock(FixedClock::at_period(40).0 - 1).current_period(), Ok(39));
+    }
+
+    #[test]
+    fn period_overflow_is_an_error() {
+        assert_eq!(FixedClock::at_period(u16::MAX).current_period(), Ok(u16::MAX));
+        let overflow = FixedClock::at_period(u16::MAX).0 + SECONDS_PER_PERIOD;
+        assert_eq!(timestamp_to_period(overflow), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(FixedClock(i64::MAX).current_period(), Err(ProgramError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn days_convert_to_whole_periods() {
+        use crate::constants::network::{daily_amount_per_period, DEFAULT_SECONDS_PER_PERIOD};
+
+        assert_eq!(periods_covering_days(365, DEFAULT_SECONDS_PER_PERIOD), 365);
+        assert_eq!(periods_covering_days(365, 3600), 8760);
+        // Weekly periods round a partial week up
+        assert_eq!(periods_covering_days(365, 7 * SECONDS_PER_DAY), 53);
+        assert_eq!(periods_covering_days(0, 3600), 0);
+        assert_eq!(periods_covering_days(u16::MAX, 60), 94_370_400);
+
+        assert_eq!(daily_amount_per_period(1000, DEFAULT_SECONDS_PER_PERIOD), 1000);
+        assert_eq!(daily_amount_per_period(1000, 3600), 41);
+        assert_eq!(daily_amount_per_period(1000, 7 * SECONDS_PER_DAY), 7000);
+        assert_eq!(daily_amount_per_period(u32::MAX, 2 * SECONDS_PER_DAY), u32::MAX);
+    }
+
+    #[test]
+    fn days_to_periods_follows_the_configured_period_length() {
+        assert_eq!(days_to_periods(365), Ok(periods_covering_days(365, SECONDS_PER_PERIOD) as u16));
+        assert_eq!(days_to_periods(0), Ok(0));
+    }
+
+    #[test]
+    fn civil_months_match_the_calendar() {
+        use crate::constants::network::DEFAULT_PERIOD_ZERO;
+
+        assert_eq!(civil_month(0), (1970, 1));
+        assert_eq!(civil_month(DEFAULT_PERIOD_ZERO), (2025, 6));
+        assert_eq!(civil_month(DEFAULT_PERIOD_ZERO - 1), (2025, 5));
+        // 2024-02-29 12:00:00 UTC
+        assert_eq!(civil_month(1709208000), (2024, 2));
+    }
+
+    #[test]
+    fn months_count_calendar_boundaries_from_the_origin() {
+        use crate::constants::network::DEFAULT_PERIOD_ZERO;
+        let day = |days: i64| DEFAULT_PERIOD_ZERO + days * SECONDS_PER_DAY;
+
+        assert_eq!(months_between(DEFAULT_PERIOD_ZERO, day(0)), 0);
+        assert_eq!(months_between(DEFAULT_PERIOD_ZERO, day(29)), 0);
+        assert_eq!(months_between(DEFAULT_PERIOD_ZERO, day(30)), 1);
+        // 2026-01-01 is 214 days after 2025-06-01
+        assert_eq!(months_between(DEFAULT_PERIOD_ZERO, day(213)), 6);
+        assert_eq!(months_between(DEFAULT_PERIOD_ZERO, day(214)), 7);
+        // An origin late in the month reaches the next one within hours
+        assert_eq!(months_between(day(29) + 23 * 3600, day(30)), 1);
+    }
+
+    #[test]
+    fn month_follows_the_configured_period_length() {
+        assert_eq!(get_month_from_period(0), 0);
+        let next_month = (0..=u16::MAX).find(|&period| get_month_from_period(period) == 1).unwrap();
+        let start = PERIOD_ZERO + next_month as i64 * SECONDS_PER_PERIOD;
+        assert_eq!(civil_month(start - SECONDS_PER_PERIOD), civil_month(PERIOD_ZERO));
+        assert_ne!(civil_month(start), civil_month(PERIOD_ZERO));
+    }
+}
@@ -0,0 +1,227 @@
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use solana_system_interface::instruction as system_instruction;
+use spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction as token_instruction,
+    state::{Account as TokenAccount, Mint},
+};
+
+/// Escrow and vault accounts can live under either token program
+pub fn validate_token_program(token_program: &AccountInfo) -> ProgramResult {
+    if *token_program.key != spl_token::ID && *token_program.key != spl_token_2022::ID {
+        msg!("Error: Token program account is neither the SPL token nor the Token-2022 program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Unpacks the base state of an SPL token or Token-2022 account, mapping any failure to InvalidAccountData
+pub fn read_token_account(token_account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    if *token_account.owner != spl_token::ID && *token_account.owner != spl_token_2022::ID {
+        msg!("Error: {} is not owned by a token program", token_account.key);
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = token_account.try_borrow_data()?;
+    StateWithExtensions::<TokenAccount>::unpack(&data)
+        .map(|state| state.base)
+        .map_err(|_| {
+            msg!("Error: {} is not a valid token account", token_account.key);
+            ProgramError::InvalidAccountData
+        })
+}
+
+/// What the program needs to know about a mint to move its tokens
+pub struct MintDetails {
+    pub decimals: u8,
+    pub transfer_fee: Option<TransferFeeConfig>,
+    pub extensions: Vec<ExtensionType>,
+}
+
+impl MintDetails {
+    /// Fee withheld by the mint when `amount` is sent in the current epoch
+    pub fn transfer_fee(&self, amount: u64) -> Result<u64, ProgramError> {
+        match &self.transfer_fee {
+            Some(config) => config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(ProgramError::ArithmeticOverflow),
+            None => Ok(0),
+        }
+    }
+
+    /// Amount to send so that `net_amount` arrives after the transfer fee
+    pub fn gross_up(&self, net_amount: u64) -> Result<u64, ProgramError> {
+        let fee = match &self.transfer_fee {
+            Some(config) => config
+                .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            None => 0,
+        };
+        net_amount.checked_add(fee).ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Size of a token account for this mint, including the extensions the mint requires
+    pub fn token_account_len(&self) -> Result<usize, ProgramError> {
+        if self.extensions.is_empty() {
+            return Ok(TokenAccount::LEN);
+        }
+        let account_extensions = ExtensionType::get_required_init_account_extensions(&self.extensions);
+        ExtensionType::try_calculate_account_len::<TokenAccount>(&account_extensions)
+    }
+}
+
+/// Reads a mint of either token program. Interest-bearing and scaled amounts only change
+/// how balances are displayed, so everything on chain is kept in raw token amounts.
+pub fn read_mint(mint_account: &AccountInfo) -> Result<MintDetails, ProgramError> {
+    if *mint_account.owner != spl_token::ID && *mint_account.owner != spl_token_2022::ID {
+        msg!("Error: Mint {} is not owned by a token program", mint_account.key);
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = mint_account.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data).map_err(|_| {
+        msg!("Error: {} is not a valid mint", mint_account.key);
+        ProgramError::InvalidAccountData
+    })?;
+
+    let extensions = mint.get_extension_types()?;
+
+    // Transfer hooks need extra accounts on every transfer and non-transferable tokens can never leave
+    if extensions.contains(&ExtensionType::TransferHook) || extensions.contains(&ExtensionType::NonTransferable) {
+        msg!("Error: Mint {} uses an extension that is not supported", mint_account.key);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(MintDetails {
+        decimals: mint.base.decimals,
+        transfer_fee: mint.get_extension::<TransferFeeConfig>().ok().copied(),
+        extensions,
+    })
+}
+
+/// Creates a token account at a PDA that is its own authority, sized for the mint's extensions
+pub fn create_pda_token_account<'a>(
+    payer: &AccountInfo<'a>,
+    target_account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    validate_token_program(token_program)?;
+
+    if mint.owner != token_program.key {
+        msg!("Error: Mint is not owned by the given token program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let rent = Rent::get()?;
+    let space = read_mint(mint)?.token_account_len()?;
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            target_account.key,
+            rent.minimum_balance(space),
+            space as u64,
+            token_program.key,
+        ),
+        &[payer.clone(), target_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+
+    invoke_signed(
+        &token_instruction::initialize_account3(
+            token_program.key,
+            target_account.key,
+            mint.key,
+            target_account.key,
+        )?,
+        &[target_account.clone(), mint.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Transfers tokens out of an account whose authority is a PDA of this program
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    validate_token_program(token_program)?;
+
+    invoke_signed(
+        &token_instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[source.clone(), mint.clone(), destination.clone(), authority.clone(), token_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Transfers tokens out of an account whose authority signed the transaction
+pub fn transfer_from_signer<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    validate_token_program(token_program)?;
+
+    invoke(
+        &token_instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[source.clone(), mint.clone(), destination.clone(), authority.clone(), token_program.clone()],
+    )
+}
+
+/// Checks a token account holds `expected_mint` and belongs to `expected_owner`
+pub fn validate_token_account_owner(token_account: &TokenAccount, expected_owner: &Pubkey, expected_mint: &Pubkey) -> ProgramResult {
+    if token_account.mint != *expected_mint {
+        msg!("Error: Token account mint {} does not match expected mint {}", token_account.mint, expected_mint);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if token_account.owner != *expected_owner {
+        msg!("Error: Token account must be owned by {}", expected_owner);
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::{msg, program_error::ProgramError};
+
+use crate::{constants::accounts::DISC_SIZE, types::account::DepinAccountType};
+
+macro_rules! pod_int {
+    ($name:ident, $int:ty) => {
+        /// Little-endian integer with alignment 1, matching its Borsh encoding at any offset
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Pod, Zeroable)]
+        pub struct $name([u8; core::mem::size_of::<$int>()]);
+
+        impl $name {
+            pub const fn new(value: $int) -> Self {
+                Self(value.to_le_bytes())
+            }
+
+            pub const fn get(self) -> $int {
+                <$int>::from_le_bytes(self.0)
+            }
+
+            pub fn set(&mut self, value: $int) {
+                self.0 = value.to_le_bytes();
+            }
+        }
+
+        impl From<$int> for $name {
+            fn from(value: $int) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for $int {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+    };
+}
+
+pod_int!(PodU16, u16);
+pod_int!(PodU32, u32);
+pod_int!(PodU64, u64);
+
+/// Fixed-size account whose Borsh layout is plain bytes, so the data after the discriminator can be
+/// borrowed in place instead of deserialized. Views describe the version 0 layout, a versioned header
+/// fails the discriminator check, so the Borsh type has to be read instead until the view is updated.
+pub trait ZeroCopyAccount: Pod {
+    const ACCOUNT_TYPE: DepinAccountType;
+    const LEN: usize = DISC_SIZE + core::mem::size_of::<Self>();
+}
+
+/// Borrows the account data as `T` after checking its discriminator
+pub fn load_account<T: ZeroCopyAccount>(data: &[u8]) -> Result<&T, ProgramError> {
+    check_layout::<T>(data)?;
+    Ok(bytemuck::from_bytes(&data[DISC_SIZE..T::LEN]))
+}
+
+/// Mutably borrows the account data as `T` after checking its discriminator
+pub fn load_account_mut<T: ZeroCopyAccount>(data: &mut [u8]) -> Result<&mut T, ProgramError> {
+    check_layout::<T>(data)?;
+    Ok(bytemuck::from_bytes_mut(&mut data[DISC_SIZE..T::LEN]))
+}
+
+/// Writes the discriminator of a freshly created account and borrows its data as `T`
+pub fn init_account<T: ZeroCopyAccount>(data: &mut [u8]) -> Result<&mut T, ProgramError> {
+    if data.len() < T::LEN {
+        msg!("Error: account data is {} bytes, expected {}", data.len(), T::LEN);
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    data[0] = T::ACCOUNT_TYPE as u8;
+    Ok(bytemuck::from_bytes_mut(&mut data[DISC_SIZE..T::LEN]))
+}
+
+fn check_layout<T: ZeroCopyAccount>(data: &[u8]) -> Result<(), ProgramError> {
+    if data.len() < T::LEN {
+        msg!("Error: account data is {} bytes, expected {}", data.len(), T::LEN);
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    if data[0] != T::ACCOUNT_TYPE as u8 {
+        msg!(
+            "Error: account type mismatch. Expected: {}, Found: {}",
+            T::ACCOUNT_TYPE as u8,
+            data[0]
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            global::accounts::{BMBState, BMBStateView},
+            rewards::accounts::{GlobalRewards, GlobalRewardsView},
+            treasury::accounts::{TreasuryState, TreasuryStateView},
+            worker::accounts::{WorkerProof, WorkerProofView},
+        },
+        types::account::DepinAccount,
+        utils::account::{read_account_data, write_account_data},
+    };
+
+    fn borsh_bytes<T: DepinAccount>(len: usize, account_type: DepinAccountType, value: &T) -> Vec<u8> {
+        let mut data = vec![0; len];
+        write_account_data(&mut data, account_type, value).unwrap();
+        data
+    }
+
+    #[test]
+    fn pod_ints_are_little_endian() {
+        assert_eq!(bytemuck::bytes_of(&PodU16::new(0x0102)), &[0x02, 0x01]);
+        assert_eq!(bytemuck::bytes_of(&PodU32::new(0x01020304)), &[4, 3, 2, 1]);
+        assert_eq!(PodU64::new(u64::MAX - 7).get(), u64::MAX - 7);
+        assert_eq!(core::mem::align_of::<PodU64>(), 1);
+    }
+
+    #[test]
+    fn views_match_the_borsh_lengths() {
+        assert_eq!(<BMBStateView as ZeroCopyAccount>::LEN, BMBState::LEN);
+        assert_eq!(<GlobalRewardsView as ZeroCopyAccount>::LEN, GlobalRewards::LEN);
+        assert_eq!(<TreasuryStateView as ZeroCopyAccount>::LEN, TreasuryState::LEN);
+        assert_eq!(<WorkerProofView as ZeroCopyAccount>::LEN, WorkerProof::LEN);
+    }
+
+    #[test]
+    fn bmb_state_view_reads_borsh_data() {
+        let mut state = BMBState::new();
+        for (period, checker_count) in [(3, 10), (5, 20), (9, 30)] {
+            state.add_period_entry(period, checker_count);
+        }
+        let data = borsh_bytes(BMBState::LEN, DepinAccountType::BMBState, &state);
+
+        let view = load_account::<BMBStateView>(&data).unwrap();
+        assert_eq!(view.current_index, state.current_index());
+        for period in 0..12 {
+            assert_eq!(view.get_checker_count_for_period(period), state.get_checker_count_for_period(period));
+        }
+    }
+
+    #[test]
+    fn worker_proof_view_round_trips_through_borsh() {
+        let proof = WorkerProof {
+            period: 513,
+            proof_root: [7; 32],
+            checkers: [1, 2, 3, 4, 5, 6, 7, u64::MAX],
+            uptime: 99,
+            latency: 12,
+        };
+
+        let mut data = vec![0; WorkerProof::LEN];
+        *init_account::<WorkerProofView>(&mut data).unwrap() = WorkerProofView::from(&proof);
+        assert_eq!(data, borsh_bytes(WorkerProof::LEN, DepinAccountType::WorkerProof, &proof));
+
+        let view = load_account::<WorkerProofView>(&data).unwrap();
+        assert_eq!((view.period.get(), view.checkers()), (513, proof.checkers));
+    }
+
+    #[test]
+    fn treasury_state_view_updates_borsh_data() {
+        let mut data = borsh_bytes(TreasuryState::LEN, DepinAccountType::TreasuryState, &TreasuryState { locked_balance: 500 });
+
+        let view = load_account_mut::<TreasuryStateView>(&mut data).unwrap();
+        view.add_locked_balance(250);
+        view.subtract_locked_balance(1_000);
+        view.add_locked_balance(40);
+
+        let state: TreasuryState = read_account_data(&data, DepinAccountType::TreasuryState).unwrap();
+        assert_eq!(state.locked_balance, 40);
+    }
+
+    #[test]
+    fn global_rewards_view_addresses_every_checker() {
+        let mut data = vec![0; GlobalRewards::LEN];
+        data[0] = DepinAccountType::GlobalRewards as u8;
+
+        let view = load_account_mut::<GlobalRewardsView>(&mut data).unwrap();
+        view.add_checker_balance(0, 5).unwrap();
+        view.add_checker_balance(GlobalRewards::ELEMENTS - 1, u32::MAX).unwrap();
+        view.add_checker_balance(GlobalRewards::ELEMENTS - 1, 1).unwrap();
+        assert_eq!(view.add_checker_balance(GlobalRewards::ELEMENTS, 1), Err(ProgramError::InvalidInstructionData));
+
+        assert_eq!(&data[1..5], &5u32.to_le_bytes());
+        assert_eq!(GlobalRewards::read_checker_balance(&data, GlobalRewards::ELEMENTS - 1), Ok(u32::MAX));
+        GlobalRewards::reset_checker_balance(&mut data, 0).unwrap();
+        assert_eq!(GlobalRewards::read_checker_balance(&data, 0), Ok(0));
+    }
+
+    #[test]
+    fn load_checks_the_discriminator_and_length() {
+        let mut data = vec![0; TreasuryState::LEN];
+        data[0] = DepinAccountType::LockedTokens as u8;
+        assert_eq!(load_account::<TreasuryStateView>(&data).err(), Some(ProgramError::InvalidAccountData));
+        assert_eq!(load_account::<TreasuryStateView>(&data[..4]).err(), Some(ProgramError::AccountDataTooSmall));
+        assert_eq!(load_account::<TreasuryStateView>(&[]).err(), Some(ProgramError::AccountDataTooSmall));
+
+        // A GlobalRewards account that is still being grown is not usable yet
+        let partial = vec![0; 8 + 10_240];
+        assert_eq!(GlobalRewards::read_checker_balance(&partial, 0), Err(ProgramError::AccountDataTooSmall));
+    }
+}
@@ -0,0 +1,42 @@
+//! Interface table of the program, built from the `depin_instructions!` declaration in
+//! `instruction.rs`. `depin-idl` renders it as the committed IDL.
+
+use borsh::schema::BorshSchemaContainer;
+
+use crate::instruction::DepinInstruction;
+
+pub struct InstructionDef {
+    pub name: &'static str,
+    pub discriminant: u8,
+    pub docs: &'static [&'static str],
+    pub accounts: &'static [AccountDef],
+    /// Accounts after the fixed list, such as proof accounts, described in words
+    pub remaining_accounts: Option<&'static str>,
+    /// PauseState flag guarding the instruction, its PDA then goes last
+    pub pause_flag: Option<u8>,
+    pub args: InstructionArgs,
+}
+
+pub struct AccountDef {
+    pub name: &'static str,
+    /// `signer`, `writable` and `optional`
+    pub attrs: &'static [&'static str],
+    pub description: &'static str,
+}
+
+impl AccountDef {
+    pub fn is(&self, attr: &str) -> bool {
+        self.attrs.contains(&attr)
+    }
+}
+
+pub enum InstructionArgs {
+    /// Borsh input struct, its fields are the instruction arguments
+    Input(BorshSchemaContainer),
+    /// Arguments of instructions that parse their data by hand
+    Fields(Vec<(&'static str, BorshSchemaContainer)>),
+}
+
+pub fn instructions() -> Vec<InstructionDef> {
+    DepinInstruction::definitions()
+}
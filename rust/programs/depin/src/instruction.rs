@@ -1,32 +1,552 @@
+use shared::features::global::accounts::PauseState;
 use solana_program::program_error::ProgramError;
 
-pub enum DepinInstruction {
-    SubmitWorkerProof = 1,
-    InitNetwork = 2,
-    ActivateWorker = 3,
-    ActivateCheckerLicenses = 4,
-    ActivateChecker = 6,
-    Unlock = 7,
-    PayoutCheckerRewards = 8,
-    UpdateWorkerUri = 9
+macro_rules! optional {
+    () => { None };
+    ($value:expr) => { Some($value) };
 }
 
-impl DepinInstruction {
-    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        let (&variant, _rest) = input
-            .split_first()
-            .ok_or(ProgramError::InvalidInstructionData)?;
-
-        Ok(match variant {
-            1 => Self::SubmitWorkerProof,
-            2 => Self::InitNetwork,
-            3 => Self::ActivateWorker,
-            4 => Self::ActivateCheckerLicenses,
-            6 => Self::ActivateChecker,
-            7 => Self::Unlock,
-            8 => Self::PayoutCheckerRewards,
-            9 => Self::UpdateWorkerUri,
-            _ => return Err(ProgramError::InvalidInstructionData),
-        })
-    }
-}
\ No newline at end of file
+#[cfg(feature = "idl")]
+macro_rules! instruction_args {
+    () => { crate::idl::InstructionArgs::Fields(Vec::new()) };
+    (input $input:ty) => { crate::idl::InstructionArgs::Input(borsh::schema_container_of::<$input>()) };
+    (data { $($field:ident: $field_type:ty),* }) => {
+        crate::idl::InstructionArgs::Fields(vec![$((stringify!($field), borsh::schema_container_of::<$field_type>())),*])
+    };
+}
+
+/// Declares `DepinInstruction` together with its interface: discriminant, instruction data, pause
+/// group and account list. `unpack` and `pause_flag` are derived from it, and with the `idl` feature
+/// so is the table `depin-idl` turns into the committed IDL.
+macro_rules! depin_instructions {
+    ($(
+        $(#[doc = $doc:literal])*
+        $name:ident = $discriminant:literal {
+            $(input: $input:ty,)?
+            $(data: { $($field:ident: $field_type:ty),* $(,)? },)?
+            $(pause: $flag:ident,)?
+            accounts: [$($account:ident [$($attr:ident),*] $description:literal),* $(,)?],
+            $(remaining: $remaining:literal,)?
+        }
+    ),* $(,)?) => {
+        pub enum DepinInstruction {
+            $($(#[doc = $doc])* $name = $discriminant),*
+        }
+
+        impl DepinInstruction {
+            pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+                let (&variant, _rest) = input
+                    .split_first()
+                    .ok_or(ProgramError::InvalidInstructionData)?;
+
+                Ok(match variant {
+                    $($discriminant => Self::$name,)*
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                })
+            }
+
+            /// PauseState flag that stops this instruction. Guarded instructions take the PauseState PDA
+            /// as their last account, after any remaining accounts.
+            pub fn pause_flag(&self) -> Option<u8> {
+                match self {
+                    $(Self::$name => optional!($(PauseState::$flag)?),)*
+                }
+            }
+
+            #[cfg(feature = "idl")]
+            pub fn definitions() -> Vec<crate::idl::InstructionDef> {
+                vec![$(crate::idl::InstructionDef {
+                    name: stringify!($name),
+                    discriminant: $discriminant,
+                    docs: &[$($doc),*],
+                    accounts: &[$(crate::idl::AccountDef {
+                        name: stringify!($account),
+                        attrs: &[$(stringify!($attr)),*],
+                        description: $description,
+                    }),*],
+                    remaining_accounts: optional!($($remaining)?),
+                    pause_flag: optional!($(PauseState::$flag)?),
+                    args: instruction_args!($(input $input)? $(data { $($field: $field_type),* })?),
+                }),*]
+            }
+        }
+    };
+}
+
+depin_instructions! {
+    /// Records a worker's proof for a period and credits the checkers it selected
+    SubmitWorkerProof = 1 {
+        input: worker::input::SubmitWorkerProofInput,
+        pause: PROOFS,
+        accounts: [
+            worker_delegate [signer, writable] "Worker delegate",
+            global_rewards [writable] "GlobalRewards PDA account",
+            worker_proof [writable] "WorkerProof PDA account (will be created)",
+            worker_metadata [] "WorkerMetadata PDA account",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            bmb_state [] "BMBState PDA account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Creates the GlobalRewards, TreasuryState and TreasuryConfig accounts
+    InitNetwork = 2 {
+        accounts: [
+            caller [signer, writable] "Caller",
+            global_rewards [writable] "GlobalRewards PDA account",
+            treasury_state [writable] "TreasuryState PDA account",
+            treasury_config [writable] "TreasuryConfig PDA account",
+            system_program [] "System program account (for account creation)",
+        ],
+    },
+    /// Activates a worker license and sets its delegate and discovery record
+    ActivateWorker = 3 {
+        input: worker::input::ActivateWorkerInput,
+        pause: ACTIVATION,
+        accounts: [
+            owner [signer, writable] "Worker license owner",
+            worker_metadata [writable] "WorkerMetadata PDA account (will be created or updated)",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Sets the number of active checker licenses from a period on
+    ActivateCheckerLicenses = 4 {
+        input: global::input::ActivateCheckersInput,
+        accounts: [
+            admin [signer, writable] "BMB License Admin",
+            bmb_state [writable] "BMBState PDA account (will be created if doesn't exist)",
+            system_program [] "System program account (for account creation)",
+        ],
+    },
+    /// Activates a checker license and sets its delegate
+    ActivateChecker = 6 {
+        input: checker::input::ActivateCheckerInput,
+        pause: ACTIVATION,
+        accounts: [
+            owner [signer, writable] "Checker license owner",
+            checker_metadata [writable] "CheckerMetadata PDA account (will be created or updated)",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Releases locked tokens to their owner once the unlock period is reached
+    Unlock = 7 {
+        data: { lock_period: u16 },
+        pause: UNLOCKS,
+        accounts: [
+            owner [signer, writable] "Token owner (must be the owner of the locked tokens)",
+            treasury_state [writable] "TreasuryState PDA account",
+            treasury_ata [writable] "Treasury ATA account (treasury authority's associated token account)",
+            treasury_authority [] "Treasury authority PDA account",
+            locked_tokens [writable] "LockedTokens PDA account (will be read and tokens released)",
+            owner_token_account [writable] "Owner's BMB token account (where unlocked tokens will be sent)",
+            token_program [] "Token program",
+        ],
+    },
+    /// Moves a checker's accrued rewards into LockedTokens of its reward recipient, or pays them out at once less a haircut
+    PayoutCheckerRewards = 8 {
+        input: checker::input::PayoutCheckerRewardsInput,
+        pause: PAYOUTS,
+        accounts: [
+            signer [signer, writable] "Signer (license owner or delegate)",
+            global_rewards [writable] "GlobalRewards PDA account",
+            checker_metadata [writable] "CheckerMetadata PDA account",
+            checker_license_metadata [] "CheckerLicenseMetadata PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+            treasury_state [writable] "TreasuryState PDA account",
+            treasury_ata [writable] "Treasury ATA account (treasury authority's associated token account)",
+            treasury_config [] "TreasuryConfig PDA account",
+            payout_destination [writable] "LockedTokens PDA account of the reward recipient (Locked mode, will be created) \
+                or its BMB token account (Instant mode)",
+            treasury_authority [] "Treasury authority PDA account (signs Instant payouts)",
+            token_program [] "Token program (for Instant payouts)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Updates the discovery URI and record of an active worker
+    UpdateWorkerUri = 9 {
+        input: worker::input::UpdateWorkerUriInput,
+        accounts: [
+            worker_delegate [signer, writable] "Worker metadata delegate (delegated_to from WorkerMetadata)",
+            worker_metadata [writable] "WorkerMetadata PDA account (must exist)",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for reallocation if needed)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Submits proofs of several workers sharing a delegate and a tree for one period
+    SubmitWorkerProofBatch = 10 {
+        input: worker::input::SubmitWorkerProofBatchInput,
+        pause: PROOFS,
+        accounts: [
+            worker_delegate [signer, writable] "Worker delegate (must be the delegate of every license in the batch)",
+            global_rewards [writable] "GlobalRewards PDA account",
+            bmb_state [] "BMBState PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "for each entry in input order: WorkerProof PDA account (writable, will be created), \
+            WorkerMetadata PDA account, WorkerLicenseMetadata PDA account and `proof_accounts_len` proof accounts",
+    },
+    /// Pays out several checkers of one owner into a single LockedTokens account, there is no instant batch payout
+    PayoutCheckerRewardsBatch = 11 {
+        input: checker::input::PayoutCheckerRewardsBatchInput,
+        pause: PAYOUTS,
+        accounts: [
+            signer [signer, writable] "Signer (owner of all licenses, or delegate of every license in the batch)",
+            global_rewards [writable] "GlobalRewards PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+            treasury_state [writable] "TreasuryState PDA account",
+            treasury_ata [writable] "Treasury ATA account (treasury authority's associated token account)",
+            treasury_config [] "TreasuryConfig PDA account",
+            locked_tokens [writable] "LockedTokens PDA account of the common owner (will be created or topped up)",
+        ],
+        remaining: "for each entry in input order: CheckerMetadata PDA account, CheckerLicenseMetadata PDA account \
+            and `proof_accounts_len` proof accounts",
+    },
+    /// Moves a transferred worker license's metadata to its new owner
+    MigrateWorkerMetadata = 12 {
+        input: worker::input::MigrateWorkerMetadataInput,
+        accounts: [
+            payer [signer, writable] "Payer (permissionless, usually the new license owner)",
+            stale_worker_metadata [writable] "Stale WorkerMetadata PDA account of the previous owner (will be closed)",
+            worker_metadata [writable] "WorkerMetadata PDA account of the current owner (will be created or updated)",
+            worker_license_metadata [writable] "WorkerLicenseMetadata PDA account (will be created if a suspension must be kept)",
+            previous_owner [writable] "Previous owner account (receives the rent of the stale account)",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    /// Moves a transferred checker license's metadata to its new owner
+    MigrateCheckerMetadata = 13 {
+        input: checker::input::MigrateCheckerMetadataInput,
+        accounts: [
+            payer [signer, writable] "Payer (permissionless, usually the new license owner)",
+            stale_checker_metadata [writable] "Stale CheckerMetadata PDA account of the previous owner (will be closed)",
+            checker_metadata [writable] "CheckerMetadata PDA account of the current owner (will be created or updated)",
+            checker_license_metadata [writable] "CheckerLicenseMetadata PDA account (will be created if a suspension must be kept)",
+            previous_owner [writable] "Previous owner account (receives the rent of the stale account)",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    SetWorkerDelegate = 14 {
+        input: worker::input::SetWorkerDelegateInput,
+        accounts: [
+            owner [signer, writable] "Worker license owner",
+            worker_metadata [writable] "WorkerMetadata PDA account (must exist)",
+        ],
+    },
+    RevokeWorkerDelegate = 15 {
+        input: worker::input::RevokeWorkerDelegateInput,
+        accounts: [
+            owner [signer, writable] "Worker license owner",
+            worker_metadata [writable] "WorkerMetadata PDA account (must exist)",
+        ],
+    },
+    SetCheckerDelegate = 16 {
+        input: checker::input::SetCheckerDelegateInput,
+        accounts: [
+            owner [signer, writable] "Checker license owner",
+            checker_metadata [writable] "CheckerMetadata PDA account (must exist)",
+        ],
+    },
+    RevokeCheckerDelegate = 17 {
+        input: checker::input::RevokeCheckerDelegateInput,
+        accounts: [
+            owner [signer, writable] "Checker license owner",
+            checker_metadata [writable] "CheckerMetadata PDA account (must exist)",
+        ],
+    },
+    /// Publishes an infrastructure offer of an active worker
+    RegisterInfraOffer = 18 {
+        input: infra_offer::input::RegisterInfraOfferInput,
+        accounts: [
+            provider [signer, writable] "Provider (worker license owner, pays for the offer account)",
+            infra_offer [writable] "InfraOffer PDA account (will be created, or taken over from a previous license owner)",
+            worker_metadata [] "WorkerMetadata PDA account of the provider",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account",
+            system_program [] "System program account (for account creation)",
+            allowed_mints [] "AllowedMints PDA account",
+        ],
+        remaining: "proof accounts of the license",
+    },
+    UpdateInfraOffer = 19 {
+        input: infra_offer::input::UpdateInfraOfferInput,
+        accounts: [
+            provider [signer, writable] "Provider",
+            infra_offer [writable] "InfraOffer PDA account",
+            allowed_mints [] "AllowedMints PDA account",
+        ],
+    },
+    SetInfraOfferStatus = 20 {
+        input: infra_offer::input::SetInfraOfferStatusInput,
+        accounts: [
+            provider [signer, writable] "Provider",
+            infra_offer [writable] "InfraOffer PDA account",
+            worker_metadata [] "WorkerMetadata PDA account of the provider (checked when resuming)",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account (checked when resuming)",
+        ],
+    },
+    CloseInfraOffer = 21 {
+        accounts: [
+            provider [signer, writable] "Provider (receives the rent of the offer account)",
+            infra_offer [writable] "InfraOffer PDA account (will be closed)",
+        ],
+    },
+    /// Deposits tokens of an allowed mint into the escrow of `beneficiary`, which may be omitted
+    /// from the data to default to the depositor
+    EscrowDeposit = 22 {
+        input: escrow_deposit::input::EscrowDepositInput,
+        pause: ESCROW,
+        accounts: [
+            depositor [signer, writable] "Depositor (pays for escrow creation, need not be the beneficiary)",
+            depositor_token_account [writable] "Depositor's token account (its mint must be listed in AllowedMints)",
+            escrow_token_account [writable] "Escrow token account of the beneficiary (PDA, address calculated by client)",
+            mint [] "Mint account",
+            token_program [] "Token program account (SPL token or Token-2022, must own the mint)",
+            system_program [] "System program account (for account creation if needed)",
+            allowed_mints [] "AllowedMints PDA account",
+        ],
+    },
+    /// Withdraws tokens from the escrow of `escrow_owner`, which is None when the withdrawer owns
+    /// the escrow
+    EscrowWithdraw = 23 {
+        input: escrow_withdraw::input::EscrowWithdrawInput,
+        pause: ESCROW,
+        accounts: [
+            withdrawer [signer, writable] "Withdrawer (owner of the escrow, or one of its delegates)",
+            withdrawer_token_account [writable] "Withdrawer's token account",
+            escrow_token_account [writable] "Escrow token account (PDA, address calculated by client)",
+            program [] "Program account (needed since program is authority)",
+            token_program [] "Token program account (SPL token or Token-2022, must own the mint)",
+            mint [] "Mint account",
+            allowed_mints [] "AllowedMints PDA account",
+            escrow_state [writable] "EscrowState PDA account of the escrow (may be empty)",
+            escrow_authority [writable, optional] "EscrowAuthority PDA account of the escrow owner (only when a delegate withdraws)",
+        ],
+    },
+    /// Opens a lease on an offer, moving the full price from the buyer's escrow into a vault
+    OpenLease = 24 {
+        input: lease::input::OpenLeaseInput,
+        pause: ESCROW,
+        accounts: [
+            buyer [signer, writable] "Buyer (pays for the lease accounts)",
+            infra_offer [] "InfraOffer PDA account",
+            lease [writable] "Lease PDA account (will be created)",
+            buyer_escrow_token_account [writable] "Buyer's escrow token account",
+            lease_vault [writable] "Lease vault token account (will be created)",
+            mint [] "Mint account (must be the offer's price mint)",
+            token_program [] "Token program account",
+            system_program [] "System program account (for account creation)",
+            buyer_escrow_state [] "Buyer's EscrowState PDA account for the mint (may be empty)",
+            worker_metadata [] "WorkerMetadata PDA account of the offer provider",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account of the worker license",
+        ],
+        remaining: "proof accounts of the worker license",
+    },
+    /// Pays the current license owner for a period the leased worker proved
+    ClaimLeasePayment = 25 {
+        input: lease::input::ClaimLeasePaymentInput,
+        pause: ESCROW,
+        accounts: [
+            license_owner [signer, writable] "Current owner of the leased worker license",
+            lease [writable] "Lease PDA account",
+            lease_vault [writable] "Lease vault token account",
+            recipient_token_account [writable] "Token account of the worker's reward recipient (owner or beneficiary) for the lease mint",
+            worker_metadata [] "WorkerMetadata PDA account of the license owner",
+            worker_proof [] "WorkerProof PDA account of the leased worker for the claimed period",
+            token_program [] "Token program account",
+            mint [] "Mint account of the lease",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account of the worker license",
+        ],
+        remaining: "proof accounts of the worker license",
+    },
+    /// Ends a lease and refunds the periods that have not started
+    CancelLease = 26 {
+        pause: ESCROW,
+        accounts: [
+            buyer [signer, writable] "Buyer",
+            lease [writable] "Lease PDA account",
+            lease_vault [writable] "Lease vault token account",
+            buyer_escrow_token_account [writable] "Buyer's escrow token account (receives the refund)",
+            token_program [] "Token program account",
+            mint [] "Mint account of the lease",
+        ],
+    },
+    /// Refunds the SLA penalty of a period whose proof breached the offer's terms
+    EnforceLeaseSla = 27 {
+        input: lease::input::EnforceLeaseSlaInput,
+        pause: ESCROW,
+        accounts: [
+            cranker [signer, writable] "Cranker (permissionless)",
+            lease [writable] "Lease PDA account",
+            lease_vault [writable] "Lease vault token account",
+            buyer_escrow_token_account [writable] "Buyer's escrow token account (receives the refund)",
+            worker_proof [] "WorkerProof PDA account of the leased worker for the enforced period (may be empty)",
+            token_program [] "Token program account",
+            mint [] "Mint account of the lease",
+        ],
+    },
+    /// Opens a per-period payment from the payer's escrow to a worker
+    OpenPaymentStream = 28 {
+        input: payment_stream::input::OpenPaymentStreamInput,
+        pause: ESCROW,
+        accounts: [
+            payer [signer, writable] "Payer (escrow owner, pays for the stream account)",
+            payment_stream [writable] "PaymentStream PDA account (will be created)",
+            payer_escrow_token_account [] "Payer's escrow token account (its mint is the stream mint)",
+            worker_metadata [] "WorkerMetadata PDA account of the worker owner",
+            system_program [] "System program account (for account creation)",
+            payer_escrow_state [writable] "Payer's EscrowState PDA account for the stream mint (will be created if doesn't exist)",
+        ],
+    },
+    /// Pays the stream's next period to the worker's current owner
+    CrankPaymentStream = 29 {
+        input: payment_stream::input::CrankPaymentStreamInput,
+        pause: ESCROW,
+        accounts: [
+            cranker [signer, writable] "Cranker (permissionless)",
+            payment_stream [writable] "PaymentStream PDA account",
+            payer_escrow_token_account [writable] "Payer's escrow token account",
+            worker_metadata [] "WorkerMetadata PDA account of the current worker owner",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account",
+            recipient_token_account [writable] "Token account of the worker's reward recipient (owner or beneficiary) for the stream mint",
+            worker_proof [] "WorkerProof PDA account for the stream's next period (may be empty)",
+            token_program [] "Token program account",
+            mint [] "Mint account of the stream",
+            payer_escrow_state [writable] "Payer's EscrowState PDA account for the stream mint",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account of the worker license",
+        ],
+        remaining: "proof accounts of the worker license",
+    },
+    /// Settles the finished periods of the stream, then closes it; the periods not started yet need a matured withdrawal notice
+    ClosePaymentStream = 30 {
+        input: payment_stream::input::ClosePaymentStreamInput,
+        pause: ESCROW,
+        accounts: [
+            payer [signer, writable] "Payer (receives the rent of the stream account)",
+            payment_stream [writable] "PaymentStream PDA account (will be closed)",
+            payer_escrow_state [writable] "Payer's EscrowState PDA account for the stream mint",
+            payer_escrow_token_account [writable] "Payer's escrow token account",
+            worker_metadata [] "WorkerMetadata PDA account of the current worker owner",
+            worker_license_metadata [] "WorkerLicenseMetadata PDA account",
+            recipient_token_account [writable] "Token account of the worker's reward recipient (owner or beneficiary) for the stream mint",
+            token_program [] "Token program account",
+            mint [] "Mint account of the stream",
+            compression_program [] "mpl_account_compression program",
+            merkle_tree [] "Merkle tree account of the worker license",
+        ],
+        remaining: "WorkerProof PDA accounts of the finished periods not settled yet, then proof accounts of the worker license",
+    },
+    /// Adds a mint to AllowedMints or updates its deposit limit and pause switch
+    SetAllowedMint = 31 {
+        input: global::input::SetAllowedMintInput,
+        accounts: [
+            admin [signer, writable] "BMB License Admin",
+            allowed_mints [writable] "AllowedMints PDA account (will be created if doesn't exist)",
+            system_program [] "System program account (for account creation)",
+        ],
+    },
+    RemoveAllowedMint = 32 {
+        input: global::input::RemoveAllowedMintInput,
+        accounts: [
+            admin [signer, writable] "BMB License Admin",
+            allowed_mints [writable] "AllowedMints PDA account",
+        ],
+    },
+    /// Lets `delegate` spend up to `spend_limit` of the beneficiary's escrow in `mint`
+    AddEscrowDelegate = 33 {
+        input: escrow_authority::input::AddEscrowDelegateInput,
+        accounts: [
+            beneficiary [signer, writable] "Escrow beneficiary (pays for account creation)",
+            escrow_authority [writable] "EscrowAuthority PDA account of the beneficiary (will be created if doesn't exist)",
+            system_program [] "System program account (for account creation)",
+        ],
+    },
+    RemoveEscrowDelegate = 34 {
+        input: escrow_authority::input::RemoveEscrowDelegateInput,
+        accounts: [
+            beneficiary [signer, writable] "Escrow beneficiary",
+            escrow_authority [writable] "EscrowAuthority PDA account of the beneficiary",
+        ],
+    },
+    /// Announces a withdrawal of `amount` from the owner's escrow, zero cancels a pending notice
+    EscrowWithdrawalNotice = 35 {
+        data: { amount: u64 },
+        pause: ESCROW,
+        accounts: [
+            owner [signer, writable] "Escrow owner (pays for account creation)",
+            escrow_state [writable] "EscrowState PDA account of the escrow (will be created if doesn't exist)",
+            mint [] "Mint account of the escrow",
+            system_program [] "System program account (for account creation if needed)",
+        ],
+    },
+    /// Rewrites a program account in the current layout of its type
+    MigrateAccount = 36 {
+        accounts: [
+            payer [signer, writable] "Payer (permissionless, pays for any growth of the account)",
+            account [writable] "Program account to migrate",
+            system_program [] "System program account (for the rent transfer)",
+        ],
+    },
+    /// Sets the paused instruction groups, cleared groups only resume after `PauseState::UNPAUSE_DELAY`
+    SetPauseFlags = 37 {
+        input: global::input::SetPauseFlagsInput,
+        accounts: [
+            admin [signer, writable] "BMB License Admin",
+            pause_state [writable] "PauseState PDA account (will be created if doesn't exist)",
+            system_program [] "System program account (for account creation)",
+        ],
+    },
+    /// Sends the checker's future payouts to another wallet, which then owns and unlocks the LockedTokens
+    SetCheckerRewardBeneficiary = 38 {
+        input: checker::input::SetCheckerRewardBeneficiaryInput,
+        accounts: [
+            owner [signer, writable] "Checker license owner (pays for growing older metadata)",
+            checker_metadata [writable] "CheckerMetadata PDA account (must exist)",
+            system_program [] "System program account (for the rent transfer)",
+        ],
+    },
+    /// Sends the payment stream and lease payments of the worker to another wallet
+    SetWorkerRewardBeneficiary = 39 {
+        input: worker::input::SetWorkerRewardBeneficiaryInput,
+        accounts: [
+            owner [signer, writable] "Worker license owner (pays for growing older metadata)",
+            worker_metadata [writable] "WorkerMetadata PDA account (must exist)",
+            system_program [] "System program account (for the rent transfer)",
+        ],
+    },
+    /// Sets the share of instant checker payouts that stays in the treasury
+    SetInstantPayoutHaircut = 40 {
+        input: global::input::SetInstantPayoutHaircutInput,
+        accounts: [
+            admin [signer, writable] "BMB License Admin (pays for growing an older TreasuryConfig)",
+            treasury_config [writable] "TreasuryConfig PDA account (must exist)",
+            system_program [] "System program account (for reallocation if needed)",
+        ],
+    },
+}
@@ -2,12 +2,14 @@ use solana_program::{account_info::AccountInfo, declare_id, entrypoint, entrypoi
 
 mod processor;
 mod instruction;
+#[cfg(feature = "idl")]
+pub mod idl;
 
 declare_id!("bmb3KBHjzHzH7ZJ1aNG1v2XpciuX9g89TyRYK7sJXec");
 
 entrypoint!(process_instruction);
 
-fn process_instruction<'a>(
+pub fn process_instruction<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
     instruction_data: &[u8],
@@ -1,11 +1,31 @@
 use global::process_activate_checker_licenses;
+use global::{process_set_allowed_mint, process_remove_allowed_mint};
+use global::pause::{check_not_paused, process_set_pause_flags};
+use global::process_set_instant_payout_haircut;
 use worker::process_submit_worker_proof;
+use worker::process_submit_worker_proof_batch;
 use worker::process_activate_worker;
 use worker::process_update_worker_uri;
+use worker::process_migrate_worker_metadata;
+use worker::{process_set_worker_delegate, process_revoke_worker_delegate};
+use worker::process_set_worker_reward_beneficiary;
 use init::process_init_network;
 use treasury::unlock::process_unlock;
 use checker::activate::process_activate_checker;
-use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+use checker::migrate_metadata::process_migrate_checker_metadata;
+use checker::delegate::{process_set_checker_delegate, process_revoke_checker_delegate};
+use checker::beneficiary::process_set_checker_reward_beneficiary;
+use infra_offer::{
+    process_register_infra_offer, process_update_infra_offer,
+    process_set_infra_offer_status, process_close_infra_offer,
+};
+use escrow_deposit::process_deposit_request;
+use escrow_withdraw::{process_withdrawal_request, process_withdrawal_notice};
+use escrow_authority::{process_add_escrow_delegate, process_remove_escrow_delegate};
+use lease::{process_open_lease, process_claim_lease_payment, process_cancel_lease, process_enforce_lease_sla};
+use migrate::process_migrate_account;
+use payment_stream::{process_open_payment_stream, process_crank_payment_stream, process_close_payment_stream};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::instruction::DepinInstruction;
 
@@ -16,6 +36,20 @@ pub fn process<'a>(
 ) -> ProgramResult {
     let instruction: DepinInstruction = DepinInstruction::unpack(instruction_data)?;
     let data = &instruction_data[1..];
+
+    // Guarded instructions carry the PauseState PDA last, it is checked and stripped before dispatch
+    let accounts = match instruction.pause_flag() {
+        Some(flag) => {
+            let Some((pause_state_account, accounts)) = accounts.split_last() else {
+                msg!("Error: Missing PauseState account");
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            check_not_paused(program_id, pause_state_account, flag)?;
+            accounts
+        }
+        None => accounts,
+    };
+
     match instruction {
         DepinInstruction::SubmitWorkerProof => {
             process_submit_worker_proof(program_id, accounts, data)?;
@@ -40,6 +74,99 @@ pub fn process<'a>(
         },
         DepinInstruction::UpdateWorkerUri => {
             process_update_worker_uri(program_id, accounts, data)?;
+        },
+        DepinInstruction::SubmitWorkerProofBatch => {
+            process_submit_worker_proof_batch(program_id, accounts, data)?;
+        },
+        DepinInstruction::PayoutCheckerRewardsBatch => {
+            checker::payout_batch::process_payout_checker_rewards_batch(program_id, accounts, data)?;
+        },
+        DepinInstruction::MigrateWorkerMetadata => {
+            process_migrate_worker_metadata(program_id, accounts, data)?;
+        },
+        DepinInstruction::MigrateCheckerMetadata => {
+            process_migrate_checker_metadata(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetWorkerDelegate => {
+            process_set_worker_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::RevokeWorkerDelegate => {
+            process_revoke_worker_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetCheckerDelegate => {
+            process_set_checker_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::RevokeCheckerDelegate => {
+            process_revoke_checker_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::RegisterInfraOffer => {
+            process_register_infra_offer(program_id, accounts, data)?;
+        },
+        DepinInstruction::UpdateInfraOffer => {
+            process_update_infra_offer(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetInfraOfferStatus => {
+            process_set_infra_offer_status(program_id, accounts, data)?;
+        },
+        DepinInstruction::CloseInfraOffer => {
+            process_close_infra_offer(program_id, accounts, data)?;
+        },
+        DepinInstruction::EscrowDeposit => {
+            process_deposit_request(program_id, accounts, data)?;
+        },
+        DepinInstruction::EscrowWithdraw => {
+            process_withdrawal_request(program_id, accounts, data)?;
+        },
+        DepinInstruction::OpenLease => {
+            process_open_lease(program_id, accounts, data)?;
+        },
+        DepinInstruction::ClaimLeasePayment => {
+            process_claim_lease_payment(program_id, accounts, data)?;
+        },
+        DepinInstruction::CancelLease => {
+            process_cancel_lease(program_id, accounts, data)?;
+        },
+        DepinInstruction::EnforceLeaseSla => {
+            process_enforce_lease_sla(program_id, accounts, data)?;
+        },
+        DepinInstruction::OpenPaymentStream => {
+            process_open_payment_stream(program_id, accounts, data)?;
+        },
+        DepinInstruction::CrankPaymentStream => {
+            process_crank_payment_stream(program_id, accounts, data)?;
+        },
+        DepinInstruction::ClosePaymentStream => {
+            process_close_payment_stream(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetAllowedMint => {
+            process_set_allowed_mint(program_id, accounts, data)?;
+        },
+        DepinInstruction::RemoveAllowedMint => {
+            process_remove_allowed_mint(program_id, accounts, data)?;
+        },
+        DepinInstruction::AddEscrowDelegate => {
+            process_add_escrow_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::RemoveEscrowDelegate => {
+            process_remove_escrow_delegate(program_id, accounts, data)?;
+        },
+        DepinInstruction::EscrowWithdrawalNotice => {
+            process_withdrawal_notice(program_id, accounts, data)?;
+        },
+        DepinInstruction::MigrateAccount => {
+            process_migrate_account(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetPauseFlags => {
+            process_set_pause_flags(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetCheckerRewardBeneficiary => {
+            process_set_checker_reward_beneficiary(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetWorkerRewardBeneficiary => {
+            process_set_worker_reward_beneficiary(program_id, accounts, data)?;
+        },
+        DepinInstruction::SetInstantPayoutHaircut => {
+            process_set_instant_payout_haircut(program_id, accounts, data)?;
         }
     }
     Ok(())
@@ -0,0 +1,365 @@
+mod common;
+
+use common::{
+    assert_instruction_error, bitmap_for_checker,
+    builders::{self, ProofArgs},
+    license_tree::{License, LicenseTree},
+    mock_compression::INVALID_PROOF,
+    TestEnv, CHECKER_COUNT, TREASURY_BALANCE,
+};
+use shared::{
+    constants::accounts::BMB_MINT,
+    features::{
+        checker::accounts::CheckerMetadata,
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{LockedTokens, TreasuryState},
+    },
+    types::account::DepinAccountType,
+};
+use solana_sdk::{instruction::InstructionError, signer::Signer};
+
+const PERIOD: u16 = 20;
+const PAYOUT_PERIOD: u16 = PERIOD + 1;
+const UNLOCK_PERIOD: u16 = PAYOUT_PERIOD + 365;
+
+/// Network with a checker count from PERIOD on, the clock one period later so PERIOD accepts proofs
+async fn network() -> TestEnv {
+    let mut env = TestEnv::start(PERIOD - 1).await;
+    env.init_network().await;
+    env.set_checker_count(PERIOD, CHECKER_COUNT).await;
+    env.warp_to_period(PAYOUT_PERIOD).await;
+    env
+}
+
+/// Submits a PERIOD proof from a new worker that credits every given checker once
+async fn credit_checkers(env: &mut TestEnv, checkers: &[&License]) {
+    let mut worker_tree = LicenseTree::new();
+    let (worker_owner, worker) = env.activate_worker(&mut worker_tree).await;
+
+    let mut bitmap = [0; 8];
+    for checker in checkers {
+        let credited = bitmap_for_checker(&worker, PERIOD, CHECKER_COUNT, checker.index);
+        bitmap.iter_mut().zip(credited).for_each(|(word, bits)| *word |= bits);
+    }
+
+    let args = ProofArgs { period: PERIOD, checkers: bitmap, uptime: 100, latency: 10 };
+    env.process(builders::submit_worker_proof(&worker_owner.pubkey(), &worker, &args), &[&worker_owner]).await.unwrap();
+}
+
+fn reward() -> u64 {
+    GlobalRewards::get_checker_reward(PERIOD) as u64
+}
+
+#[tokio::test]
+async fn activate_checker_creates_metadata() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+
+    let (owner, license) = env.activate_checker(&mut tree).await;
+
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&license), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.delegated_to, owner.pubkey());
+    assert!(metadata.suspended_at.is_none());
+}
+
+#[tokio::test]
+async fn activate_checker_rejects_a_proof_that_does_not_hash_to_the_root() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let owner = env.funded_keypair();
+    let index = tree.mint(owner.pubkey());
+    env.sync_tree(&tree);
+
+    let mut license = tree.license(index);
+    license.proof.reverse();
+
+    let instruction = builders::activate_checker(&owner.pubkey(), &license, &owner.pubkey());
+    assert_instruction_error(env.process(instruction, &[&owner]).await, InstructionError::Custom(INVALID_PROOF));
+}
+
+#[tokio::test]
+async fn checker_delegate_can_be_set_and_revoked_by_the_owner_only() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_checker(&mut tree).await;
+    let delegate = env.funded_keypair();
+
+    let result = env.process(builders::set_checker_delegate(&delegate.pubkey(), &license, &delegate.pubkey()), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.process(builders::set_checker_delegate(&owner.pubkey(), &license, &delegate.pubkey()), &[&owner]).await.unwrap();
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&license), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.delegated_to, delegate.pubkey());
+
+    env.process(builders::revoke_checker_delegate(&owner.pubkey(), &license), &[&owner]).await.unwrap();
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&license), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.delegated_to, owner.pubkey());
+}
+
+#[tokio::test]
+async fn migrate_checker_metadata_resets_the_delegate_and_beneficiary_for_the_new_owner() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let (previous_owner, license) = env.activate_checker(&mut tree).await;
+    let delegate = env.funded_keypair();
+    env.process(builders::set_checker_delegate(&previous_owner.pubkey(), &license, &delegate.pubkey()), &[&previous_owner])
+        .await
+        .unwrap();
+    env.process(builders::set_checker_reward_beneficiary(&previous_owner.pubkey(), &license, Some(delegate.pubkey())), &[&previous_owner])
+        .await
+        .unwrap();
+
+    let new_owner = env.funded_keypair();
+    tree.transfer(license.index, new_owner.pubkey());
+    env.sync_tree(&tree);
+    let transferred = tree.license(license.index);
+
+    env.process(builders::migrate_checker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner])
+        .await
+        .unwrap();
+
+    let (stale, _) = CheckerMetadata::find_pda(&depin::ID, &license.asset_id, &previous_owner.pubkey());
+    assert!(env.account(&stale).await.is_none());
+
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&transferred), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.delegated_to, new_owner.pubkey());
+    assert_eq!(metadata.reward_beneficiary, None);
+
+    // The stale metadata is gone, a second migration has nothing to move
+    let result = env.process(builders::migrate_checker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner]).await;
+    assert_instruction_error(result, InstructionError::UninitializedAccount);
+}
+
+#[tokio::test]
+async fn payout_locks_the_balance_and_resets_it() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+
+    credit_checkers(&mut env, &[&checker]).await;
+    assert_eq!(env.checker_balance(checker.index).await as u64, reward());
+
+    env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner])
+        .await
+        .unwrap();
+
+    let locked: LockedTokens = env
+        .read(&builders::locked_tokens(&owner.pubkey(), PAYOUT_PERIOD, UNLOCK_PERIOD), DepinAccountType::LockedTokens)
+        .await;
+    assert_eq!(locked.owner, owner.pubkey());
+    assert_eq!(locked.total_locked, reward());
+    assert_eq!(env.checker_balance(checker.index).await, 0);
+
+    let (treasury_state, _) = TreasuryState::find_pda(&depin::ID);
+    let state: TreasuryState = env.read(&treasury_state, DepinAccountType::TreasuryState).await;
+    assert_eq!(state.locked_balance, reward());
+
+    let result = env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn payout_rejects_strangers_and_a_wrong_lock_schedule() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+    credit_checkers(&mut env, &[&checker]).await;
+
+    let stranger = env.funded_keypair();
+    let result = env.process(builders::payout_checker_rewards(&stranger.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&stranger]).await;
+    assert_instruction_error(result, InstructionError::MissingRequiredSignature);
+
+    let result = env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PERIOD, UNLOCK_PERIOD), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    assert_eq!(env.checker_balance(checker.index).await as u64, reward());
+}
+
+#[tokio::test]
+async fn payout_batch_sums_licenses_of_one_owner() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let owner = env.funded_keypair();
+
+    for _ in 0..2 {
+        let index = tree.mint(owner.pubkey());
+        env.sync_tree(&tree);
+        let license = tree.license(index);
+        env.process(builders::activate_checker(&owner.pubkey(), &license, &owner.pubkey()), &[&owner]).await.unwrap();
+    }
+    let (_, other) = env.activate_checker(&mut tree).await;
+    let (first, second) = (tree.license(0), tree.license(1));
+
+    credit_checkers(&mut env, &[&first, &second, &other]).await;
+
+    let mixed = builders::payout_checker_rewards_batch(&owner.pubkey(), &[&first, &tree.license(other.index)], PAYOUT_PERIOD, UNLOCK_PERIOD);
+    assert_instruction_error(env.process(mixed, &[&owner]).await, InstructionError::InvalidArgument);
+
+    env.process(builders::payout_checker_rewards_batch(&owner.pubkey(), &[&first, &second], PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner])
+        .await
+        .unwrap();
+
+    let locked: LockedTokens = env
+        .read(&builders::locked_tokens(&owner.pubkey(), PAYOUT_PERIOD, UNLOCK_PERIOD), DepinAccountType::LockedTokens)
+        .await;
+    assert_eq!(locked.total_locked, 2 * reward());
+    assert_eq!(env.checker_balance(first.index).await, 0);
+    assert_eq!(env.checker_balance(second.index).await, 0);
+    assert_eq!(env.checker_balance(other.index).await as u64, reward());
+}
+
+#[tokio::test]
+async fn reward_beneficiary_receives_and_unlocks_the_payout() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+    let beneficiary = env.funded_keypair();
+    credit_checkers(&mut env, &[&checker]).await;
+
+    let stranger = env.funded_keypair();
+    let result = env.process(builders::set_checker_reward_beneficiary(&stranger.pubkey(), &checker, Some(stranger.pubkey())), &[&stranger]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.process(builders::set_checker_reward_beneficiary(&owner.pubkey(), &checker, Some(beneficiary.pubkey())), &[&owner])
+        .await
+        .unwrap();
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&checker), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.reward_beneficiary, Some(beneficiary.pubkey()));
+
+    // The owner's LockedTokens is no longer the destination
+    let result = env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let payout = builders::payout_checker_rewards_to(&owner.pubkey(), &checker, &beneficiary.pubkey(), PAYOUT_PERIOD, UNLOCK_PERIOD);
+    env.process(payout, &[&owner]).await.unwrap();
+
+    let locked: LockedTokens = env
+        .read(&builders::locked_tokens(&beneficiary.pubkey(), PAYOUT_PERIOD, UNLOCK_PERIOD), DepinAccountType::LockedTokens)
+        .await;
+    assert_eq!((locked.owner, locked.total_locked), (beneficiary.pubkey(), reward()));
+
+    let beneficiary_token = env.create_token_account(&BMB_MINT, &beneficiary.pubkey(), 0);
+    env.warp_to_period(UNLOCK_PERIOD).await;
+    env.process(builders::unlock(&beneficiary.pubkey(), &beneficiary_token, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&beneficiary])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_token).await, reward());
+
+    env.process(builders::set_checker_reward_beneficiary(&owner.pubkey(), &checker, None), &[&owner]).await.unwrap();
+    let metadata: CheckerMetadata = env.read(&builders::checker_metadata(&checker), DepinAccountType::CheckerMetadata).await;
+    assert_eq!(metadata.reward_recipient(&owner.pubkey()), owner.pubkey());
+}
+
+#[tokio::test]
+async fn instant_payout_sends_the_rewards_less_the_haircut_without_locking() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+    credit_checkers(&mut env, &[&checker]).await;
+    env.process(builders::set_instant_payout_haircut(&env.payer().pubkey(), 2_500), &[]).await.unwrap();
+
+    // Only a BMB account of the reward recipient can receive the payout
+    let stranger_token = env.create_token_account(&BMB_MINT, &env.payer().pubkey(), 0);
+    let result = env.process(builders::payout_checker_rewards_instantly(&owner.pubkey(), &checker, &stranger_token, 2_500), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidAccountData);
+
+    // The payout is refused when the haircut is above what the caller accepted
+    let owner_token = env.create_token_account(&BMB_MINT, &owner.pubkey(), 0);
+    let result = env.process(builders::payout_checker_rewards_instantly(&owner.pubkey(), &checker, &owner_token, 2_499), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+    assert_eq!(env.checker_balance(checker.index).await as u64, reward());
+
+    // A delegate can only lock the rewards, the haircut is the owner's call
+    let delegate = env.funded_keypair();
+    env.process(builders::set_checker_delegate(&owner.pubkey(), &checker, &delegate.pubkey()), &[&owner]).await.unwrap();
+    let result = env.process(builders::payout_checker_rewards_instantly(&delegate.pubkey(), &checker, &owner_token, 2_500), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::MissingRequiredSignature);
+
+    env.process(builders::payout_checker_rewards_instantly(&owner.pubkey(), &checker, &owner_token, 2_500), &[&owner])
+        .await
+        .unwrap();
+
+    let haircut = reward() / 4;
+    assert_eq!(env.token_balance(&owner_token).await, reward() - haircut);
+    assert_eq!(env.token_balance(&builders::treasury_ata()).await, TREASURY_BALANCE - (reward() - haircut));
+    assert_eq!(env.checker_balance(checker.index).await, 0);
+
+    let locked_tokens = builders::locked_tokens(&owner.pubkey(), PAYOUT_PERIOD, UNLOCK_PERIOD);
+    assert!(env.account(&locked_tokens).await.is_none());
+    let (treasury_state, _) = TreasuryState::find_pda(&depin::ID);
+    let state: TreasuryState = env.read(&treasury_state, DepinAccountType::TreasuryState).await;
+    assert_eq!(state.locked_balance, 0);
+}
+
+#[tokio::test]
+async fn payout_batch_rejects_licenses_with_different_beneficiaries() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let owner = env.funded_keypair();
+
+    for _ in 0..2 {
+        let index = tree.mint(owner.pubkey());
+        env.sync_tree(&tree);
+        let license = tree.license(index);
+        env.process(builders::activate_checker(&owner.pubkey(), &license, &owner.pubkey()), &[&owner]).await.unwrap();
+    }
+    let (first, second) = (tree.license(0), tree.license(1));
+    credit_checkers(&mut env, &[&first, &second]).await;
+
+    let beneficiary = env.funded_keypair();
+    env.process(builders::set_checker_reward_beneficiary(&owner.pubkey(), &second, Some(beneficiary.pubkey())), &[&owner])
+        .await
+        .unwrap();
+
+    let batch = builders::payout_checker_rewards_batch(&owner.pubkey(), &[&first, &second], PAYOUT_PERIOD, UNLOCK_PERIOD);
+    assert_instruction_error(env.process(batch, &[&owner]).await, InstructionError::InvalidArgument);
+    assert_eq!(env.checker_balance(second.index).await as u64, reward());
+}
+
+#[tokio::test]
+async fn unlock_before_maturity_pays_out_minus_a_linear_penalty() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+    credit_checkers(&mut env, &[&checker]).await;
+    env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner])
+        .await
+        .unwrap();
+
+    let owner_token = env.create_token_account(&BMB_MINT, &owner.pubkey(), 0);
+
+    let result = env.process(builders::unlock(&owner.pubkey(), &owner_token, PERIOD, UNLOCK_PERIOD), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::UninitializedAccount);
+
+    // A fifth into the lock, 80% of the 90% maximum penalty is still due
+    env.warp_to_period(PAYOUT_PERIOD + 73).await;
+    env.process(builders::unlock(&owner.pubkey(), &owner_token, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner]).await.unwrap();
+
+    let penalty = reward() * 7_200 / 10_000;
+    assert_eq!(env.token_balance(&owner_token).await, reward() - penalty);
+    assert_eq!(env.token_balance(&builders::treasury_ata()).await, TREASURY_BALANCE - reward() + penalty);
+
+    let (treasury_state, _) = TreasuryState::find_pda(&depin::ID);
+    let state: TreasuryState = env.read(&treasury_state, DepinAccountType::TreasuryState).await;
+    assert_eq!(state.locked_balance, 0);
+
+    let result = env.process(builders::unlock(&owner.pubkey(), &owner_token, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn unlock_after_maturity_pays_out_in_full() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, checker) = env.activate_checker(&mut tree).await;
+    credit_checkers(&mut env, &[&checker]).await;
+    env.process(builders::payout_checker_rewards(&owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner])
+        .await
+        .unwrap();
+
+    let owner_token = env.create_token_account(&BMB_MINT, &owner.pubkey(), 0);
+    env.warp_to_period(UNLOCK_PERIOD).await;
+    env.process(builders::unlock(&owner.pubkey(), &owner_token, PAYOUT_PERIOD, UNLOCK_PERIOD), &[&owner]).await.unwrap();
+
+    assert_eq!(env.token_balance(&owner_token).await, reward());
+}
@@ -0,0 +1,733 @@
+//! Instruction builders following the "Expected Accounts" lists of each processor
+
+use borsh::BorshSerialize;
+use checker::input::{
+    ActivateCheckerInput, CheckerPayoutBatchEntry, MigrateCheckerMetadataInput, PayoutCheckerRewardsBatchInput,
+    PayoutCheckerRewardsInput, PayoutMode, RevokeCheckerDelegateInput, SetCheckerDelegateInput, SetCheckerRewardBeneficiaryInput,
+};
+use escrow_authority::input::{AddEscrowDelegateInput, RemoveEscrowDelegateInput};
+use escrow_deposit::input::EscrowDepositInput;
+use escrow_withdraw::input::EscrowWithdrawInput;
+use global::input::{ActivateCheckersInput, RemoveAllowedMintInput, SetAllowedMintInput, SetInstantPayoutHaircutInput, SetPauseFlagsInput};
+use infra_offer::input::{RegisterInfraOfferInput, SetInfraOfferStatusInput, UpdateInfraOfferInput};
+use lease::input::{ClaimLeasePaymentInput, EnforceLeaseSlaInput, OpenLeaseInput};
+use payment_stream::input::{ClosePaymentStreamInput, CrankPaymentStreamInput, OpenPaymentStreamInput};
+use shared::{
+    constants::{accounts::BMB_MINT, programs::MPL_ACCOUNT_COMPRESSION_PROGRAM},
+    features::{
+        checker::accounts::{CheckerLicenseMetadata, CheckerMetadata},
+        escrow::accounts::{EscrowAuthority, EscrowState, EscrowToken},
+        global::accounts::{AllowedMints, BMBState, PauseState},
+        infra_offer::accounts::{InfraOffer, InfraOfferStatus, SlaTerms},
+        lease::accounts::{Lease, LeaseVault},
+        payment_stream::accounts::PaymentStream,
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{LockedTokens, TreasuryAuthority, TreasuryConfig, TreasuryState},
+        worker::accounts::{WorkerLicenseMetadata, WorkerMetadata, WorkerProof},
+    },
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_system_interface::program::ID as SYSTEM_PROGRAM;
+use spl_associated_token_account::get_associated_token_address;
+use worker::input::{
+    ActivateWorkerInput, MigrateWorkerMetadataInput, RevokeWorkerDelegateInput, SetWorkerDelegateInput,
+    SetWorkerRewardBeneficiaryInput, SubmitWorkerProofBatchInput, SubmitWorkerProofInput, UpdateWorkerUriInput, WorkerProofBatchEntry,
+};
+
+use std::ops::Range;
+
+use super::license_tree::License;
+
+fn instruction(discriminator: u8, data: &[u8], accounts: Vec<AccountMeta>) -> Instruction {
+    let mut instruction_data = vec![discriminator];
+    instruction_data.extend_from_slice(data);
+
+    Instruction {
+        program_id: depin::ID,
+        accounts,
+        data: instruction_data,
+    }
+}
+
+/// Instructions stopped by a PauseState flag take the PauseState PDA as their last account
+fn guarded(discriminator: u8, data: &[u8], mut accounts: Vec<AccountMeta>) -> Instruction {
+    accounts.push(AccountMeta::new_readonly(pause_state(), false));
+    instruction(discriminator, data, accounts)
+}
+
+fn borsh(input: &impl BorshSerialize) -> Vec<u8> {
+    borsh::to_vec(input).unwrap()
+}
+
+fn pda(find: (Pubkey, u8)) -> Pubkey {
+    find.0
+}
+
+pub fn worker_metadata(license: &License) -> Pubkey {
+    pda(WorkerMetadata::find_pda(&depin::ID, &license.asset_id, &license.owner))
+}
+
+pub fn worker_license_metadata(license: &License) -> Pubkey {
+    pda(WorkerLicenseMetadata::find_pda(&depin::ID, &license.asset_id))
+}
+
+pub fn worker_proof(worker_license: &Pubkey, period: u16) -> Pubkey {
+    pda(WorkerProof::find_pda(&depin::ID, worker_license, period))
+}
+
+pub fn checker_metadata(license: &License) -> Pubkey {
+    pda(CheckerMetadata::find_pda(&depin::ID, &license.asset_id, &license.owner))
+}
+
+pub fn checker_license_metadata(license: &License) -> Pubkey {
+    pda(CheckerLicenseMetadata::find_pda(&depin::ID, &license.asset_id))
+}
+
+pub fn global_rewards() -> Pubkey {
+    pda(GlobalRewards::find_pda(&depin::ID))
+}
+
+pub fn pause_state() -> Pubkey {
+    pda(PauseState::find_pda(&depin::ID))
+}
+
+pub fn bmb_state() -> Pubkey {
+    pda(BMBState::find_pda(&depin::ID))
+}
+
+pub fn allowed_mints() -> Pubkey {
+    pda(AllowedMints::find_pda(&depin::ID))
+}
+
+pub fn treasury_ata() -> Pubkey {
+    get_associated_token_address(&pda(TreasuryAuthority::find_pda(&depin::ID)), &BMB_MINT)
+}
+
+pub fn locked_tokens(owner: &Pubkey, lock_period: u16, unlock_period: u16) -> Pubkey {
+    pda(LockedTokens::find_pda(&depin::ID, owner, lock_period, unlock_period))
+}
+
+pub fn escrow_token(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    pda(EscrowToken::find_pda(&depin::ID, owner, mint))
+}
+
+pub fn escrow_state(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    pda(EscrowState::find_pda(&depin::ID, owner, mint))
+}
+
+pub fn escrow_authority(beneficiary: &Pubkey) -> Pubkey {
+    pda(EscrowAuthority::find_pda(&depin::ID, beneficiary))
+}
+
+pub fn infra_offer(worker_license: &Pubkey, offer_id: u16) -> Pubkey {
+    pda(InfraOffer::find_pda(&depin::ID, worker_license, offer_id))
+}
+
+pub fn lease(offer: &Pubkey, buyer: &Pubkey, lease_id: u64) -> Pubkey {
+    pda(Lease::find_pda(&depin::ID, offer, buyer, lease_id))
+}
+
+pub fn lease_vault(lease: &Pubkey) -> Pubkey {
+    pda(LeaseVault::find_pda(&depin::ID, lease))
+}
+
+pub fn payment_stream(payer: &Pubkey, worker_license: &Pubkey, stream_id: u64) -> Pubkey {
+    pda(PaymentStream::find_pda(&depin::ID, payer, worker_license, stream_id))
+}
+
+pub fn init_network(caller: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*caller, true),
+        AccountMeta::new(global_rewards(), false),
+        AccountMeta::new(pda(TreasuryState::find_pda(&depin::ID)), false),
+        AccountMeta::new(pda(TreasuryConfig::find_pda(&depin::ID)), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    instruction(2, &[], accounts)
+}
+
+pub fn activate_checker_licenses(admin: &Pubkey, period: u16, checker_count: u32) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(bmb_state(), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    instruction(4, &borsh(&ActivateCheckersInput { period, checker_count }), accounts)
+}
+
+pub fn set_allowed_mint(admin: &Pubkey, mint: &Pubkey, max_deposit: u64, paused: bool) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(allowed_mints(), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    let input = SetAllowedMintInput { mint: *mint, max_deposit, paused };
+    instruction(31, &borsh(&input), accounts)
+}
+
+pub fn remove_allowed_mint(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = vec![AccountMeta::new(*admin, true), AccountMeta::new(allowed_mints(), false)];
+    instruction(32, &borsh(&RemoveAllowedMintInput { mint: *mint }), accounts)
+}
+
+fn license_accounts(signer: &Pubkey, metadata: Pubkey, license: &License) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    accounts.extend(license.proof_metas());
+    accounts
+}
+
+pub fn activate_worker(owner: &Pubkey, license: &License, delegated_to: &Pubkey, discovery_uri: &str) -> Instruction {
+    let input = ActivateWorkerInput {
+        license_context: license.context(),
+        delegated_to: *delegated_to,
+        discovery_uri: discovery_uri.to_string(),
+        discovery: None,
+    };
+    guarded(3, &borsh(&input), license_accounts(owner, worker_metadata(license), license))
+}
+
+pub fn update_worker_uri(delegate: &Pubkey, license: &License, discovery_uri: &str) -> Instruction {
+    let input = UpdateWorkerUriInput {
+        license_context: license.context(),
+        discovery_uri: discovery_uri.to_string(),
+        discovery: None,
+    };
+    instruction(9, &borsh(&input), license_accounts(delegate, worker_metadata(license), license))
+}
+
+pub fn set_worker_delegate(owner: &Pubkey, license: &License, delegated_to: &Pubkey) -> Instruction {
+    let accounts = vec![AccountMeta::new(*owner, true), AccountMeta::new(worker_metadata(license), false)];
+    let input = SetWorkerDelegateInput { license: license.asset_id, delegated_to: *delegated_to };
+    instruction(14, &borsh(&input), accounts)
+}
+
+pub fn set_worker_reward_beneficiary(owner: &Pubkey, license: &License, beneficiary: Option<Pubkey>) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(worker_metadata(license), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    let input = SetWorkerRewardBeneficiaryInput { license: license.asset_id, beneficiary };
+    instruction(39, &borsh(&input), accounts)
+}
+
+pub fn revoke_worker_delegate(owner: &Pubkey, license: &License) -> Instruction {
+    let accounts = vec![AccountMeta::new(*owner, true), AccountMeta::new(worker_metadata(license), false)];
+    instruction(15, &borsh(&RevokeWorkerDelegateInput { license: license.asset_id }), accounts)
+}
+
+/// `license` is read after the transfer, `previous_owner` is the wallet whose metadata is stale
+pub fn migrate_worker_metadata(payer: &Pubkey, license: &License, previous_owner: &Pubkey) -> Instruction {
+    let stale = pda(WorkerMetadata::find_pda(&depin::ID, &license.asset_id, previous_owner));
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(stale, false),
+        AccountMeta::new(worker_metadata(license), false),
+        AccountMeta::new(worker_license_metadata(license), false),
+        AccountMeta::new(*previous_owner, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    accounts.extend(license.proof_metas());
+
+    let input = MigrateWorkerMetadataInput { license_context: license.context(), previous_owner: *previous_owner };
+    instruction(12, &borsh(&input), accounts)
+}
+
+pub struct ProofArgs {
+    pub period: u16,
+    pub checkers: [u64; 8],
+    pub uptime: u32,
+    pub latency: u32,
+}
+
+pub fn submit_worker_proof(delegate: &Pubkey, license: &License, args: &ProofArgs) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*delegate, true),
+        AccountMeta::new(global_rewards(), false),
+        AccountMeta::new(worker_proof(&license.asset_id, args.period), false),
+        AccountMeta::new_readonly(worker_metadata(license), false),
+        AccountMeta::new_readonly(worker_license_metadata(license), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(bmb_state(), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    accounts.extend(license.proof_metas());
+
+    let input = SubmitWorkerProofInput {
+        license_context: license.context(),
+        proof_root: [7; 32],
+        period: args.period,
+        checkers: args.checkers,
+        uptime: args.uptime,
+        latency: args.latency,
+    };
+    guarded(1, &borsh(&input), accounts)
+}
+
+/// All licenses must come from the same tree
+pub fn submit_worker_proof_batch(delegate: &Pubkey, period: u16, entries: &[(&License, [u64; 8])]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*delegate, true),
+        AccountMeta::new(global_rewards(), false),
+        AccountMeta::new_readonly(bmb_state(), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(entries[0].0.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+
+    let mut batch = Vec::with_capacity(entries.len());
+    for (license, checkers) in entries {
+        accounts.push(AccountMeta::new(worker_proof(&license.asset_id, period), false));
+        accounts.push(AccountMeta::new_readonly(worker_metadata(license), false));
+        accounts.push(AccountMeta::new_readonly(worker_license_metadata(license), false));
+        accounts.extend(license.proof_metas());
+
+        batch.push(WorkerProofBatchEntry {
+            license_context: license.context(),
+            proof_root: [7; 32],
+            checkers: *checkers,
+            uptime: 100,
+            latency: 10,
+            proof_accounts_len: license.proof.len() as u8,
+        });
+    }
+
+    guarded(10, &borsh(&SubmitWorkerProofBatchInput { period, entries: batch }), accounts)
+}
+
+pub fn activate_checker(owner: &Pubkey, license: &License, delegated_to: &Pubkey) -> Instruction {
+    let input = ActivateCheckerInput { license_context: license.context(), delegated_to: *delegated_to };
+    guarded(6, &borsh(&input), license_accounts(owner, checker_metadata(license), license))
+}
+
+pub fn set_checker_delegate(owner: &Pubkey, license: &License, delegated_to: &Pubkey) -> Instruction {
+    let accounts = vec![AccountMeta::new(*owner, true), AccountMeta::new(checker_metadata(license), false)];
+    let input = SetCheckerDelegateInput { license: license.asset_id, delegated_to: *delegated_to };
+    instruction(16, &borsh(&input), accounts)
+}
+
+pub fn revoke_checker_delegate(owner: &Pubkey, license: &License) -> Instruction {
+    let accounts = vec![AccountMeta::new(*owner, true), AccountMeta::new(checker_metadata(license), false)];
+    instruction(17, &borsh(&RevokeCheckerDelegateInput { license: license.asset_id }), accounts)
+}
+
+pub fn set_checker_reward_beneficiary(owner: &Pubkey, license: &License, beneficiary: Option<Pubkey>) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(checker_metadata(license), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    let input = SetCheckerRewardBeneficiaryInput { license: license.asset_id, beneficiary };
+    instruction(38, &borsh(&input), accounts)
+}
+
+pub fn migrate_checker_metadata(payer: &Pubkey, license: &License, previous_owner: &Pubkey) -> Instruction {
+    let stale = pda(CheckerMetadata::find_pda(&depin::ID, &license.asset_id, previous_owner));
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(stale, false),
+        AccountMeta::new(checker_metadata(license), false),
+        AccountMeta::new(checker_license_metadata(license), false),
+        AccountMeta::new(*previous_owner, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    accounts.extend(license.proof_metas());
+
+    let input = MigrateCheckerMetadataInput { license_context: license.context(), previous_owner: *previous_owner };
+    instruction(13, &borsh(&input), accounts)
+}
+
+fn treasury_accounts(owner: &Pubkey, lock_period: u16, unlock_period: u16) -> [AccountMeta; 4] {
+    [
+        AccountMeta::new(pda(TreasuryState::find_pda(&depin::ID)), false),
+        AccountMeta::new(treasury_ata(), false),
+        AccountMeta::new_readonly(pda(TreasuryConfig::find_pda(&depin::ID)), false),
+        AccountMeta::new(locked_tokens(owner, lock_period, unlock_period), false),
+    ]
+}
+
+pub fn payout_checker_rewards(signer: &Pubkey, license: &License, lock_period: u16, unlock_period: u16) -> Instruction {
+    payout_checker_rewards_to(signer, license, &license.owner, lock_period, unlock_period)
+}
+
+/// Payout of a checker whose rewards go to `recipient`, its reward beneficiary
+pub fn payout_checker_rewards_to(signer: &Pubkey, license: &License, recipient: &Pubkey, lock_period: u16, unlock_period: u16) -> Instruction {
+    let destination = locked_tokens(recipient, lock_period, unlock_period);
+    payout_checker_rewards_into(signer, license, &destination, PayoutMode::Locked, 0)
+}
+
+/// Instant payout into `recipient_token_account`, the BMB token account of the reward recipient,
+/// failing if the treasury keeps more than `max_haircut_bps`
+pub fn payout_checker_rewards_instantly(signer: &Pubkey, license: &License, recipient_token_account: &Pubkey, max_haircut_bps: u16) -> Instruction {
+    payout_checker_rewards_into(signer, license, recipient_token_account, PayoutMode::Instant, max_haircut_bps)
+}
+
+fn payout_checker_rewards_into(signer: &Pubkey, license: &License, destination: &Pubkey, mode: PayoutMode, max_haircut_bps: u16) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(global_rewards(), false),
+        AccountMeta::new(checker_metadata(license), false),
+        AccountMeta::new_readonly(checker_license_metadata(license), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+        AccountMeta::new(pda(TreasuryState::find_pda(&depin::ID)), false),
+        AccountMeta::new(treasury_ata(), false),
+        AccountMeta::new_readonly(pda(TreasuryConfig::find_pda(&depin::ID)), false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(pda(TreasuryAuthority::find_pda(&depin::ID)), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    accounts.extend(license.proof_metas());
+
+    guarded(8, &borsh(&PayoutCheckerRewardsInput { license_context: license.context(), mode, max_haircut_bps }), accounts)
+}
+
+pub fn payout_checker_rewards_batch(signer: &Pubkey, licenses: &[&License], lock_period: u16, unlock_period: u16) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(global_rewards(), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(licenses[0].tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    accounts.extend(treasury_accounts(&licenses[0].owner, lock_period, unlock_period));
+
+    let mut entries = Vec::with_capacity(licenses.len());
+    for license in licenses {
+        accounts.push(AccountMeta::new_readonly(checker_metadata(license), false));
+        accounts.push(AccountMeta::new_readonly(checker_license_metadata(license), false));
+        accounts.extend(license.proof_metas());
+        entries.push(CheckerPayoutBatchEntry {
+            license_context: license.context(),
+            proof_accounts_len: license.proof.len() as u8,
+        });
+    }
+
+    guarded(11, &borsh(&PayoutCheckerRewardsBatchInput { entries }), accounts)
+}
+
+pub fn unlock(owner: &Pubkey, owner_token_account: &Pubkey, lock_period: u16, unlock_period: u16) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(pda(TreasuryState::find_pda(&depin::ID)), false),
+        AccountMeta::new(treasury_ata(), false),
+        AccountMeta::new_readonly(pda(TreasuryAuthority::find_pda(&depin::ID)), false),
+        AccountMeta::new(locked_tokens(owner, lock_period, unlock_period), false),
+        AccountMeta::new(*owner_token_account, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    // UnlockInput is a single u16
+    guarded(7, &lock_period.to_le_bytes(), accounts)
+}
+
+pub struct OfferTerms {
+    pub price_mint: Pubkey,
+    pub price_per_period: u64,
+    pub sla: Option<SlaTerms>,
+}
+
+pub fn register_infra_offer(provider: &Pubkey, license: &License, offer_id: u16, terms: &OfferTerms) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*provider, true),
+        AccountMeta::new(infra_offer(&license.asset_id, offer_id), false),
+        AccountMeta::new_readonly(worker_metadata(license), false),
+        AccountMeta::new_readonly(worker_license_metadata(license), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(license.tree, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(allowed_mints(), false),
+    ];
+    accounts.extend(license.proof_metas());
+
+    let input = RegisterInfraOfferInput {
+        license_context: license.context(),
+        offer_id,
+        cpu: 8,
+        memory: 16_384,
+        region: "eu-west".to_string(),
+        price_mint: terms.price_mint,
+        price_per_period: terms.price_per_period,
+        name: "gpu-node".to_string(),
+        description: String::new(),
+        sla: terms.sla,
+    };
+    instruction(18, &borsh(&input), accounts)
+}
+
+pub fn update_infra_offer(provider: &Pubkey, offer: &Pubkey, terms: &OfferTerms, region: &str) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*provider, true),
+        AccountMeta::new(*offer, false),
+        AccountMeta::new_readonly(allowed_mints(), false),
+    ];
+    let input = UpdateInfraOfferInput {
+        cpu: 16,
+        memory: 32_768,
+        region: region.to_string(),
+        price_mint: terms.price_mint,
+        price_per_period: terms.price_per_period,
+        name: "gpu-node".to_string(),
+        description: "updated".to_string(),
+        sla: terms.sla,
+    };
+    instruction(19, &borsh(&input), accounts)
+}
+
+pub fn set_infra_offer_status(provider: &Pubkey, offer: &Pubkey, license: &License, status: InfraOfferStatus) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*provider, true),
+        AccountMeta::new(*offer, false),
+        AccountMeta::new_readonly(worker_metadata(license), false),
+        AccountMeta::new_readonly(worker_license_metadata(license), false),
+    ];
+    instruction(20, &borsh(&SetInfraOfferStatusInput { status }), accounts)
+}
+
+pub fn close_infra_offer(provider: &Pubkey, offer: &Pubkey) -> Instruction {
+    let accounts = vec![AccountMeta::new(*provider, true), AccountMeta::new(*offer, false)];
+    instruction(21, &[], accounts)
+}
+
+pub fn escrow_deposit(depositor: &Pubkey, depositor_token: &Pubkey, beneficiary: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*depositor, true),
+        AccountMeta::new(*depositor_token, false),
+        AccountMeta::new(escrow_token(beneficiary, mint), false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(allowed_mints(), false),
+    ];
+
+    let input = EscrowDepositInput { amount, beneficiary: Some(*beneficiary) };
+    guarded(22, &borsh(&input), accounts)
+}
+
+pub fn escrow_withdraw(withdrawer: &Pubkey, withdrawer_token: &Pubkey, escrow_owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*withdrawer, true),
+        AccountMeta::new(*withdrawer_token, false),
+        AccountMeta::new(escrow_token(escrow_owner, mint), false),
+        AccountMeta::new_readonly(depin::ID, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(allowed_mints(), false),
+        AccountMeta::new(escrow_state(escrow_owner, mint), false),
+    ];
+
+    let mut input = EscrowWithdrawInput { amount, escrow_owner: None };
+    if escrow_owner != withdrawer {
+        accounts.push(AccountMeta::new(escrow_authority(escrow_owner), false));
+        input.escrow_owner = Some(*escrow_owner);
+    }
+    guarded(23, &borsh(&input), accounts)
+}
+
+pub fn escrow_withdrawal_notice(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(escrow_state(owner, mint), false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    guarded(35, &amount.to_le_bytes(), accounts)
+}
+
+pub fn add_escrow_delegate(beneficiary: &Pubkey, delegate: &Pubkey, mint: &Pubkey, spend_limit: u64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*beneficiary, true),
+        AccountMeta::new(escrow_authority(beneficiary), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    let input = AddEscrowDelegateInput { delegate: *delegate, mint: *mint, spend_limit };
+    instruction(33, &borsh(&input), accounts)
+}
+
+pub fn remove_escrow_delegate(beneficiary: &Pubkey, delegate: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = vec![AccountMeta::new(*beneficiary, true), AccountMeta::new(escrow_authority(beneficiary), false)];
+    instruction(34, &borsh(&RemoveEscrowDelegateInput { delegate: *delegate, mint: *mint }), accounts)
+}
+
+/// `worker` is the offer's license, its proof shows the provider still owns it
+pub fn open_lease(buyer: &Pubkey, offer: &Pubkey, worker: &License, mint: &Pubkey, lease_id: u64, start_period: u16, periods: u16) -> Instruction {
+    let lease = lease(offer, buyer, lease_id);
+    let mut accounts = vec![
+        AccountMeta::new(*buyer, true),
+        AccountMeta::new_readonly(*offer, false),
+        AccountMeta::new(lease, false),
+        AccountMeta::new(escrow_token(buyer, mint), false),
+        AccountMeta::new(lease_vault(&lease), false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(escrow_state(buyer, mint), false),
+        AccountMeta::new_readonly(worker_metadata(worker), false),
+        AccountMeta::new_readonly(worker_license_metadata(worker), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(worker.tree, false),
+    ];
+    accounts.extend(worker.proof_metas());
+
+    let input = OpenLeaseInput { license_context: worker.context(), lease_id, start_period, periods };
+    guarded(24, &borsh(&input), accounts)
+}
+
+pub fn claim_lease_payment(owner: &Pubkey, lease: &Pubkey, recipient_token: &Pubkey, worker: &License, mint: &Pubkey, period: u16) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*lease, false),
+        AccountMeta::new(lease_vault(lease), false),
+        AccountMeta::new(*recipient_token, false),
+        AccountMeta::new_readonly(pda(WorkerMetadata::find_pda(&depin::ID, &worker.asset_id, owner)), false),
+        AccountMeta::new_readonly(worker_proof(&worker.asset_id, period), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(worker.tree, false),
+    ];
+    accounts.extend(worker.proof_metas());
+
+    let input = ClaimLeasePaymentInput { license_context: worker.context(), period };
+    guarded(25, &borsh(&input), accounts)
+}
+
+pub fn cancel_lease(buyer: &Pubkey, lease: &Pubkey, mint: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*buyer, true),
+        AccountMeta::new(*lease, false),
+        AccountMeta::new(lease_vault(lease), false),
+        AccountMeta::new(escrow_token(buyer, mint), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+    ];
+    guarded(26, &[], accounts)
+}
+
+pub fn enforce_lease_sla(cranker: &Pubkey, lease: &Pubkey, buyer: &Pubkey, worker_license: &Pubkey, mint: &Pubkey, period: u16) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*cranker, true),
+        AccountMeta::new(*lease, false),
+        AccountMeta::new(lease_vault(lease), false),
+        AccountMeta::new(escrow_token(buyer, mint), false),
+        AccountMeta::new_readonly(worker_proof(worker_license, period), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+    ];
+    guarded(27, &borsh(&EnforceLeaseSlaInput { period }), accounts)
+}
+
+pub struct StreamTerms {
+    pub stream_id: u64,
+    pub rate_per_period: u64,
+    pub start_period: u16,
+    pub end_period: u16,
+}
+
+pub fn open_payment_stream(payer: &Pubkey, worker: &License, mint: &Pubkey, terms: &StreamTerms) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(payment_stream(payer, &worker.asset_id, terms.stream_id), false),
+        AccountMeta::new_readonly(escrow_token(payer, mint), false),
+        AccountMeta::new_readonly(worker_metadata(worker), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+        AccountMeta::new(escrow_state(payer, mint), false),
+    ];
+    let input = OpenPaymentStreamInput {
+        stream_id: terms.stream_id,
+        worker_license: worker.asset_id,
+        worker_owner: worker.owner,
+        rate_per_period: terms.rate_per_period,
+        start_period: terms.start_period,
+        end_period: terms.end_period,
+    };
+    guarded(28, &borsh(&input), accounts)
+}
+
+pub fn crank_payment_stream(cranker: &Pubkey, stream: &Pubkey, payer: &Pubkey, worker: &License, recipient_token: &Pubkey, mint: &Pubkey, period: u16) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*cranker, true),
+        AccountMeta::new(*stream, false),
+        AccountMeta::new(escrow_token(payer, mint), false),
+        AccountMeta::new_readonly(worker_metadata(worker), false),
+        AccountMeta::new_readonly(worker_license_metadata(worker), false),
+        AccountMeta::new(*recipient_token, false),
+        AccountMeta::new_readonly(worker_proof(&worker.asset_id, period), false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(escrow_state(payer, mint), false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(worker.tree, false),
+    ];
+    accounts.extend(worker.proof_metas());
+
+    let input = CrankPaymentStreamInput {
+        license_context: worker.context(),
+    };
+    guarded(29, &borsh(&input), accounts)
+}
+
+/// `unsettled` are the finished periods the stream has not settled yet
+pub fn close_payment_stream(payer: &Pubkey, stream: &Pubkey, worker: &License, recipient_token: &Pubkey, mint: &Pubkey, unsettled: Range<u16>) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*stream, false),
+        AccountMeta::new(escrow_state(payer, mint), false),
+        AccountMeta::new(escrow_token(payer, mint), false),
+        AccountMeta::new_readonly(worker_metadata(worker), false),
+        AccountMeta::new_readonly(worker_license_metadata(worker), false),
+        AccountMeta::new(*recipient_token, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(MPL_ACCOUNT_COMPRESSION_PROGRAM, false),
+        AccountMeta::new_readonly(worker.tree, false),
+    ];
+    accounts.extend(unsettled.map(|period| AccountMeta::new_readonly(worker_proof(&worker.asset_id, period), false)));
+    accounts.extend(worker.proof_metas());
+
+    let input = ClosePaymentStreamInput {
+        license_context: worker.context(),
+    };
+    guarded(30, &borsh(&input), accounts)
+}
+
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    instruction(36, &[], accounts)
+}
+
+pub fn set_pause_flags(admin: &Pubkey, flags: u8) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(pause_state(), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    instruction(37, &borsh(&SetPauseFlagsInput { flags }), accounts)
+}
+
+pub fn set_instant_payout_haircut(admin: &Pubkey, haircut_bps: u16) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(pda(TreasuryConfig::find_pda(&depin::ID)), false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM, false),
+    ];
+    instruction(40, &borsh(&SetInstantPayoutHaircutInput { haircut_bps }), accounts)
+}
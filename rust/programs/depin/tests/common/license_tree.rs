@@ -0,0 +1,129 @@
+use mpl_bubblegum::{types::LeafSchema, utils::get_asset_id};
+use shared::features::bubblegum::cnft_context::CnftContext;
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+
+use super::mock_compression::hash_pair;
+
+pub const TREE_DEPTH: usize = 4;
+
+/// Off-chain copy of a license tree, leaves are hashed exactly like the program hashes them
+pub struct LicenseTree {
+    pub address: Pubkey,
+    owners: Vec<(Pubkey, Pubkey)>,
+}
+
+/// A license leaf with the root and proof of the tree at the time it was read
+#[derive(Clone, Debug)]
+pub struct License {
+    pub tree: Pubkey,
+    pub asset_id: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub index: u32,
+    pub root: [u8; 32],
+    pub proof: Vec<Pubkey>,
+}
+
+impl LicenseTree {
+    pub fn new() -> Self {
+        Self {
+            address: Pubkey::new_unique(),
+            owners: Vec::new(),
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels().last().unwrap()[0]
+    }
+
+    /// Appends a leaf owned and delegated to `owner`, returns its index
+    pub fn mint(&mut self, owner: Pubkey) -> u32 {
+        assert!(self.owners.len() < 1 << TREE_DEPTH, "license tree is full");
+        self.owners.push((owner, owner));
+        self.owners.len() as u32 - 1
+    }
+
+    pub fn transfer(&mut self, index: u32, new_owner: Pubkey) {
+        self.owners[index as usize] = (new_owner, new_owner);
+    }
+
+    pub fn license(&self, index: u32) -> License {
+        let levels = self.levels();
+        let proof = (0..TREE_DEPTH)
+            .map(|level| Pubkey::new_from_array(levels[level][(index as usize >> level) ^ 1]))
+            .collect();
+        let (owner, delegate) = self.owners[index as usize];
+
+        License {
+            tree: self.address,
+            asset_id: get_asset_id(&self.address, index as u64),
+            owner,
+            delegate,
+            index,
+            root: self.root(),
+            proof,
+        }
+    }
+
+    fn leaf(&self, index: usize) -> [u8; 32] {
+        match self.owners.get(index) {
+            Some(_) => self.license_leaf(index as u32).hash(),
+            None => [0; 32],
+        }
+    }
+
+    fn license_leaf(&self, index: u32) -> LeafSchema {
+        let (owner, delegate) = self.owners[index as usize];
+        let context = context(&self.address, owner, delegate, index, [0; 32]);
+        leaf_schema(&self.address, &context)
+    }
+
+    fn levels(&self) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![(0..1 << TREE_DEPTH).map(|index| self.leaf(index)).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels.last().unwrap().chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+            levels.push(next);
+        }
+        levels
+    }
+}
+
+impl License {
+    pub fn context(&self) -> CnftContext {
+        context(&self.tree, self.owner, self.delegate, self.index, self.root)
+    }
+
+    pub fn proof_metas(&self) -> Vec<AccountMeta> {
+        self.proof.iter().map(|node| AccountMeta::new_readonly(*node, false)).collect()
+    }
+}
+
+fn context(tree: &Pubkey, owner: Pubkey, delegate: Pubkey, index: u32, root: [u8; 32]) -> CnftContext {
+    CnftContext {
+        owner,
+        delegate,
+        nonce: index as u64,
+        index,
+        root,
+        // Metadata hashes only need to be stable per leaf
+        data_hash: tree.to_bytes(),
+        creator_hash: [index as u8; 32],
+        collection_hash: [0; 32],
+        asset_data_hash: [0; 32],
+        flags: 0,
+    }
+}
+
+fn leaf_schema(tree: &Pubkey, context: &CnftContext) -> LeafSchema {
+    LeafSchema::V2 {
+        id: get_asset_id(tree, context.nonce),
+        owner: context.owner,
+        delegate: context.delegate,
+        nonce: context.nonce,
+        data_hash: context.data_hash,
+        creator_hash: context.creator_hash,
+        collection_hash: context.collection_hash,
+        asset_data_hash: context.asset_data_hash,
+        flags: context.flags,
+    }
+}
@@ -0,0 +1,49 @@
+//! Stand-in for mpl-account-compression that only implements `verify_leaf`.
+//! The tree account stores its current root in the first 32 bytes, proofs are checked
+//! against it the same way the real program walks a concurrent merkle tree.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, keccak::hashv, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// Custom error the real program raises for a leaf that does not hash up to the root
+pub const INVALID_PROOF: u32 = 6001;
+
+pub fn process_instruction(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = data
+        .strip_prefix(&VERIFY_LEAF_DISCRIMINATOR)
+        .filter(|args| args.len() == 32 + 32 + 4)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let root: [u8; 32] = args[0..32].try_into().unwrap();
+    let leaf: [u8; 32] = args[32..64].try_into().unwrap();
+    let index = u32::from_le_bytes(args[64..68].try_into().unwrap());
+
+    let (tree, proof) = accounts.split_first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if tree.data_len() < 32 || tree.try_borrow_data()?[..32] != root {
+        msg!("Mock compression: root does not match the tree {}", tree.key);
+        return Err(ProgramError::Custom(INVALID_PROOF));
+    }
+
+    let computed = proof.iter().enumerate().fold(leaf, |node, (level, sibling)| {
+        if index >> level & 1 == 0 {
+            hash_pair(&node, &sibling.key.to_bytes())
+        } else {
+            hash_pair(&sibling.key.to_bytes(), &node)
+        }
+    });
+
+    if computed != root {
+        msg!("Mock compression: leaf {} does not hash up to the root", index);
+        return Err(ProgramError::Custom(INVALID_PROOF));
+    }
+
+    Ok(())
+}
+
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
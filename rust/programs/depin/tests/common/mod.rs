@@ -0,0 +1,318 @@
+//! In-process test harness: the depin program and a mock account compression program run as
+//! native builtins on a solana-program-test bank, the clock is warped per period. Benchmarks load
+//! the SBF build of depin instead, native builtins are not metered.
+
+#![allow(dead_code)]
+
+pub mod builders;
+pub mod license_tree;
+pub mod mock_compression;
+
+use shared::{
+    constants::{accounts::BMB_MINT, programs::MPL_ACCOUNT_COMPRESSION_PROGRAM},
+    features::{
+        global::accounts::AllowedMints,
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::TreasuryAuthority,
+        worker::accounts::WorkerMetadata,
+    },
+    types::account::{DepinAccount, DepinAccountType},
+    utils::{account::{read_account_data, write_account_data}, bmb::{PERIOD_ZERO, SECONDS_PER_PERIOD}, brand},
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, program_pack::Pack};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    clock::Clock,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use license_tree::{License, LicenseTree};
+
+pub const DECIMALS: u8 = 6;
+
+/// BRAND draws 512 distinct checkers per proof, with exactly 512 every checker has a slot
+pub const CHECKER_COUNT: u32 = 512;
+
+/// BMB held by the treasury authority at genesis
+pub const TREASURY_BALANCE: u64 = 1_000_000_000;
+
+/// The processor ties the slice and account lifetimes together, the builtin entrypoint type does not
+fn process_depin<'a, 'b, 'c>(program_id: &'a Pubkey, accounts: &'b [AccountInfo<'c>], data: &[u8]) -> ProgramResult {
+    let accounts: &'c [AccountInfo<'c>] = unsafe { std::mem::transmute(accounts) };
+    depin::process_instruction(program_id, accounts, data)
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    /// Makes otherwise identical transactions distinct
+    nonce: u64,
+}
+
+impl TestEnv {
+    /// Boots a bank with the BMB mint and a funded treasury, at the start of `period`
+    pub async fn start(period: u16) -> Self {
+        let mut program_test = ProgramTest::new("depin", depin::ID, processor!(process_depin));
+        program_test.prefer_bpf(false);
+        Self::boot(program_test, period).await
+    }
+
+    /// Like `start`, with depin loaded from `depin.so` in SBF_OUT_DIR so its compute units are metered
+    pub async fn start_sbf(period: u16) -> Self {
+        let mut program_test = ProgramTest::new("depin", depin::ID, None);
+        program_test.prefer_bpf(true);
+        Self::boot(program_test, period).await
+    }
+
+    async fn boot(mut program_test: ProgramTest, period: u16) -> Self {
+        program_test.add_program(
+            "mock_account_compression",
+            MPL_ACCOUNT_COMPRESSION_PROGRAM,
+            processor!(mock_compression::process_instruction),
+        );
+
+        let (treasury_authority, _) = TreasuryAuthority::find_pda(&depin::ID);
+        program_test.add_account(BMB_MINT, mint_account());
+        program_test.add_account(
+            get_associated_token_address(&treasury_authority, &BMB_MINT),
+            token_account(&BMB_MINT, &treasury_authority, TREASURY_BALANCE),
+        );
+
+        let mut env = Self {
+            ctx: program_test.start_with_context().await,
+            nonce: 0,
+        };
+        env.warp_to_period(period).await;
+        env
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    pub async fn process(&mut self, instruction: Instruction, signers: &[&Keypair]) -> Result<(), BanksClientError> {
+        self.nonce += 1;
+        let instructions = [instruction, ComputeBudgetInstruction::set_compute_unit_price(self.nonce)];
+
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend(signers.iter().filter(|signer| signer.pubkey() != payer.pubkey()));
+
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.ctx.last_blockhash,
+        );
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    /// Compute units consumed by `instruction` alone, simulated without committing
+    pub async fn compute_units(&mut self, instruction: Instruction, signers: &[&Keypair]) -> u64 {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend(signers.iter().filter(|signer| signer.pubkey() != payer.pubkey()));
+
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer.pubkey()), &all_signers, self.ctx.last_blockhash);
+        let simulation = self.ctx.banks_client.simulate_transaction(transaction).await.unwrap();
+        let details = simulation.simulation_details.expect("simulation details");
+        if let Some(Err(err)) = simulation.result {
+            panic!("simulation failed with {err:?}, logs: {:#?}", details.logs);
+        }
+        details.units_consumed
+    }
+
+    pub async fn warp_to_period(&mut self, period: u16) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = PERIOD_ZERO + period as i64 * SECONDS_PER_PERIOD + SECONDS_PER_PERIOD / 2;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.ctx.banks_client.get_account(*address).await.unwrap()
+    }
+
+    pub async fn read<T: DepinAccount>(&mut self, address: &Pubkey, account_type: DepinAccountType) -> T {
+        let account = self.account(address).await.unwrap_or_else(|| panic!("{address} does not exist"));
+        read_account_data(&account.data, account_type).unwrap()
+    }
+
+    /// A new system account with 10 SOL
+    pub fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        self.ctx.set_account(
+            &keypair.pubkey(),
+            &AccountSharedData::new(10 * LAMPORTS_PER_SOL, 0, &solana_system_interface::program::ID),
+        );
+        keypair
+    }
+
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        self.ctx.set_account(&mint, &mint_account().into());
+        mint
+    }
+
+    pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let address = Pubkey::new_unique();
+        self.ctx.set_account(&address, &token_account(mint, owner, amount).into());
+        address
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self.account(address).await.unwrap_or_else(|| panic!("token account {address} does not exist"));
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Stores the tree's current root where the mock compression program reads it
+    pub fn sync_tree(&mut self, tree: &LicenseTree) {
+        let mut account = AccountSharedData::new(LAMPORTS_PER_SOL, 32, &MPL_ACCOUNT_COMPRESSION_PROGRAM);
+        account.set_data_from_slice(&tree.root());
+        self.ctx.set_account(&tree.address, &account);
+    }
+
+    /// Runs InitNetwork until GlobalRewards reached its full size
+    pub async fn init_network(&mut self) {
+        let caller = self.payer();
+        for _ in 0..=GlobalRewards::LEN.div_ceil(10_240) {
+            self.process(builders::init_network(&caller.pubkey()), &[]).await.unwrap();
+        }
+
+        let (global_rewards, _) = GlobalRewards::find_pda(&depin::ID);
+        assert_eq!(self.account(&global_rewards).await.unwrap().data.len(), GlobalRewards::LEN);
+    }
+
+    pub async fn set_checker_count(&mut self, period: u16, checker_count: u32) {
+        let admin = self.payer();
+        self.process(builders::activate_checker_licenses(&admin.pubkey(), period, checker_count), &[]).await.unwrap();
+    }
+
+    /// Mints a license to a new funded owner and activates its worker, the owner is its own delegate
+    pub async fn activate_worker(&mut self, tree: &mut LicenseTree) -> (Keypair, License) {
+        let owner = self.funded_keypair();
+        let index = tree.mint(owner.pubkey());
+        self.sync_tree(tree);
+
+        let license = tree.license(index);
+        self.process(builders::activate_worker(&owner.pubkey(), &license, &owner.pubkey(), "https://worker.example"), &[&owner])
+            .await
+            .unwrap();
+        (owner, license)
+    }
+
+    /// Mints a license to a new funded owner and activates its checker, the owner is its own delegate
+    pub async fn activate_checker(&mut self, tree: &mut LicenseTree) -> (Keypair, License) {
+        let owner = self.funded_keypair();
+        let index = tree.mint(owner.pubkey());
+        self.sync_tree(tree);
+
+        let license = tree.license(index);
+        self.process(builders::activate_checker(&owner.pubkey(), &license, &owner.pubkey()), &[&owner])
+            .await
+            .unwrap();
+        (owner, license)
+    }
+
+    /// Marks the worker of `license` suspended, no instruction suspends workers yet
+    pub async fn suspend_worker(&mut self, license: &License) {
+        let address = builders::worker_metadata(license);
+        let mut account = self.account(&address).await.unwrap();
+        let mut metadata: WorkerMetadata = read_account_data(&account.data, DepinAccountType::WorkerMetadata).unwrap();
+        metadata.suspended_at = Some(0);
+        write_account_data(&mut account.data, DepinAccountType::WorkerMetadata, &metadata).unwrap();
+        self.ctx.set_account(&address, &account.into());
+    }
+
+    /// Deposits `amount` of `mint` into the escrow of `owner`, from a token account holding exactly that much
+    pub async fn fund_escrow(&mut self, owner: &Keypair, mint: &Pubkey, amount: u64) {
+        let source = self.create_token_account(mint, &owner.pubkey(), amount);
+        self.process(builders::escrow_deposit(&owner.pubkey(), &source, &owner.pubkey(), mint, amount), &[owner])
+            .await
+            .unwrap();
+    }
+
+    /// Unpaid rewards of the checker at `index` in GlobalRewards
+    pub async fn checker_balance(&mut self, index: u32) -> u32 {
+        let (global_rewards, _) = GlobalRewards::find_pda(&depin::ID);
+        let account = self.account(&global_rewards).await.unwrap();
+        GlobalRewards::read_checker_balance(&account.data, index as usize).unwrap()
+    }
+
+    /// Lists `mint` in AllowedMints with an unreachable deposit cap
+    pub async fn allow_mint(&mut self, mint: &Pubkey) {
+        let admin = self.payer();
+        self.process(builders::set_allowed_mint(&admin.pubkey(), mint, u64::MAX, false), &[]).await.unwrap();
+
+        let (allowed_mints, _) = AllowedMints::find_pda(&depin::ID);
+        let listed: AllowedMints = self.read(&allowed_mints, DepinAccountType::AllowedMints).await;
+        assert!(listed.mints.iter().any(|entry| entry.mint == *mint));
+    }
+}
+
+fn mint_account() -> Account {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: Some(Pubkey::new_unique()).into(),
+        supply: u64::MAX / 2,
+        decimals: DECIMALS,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    }
+    .pack_into_slice(&mut data);
+
+    Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+
+    Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Proof bitmap crediting the checker at `checker_index` through the first BRAND slot that selects it
+pub fn bitmap_for_checker(worker: &License, period: u16, checker_count: u32, checker_index: u32) -> [u64; 8] {
+    let numbers = brand::generate_numbers(worker.asset_id.as_ref(), period, 512, checker_count as u64);
+    let slot = numbers
+        .iter()
+        .position(|number| *number == checker_index)
+        .unwrap_or_else(|| panic!("BRAND never selects checker {checker_index} for this worker"));
+
+    let mut bitmap = [0; 8];
+    bitmap[slot / 64] |= 1 << (slot % 64);
+    bitmap
+}
+
+/// Asserts that the first instruction of the transaction failed with `expected`
+pub fn assert_instruction_error(result: Result<(), BanksClientError>, expected: InstructionError) {
+    match result.map_err(|err| err.unwrap()) {
+        Err(TransactionError::InstructionError(0, err)) => assert_eq!(err, expected),
+        other => panic!("expected {expected:?}, got {other:?}"),
+    }
+}
@@ -0,0 +1,125 @@
+//! Compute units of the instructions on the hot accounts (GlobalRewards, BMBState, TreasuryState and
+//! WorkerProof). Native builtins are not metered, so this runs against the SBF build of depin through
+//! `compute-units.sh`, which builds it first.
+//!
+//! Every instruction is checked against its figure in `compute_units.baseline`, so a change that makes
+//! a hot instruction more expensive fails here. `CU_RECORD=1 ./compute-units.sh` rewrites the baseline
+//! after an intended change, commit it together with the change.
+
+mod common;
+
+use common::{
+    bitmap_for_checker,
+    builders::{self, ProofArgs},
+    license_tree::LicenseTree,
+    TestEnv, CHECKER_COUNT,
+};
+use shared::constants::accounts::BMB_MINT;
+use solana_sdk::signer::Signer;
+use std::{collections::BTreeMap, fs};
+
+const PERIOD: u16 = 20;
+const PAYOUT_PERIOD: u16 = PERIOD + 1;
+const UNLOCK_PERIOD: u16 = PAYOUT_PERIOD + 365;
+
+/// Compute units an instruction gets without a SetComputeUnitLimit instruction
+const DEFAULT_INSTRUCTION_BUDGET: u64 = 200_000;
+
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.baseline");
+
+/// Headroom over the recorded figure, compiler and runtime updates move it by a few units
+const TOLERANCE_PERCENT: u64 = 2;
+
+/// Reads the `<instruction>\t<units>` lines of the baseline
+fn read_baseline() -> BTreeMap<String, u64> {
+    let baseline = fs::read_to_string(BASELINE)
+        .unwrap_or_else(|err| panic!("no baseline at {BASELINE} ({err}), record one with `CU_RECORD=1 ./compute-units.sh`"));
+    baseline
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, units) = line.split_once('\t').expect("baseline lines are `<instruction>\\t<units>`");
+            (name.to_string(), units.parse().expect("baseline units are a number"))
+        })
+        .collect()
+}
+
+fn write_baseline(report: &[(&str, u64)]) {
+    let mut baseline = String::from("# Written by `CU_RECORD=1 ./compute-units.sh`, compute units of the SBF build\n");
+    for (name, units) in report {
+        baseline.push_str(&format!("{name}\t{units}\n"));
+    }
+    fs::write(BASELINE, baseline).unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs the SBF build of depin in SBF_OUT_DIR"]
+async fn hot_account_instructions() {
+    let mut env = TestEnv::start_sbf(PERIOD - 1).await;
+    env.init_network().await;
+    env.set_checker_count(PERIOD, CHECKER_COUNT).await;
+    env.warp_to_period(PAYOUT_PERIOD).await;
+
+    let mut checker_tree = LicenseTree::new();
+    let (checker_owner, checker) = env.activate_checker(&mut checker_tree).await;
+
+    let mut worker_tree = LicenseTree::new();
+    let delegate = env.funded_keypair();
+    let mut workers = Vec::new();
+    for _ in 0..3 {
+        let (owner, _) = env.activate_worker(&mut worker_tree).await;
+        workers.push(owner);
+    }
+    let licenses: Vec<_> = (0..3).map(|index| worker_tree.license(index)).collect();
+    for (owner, license) in workers.iter().zip(&licenses).skip(1) {
+        env.process(builders::set_worker_delegate(&owner.pubkey(), license, &delegate.pubkey()), &[owner]).await.unwrap();
+    }
+
+    let mut report = Vec::new();
+
+    // Every selected checker is credited through BRAND, so a full bitmap is the worst case
+    let args = ProofArgs { period: PERIOD, checkers: [u64::MAX; 8], uptime: 100, latency: 10 };
+    let submit = builders::submit_worker_proof(&workers[0].pubkey(), &licenses[0], &args);
+    report.push(("submit_worker_proof (512 checkers)", env.compute_units(submit, &[&workers[0]]).await));
+
+    let single = ProofArgs { checkers: bitmap_for_checker(&licenses[0], PERIOD, CHECKER_COUNT, checker.index), ..args };
+    let submit = builders::submit_worker_proof(&workers[0].pubkey(), &licenses[0], &single);
+    report.push(("submit_worker_proof (1 checker)", env.compute_units(submit.clone(), &[&workers[0]]).await));
+    env.process(submit, &[&workers[0]]).await.unwrap();
+
+    let entries = [(&licenses[1], [1; 8]), (&licenses[2], [1; 8])];
+    let batch = builders::submit_worker_proof_batch(&delegate.pubkey(), PERIOD, &entries);
+    report.push(("submit_worker_proof_batch (2 proofs)", env.compute_units(batch, &[&delegate]).await));
+
+    let payout = builders::payout_checker_rewards(&checker_owner.pubkey(), &checker, PAYOUT_PERIOD, UNLOCK_PERIOD);
+    report.push(("payout_checker_rewards", env.compute_units(payout.clone(), &[&checker_owner]).await));
+    env.process(payout, &[&checker_owner]).await.unwrap();
+
+    let owner_token = env.create_token_account(&BMB_MINT, &checker_owner.pubkey(), 0);
+    env.warp_to_period(PAYOUT_PERIOD + 73).await;
+    let unlock = builders::unlock(&checker_owner.pubkey(), &owner_token, PAYOUT_PERIOD, UNLOCK_PERIOD);
+    report.push(("unlock", env.compute_units(unlock, &[&checker_owner]).await));
+
+    if std::env::var_os("CU_RECORD").is_some() {
+        write_baseline(&report);
+    }
+    let baseline = read_baseline();
+
+    println!("{:<40} {:>10} {:>10}", "instruction", "CU", "baseline");
+    for (name, units) in &report {
+        let recorded = baseline.get(*name).map_or("-".to_string(), u64::to_string);
+        println!("{name:<40} {units:>10} {recorded:>10}");
+    }
+
+    let over_budget: Vec<_> = report.iter().filter(|(_, units)| *units > DEFAULT_INSTRUCTION_BUDGET).collect();
+    assert!(over_budget.is_empty(), "over the {DEFAULT_INSTRUCTION_BUDGET} CU default budget: {over_budget:?}");
+
+    for (name, units) in &report {
+        let recorded = *baseline.get(*name).unwrap_or_else(|| panic!("{name} is missing from the baseline"));
+        let ceiling = recorded + recorded * TOLERANCE_PERCENT / 100;
+        assert!(*units <= ceiling, "{name} uses {units} CU, over its ceiling of {ceiling} ({recorded} recorded)");
+    }
+
+    // Crediting checkers is the only part of a proof that grows with the bitmap
+    assert!(report[1].1 < report[0].1, "a single checker proof costs as much as a full bitmap: {report:?}");
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use common::{
+    assert_instruction_error,
+    builders::{self, StreamTerms},
+    license_tree::LicenseTree,
+    TestEnv,
+};
+use shared::{
+    features::{
+        escrow::accounts::{EscrowAuthority, EscrowState},
+        global::accounts::PauseState,
+    },
+    types::account::DepinAccountType,
+};
+use solana_sdk::{instruction::InstructionError, signer::Signer};
+
+const PERIOD: u16 = 30;
+
+#[tokio::test]
+async fn deposit_can_fund_another_beneficiary() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+
+    let depositor = env.funded_keypair();
+    let beneficiary = env.funded_keypair();
+    let source = env.create_token_account(&mint, &depositor.pubkey(), 1_000);
+
+    env.process(builders::escrow_deposit(&depositor.pubkey(), &source, &beneficiary.pubkey(), &mint, 600), &[&depositor])
+        .await
+        .unwrap();
+
+    assert_eq!(env.token_balance(&source).await, 400);
+    assert_eq!(env.token_balance(&builders::escrow_token(&beneficiary.pubkey(), &mint)).await, 600);
+    assert!(env.account(&builders::escrow_token(&depositor.pubkey(), &mint)).await.is_none());
+
+    // Only the beneficiary owns the escrow, the depositor cannot take it back
+    let result = env.process(builders::escrow_withdraw(&depositor.pubkey(), &source, &depositor.pubkey(), &mint, 600), &[&depositor]).await;
+    assert_instruction_error(result, InstructionError::UninitializedAccount);
+
+    let target = env.create_token_account(&mint, &beneficiary.pubkey(), 0);
+    env.process(builders::escrow_withdraw(&beneficiary.pubkey(), &target, &beneficiary.pubkey(), &mint, 600), &[&beneficiary])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&target).await, 600);
+}
+
+#[tokio::test]
+async fn deposit_enforces_the_allowed_mints() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let admin = env.payer();
+    let depositor = env.funded_keypair();
+    let mint = env.create_mint();
+    let source = env.create_token_account(&mint, &depositor.pubkey(), 1_000);
+    let deposit = |amount| builders::escrow_deposit(&depositor.pubkey(), &source, &depositor.pubkey(), &mint, amount);
+
+    // Lists another mint so AllowedMints exists but does not contain this one
+    let other = env.create_mint();
+    env.allow_mint(&other).await;
+    assert_instruction_error(env.process(deposit(100), &[&depositor]).await, InstructionError::InvalidAccountData);
+
+    env.process(builders::set_allowed_mint(&admin.pubkey(), &mint, 500, true), &[]).await.unwrap();
+    assert_instruction_error(env.process(deposit(100), &[&depositor]).await, InstructionError::InvalidAccountData);
+
+    env.process(builders::set_allowed_mint(&admin.pubkey(), &mint, 500, false), &[]).await.unwrap();
+    assert_instruction_error(env.process(deposit(501), &[&depositor]).await, InstructionError::InvalidArgument);
+    assert_instruction_error(env.process(deposit(0), &[&depositor]).await, InstructionError::InvalidInstructionData);
+
+    env.process(deposit(500), &[&depositor]).await.unwrap();
+    assert_eq!(env.token_balance(&builders::escrow_token(&depositor.pubkey(), &mint)).await, 500);
+}
+
+#[tokio::test]
+async fn withdraw_cannot_exceed_the_escrow_balance() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+    let owner = env.funded_keypair();
+    env.fund_escrow(&owner, &mint, 300).await;
+
+    let target = env.create_token_account(&mint, &owner.pubkey(), 0);
+    let result = env.process(builders::escrow_withdraw(&owner.pubkey(), &target, &owner.pubkey(), &mint, 301), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InsufficientFunds);
+
+    let mut instruction = builders::escrow_withdraw(&owner.pubkey(), &target, &owner.pubkey(), &mint, 300);
+    instruction.accounts[0].is_signer = false;
+    assert_instruction_error(env.process(instruction, &[]).await, InstructionError::MissingRequiredSignature);
+}
+
+#[tokio::test]
+async fn delegates_withdraw_within_their_spend_limit() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+    let owner = env.funded_keypair();
+    let delegate = env.funded_keypair();
+    env.fund_escrow(&owner, &mint, 1_000).await;
+    let target = env.create_token_account(&mint, &delegate.pubkey(), 0);
+
+    let result = env.process(builders::escrow_withdraw(&delegate.pubkey(), &target, &owner.pubkey(), &mint, 100), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::UninitializedAccount);
+
+    let result = env.process(builders::add_escrow_delegate(&owner.pubkey(), &owner.pubkey(), &mint, 100), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.process(builders::add_escrow_delegate(&owner.pubkey(), &delegate.pubkey(), &mint, 300), &[&owner]).await.unwrap();
+    env.process(builders::escrow_withdraw(&delegate.pubkey(), &target, &owner.pubkey(), &mint, 200), &[&delegate]).await.unwrap();
+
+    let result = env.process(builders::escrow_withdraw(&delegate.pubkey(), &target, &owner.pubkey(), &mint, 101), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::InsufficientFunds);
+
+    let authority: EscrowAuthority = env.read(&builders::escrow_authority(&owner.pubkey()), DepinAccountType::EscrowAuthority).await;
+    assert_eq!((authority.delegates[0].spend_limit, authority.delegates[0].spent), (300, 200));
+    assert_eq!(env.token_balance(&target).await, 200);
+
+    env.process(builders::remove_escrow_delegate(&owner.pubkey(), &delegate.pubkey(), &mint), &[&owner]).await.unwrap();
+    let result = env.process(builders::escrow_withdraw(&delegate.pubkey(), &target, &owner.pubkey(), &mint, 50), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+}
+
+#[tokio::test]
+async fn encumbered_funds_need_a_matured_withdrawal_notice() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+    let payer = env.funded_keypair();
+    env.fund_escrow(&payer, &mint, 1_000).await;
+
+    let mut tree = LicenseTree::new();
+    let (_, worker) = env.activate_worker(&mut tree).await;
+    let terms = StreamTerms {
+        stream_id: 1,
+        rate_per_period: 100,
+        start_period: PERIOD,
+        end_period: PERIOD + 6,
+    };
+    env.process(builders::open_payment_stream(&payer.pubkey(), &worker, &mint, &terms), &[&payer]).await.unwrap();
+
+    let target = env.create_token_account(&mint, &payer.pubkey(), 0);
+    let withdraw = |amount| builders::escrow_withdraw(&payer.pubkey(), &target, &payer.pubkey(), &mint, amount);
+
+    // 600 back the stream, only the rest leaves without notice
+    env.process(withdraw(400), &[&payer]).await.unwrap();
+    assert_instruction_error(env.process(withdraw(100), &[&payer]).await, InstructionError::InsufficientFunds);
+
+    env.process(builders::escrow_withdrawal_notice(&payer.pubkey(), &mint, 200), &[&payer]).await.unwrap();
+    let state: EscrowState = env.read(&builders::escrow_state(&payer.pubkey(), &mint), DepinAccountType::EscrowState).await;
+    assert_eq!((state.pending_withdrawal, state.unlock_period), (200, PERIOD + EscrowState::NOTICE_PERIODS));
+
+    env.warp_to_period(PERIOD + EscrowState::NOTICE_PERIODS - 1).await;
+    assert_instruction_error(env.process(withdraw(200), &[&payer]).await, InstructionError::InsufficientFunds);
+
+    env.warp_to_period(PERIOD + EscrowState::NOTICE_PERIODS).await;
+    assert_instruction_error(env.process(withdraw(201), &[&payer]).await, InstructionError::InsufficientFunds);
+    env.process(withdraw(200), &[&payer]).await.unwrap();
+
+    let state: EscrowState = env.read(&builders::escrow_state(&payer.pubkey(), &mint), DepinAccountType::EscrowState).await;
+    assert_eq!(state.pending_withdrawal, 0);
+    assert_eq!(env.token_balance(&target).await, 600);
+}
+
+#[tokio::test]
+async fn escrow_pause_stops_payment_streams_and_withdrawal_notices() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let admin = env.payer();
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+    let payer = env.funded_keypair();
+    env.fund_escrow(&payer, &mint, 1_000).await;
+
+    let mut tree = LicenseTree::new();
+    let (_, worker) = env.activate_worker(&mut tree).await;
+    let terms = StreamTerms {
+        stream_id: 1,
+        rate_per_period: 100,
+        start_period: PERIOD,
+        end_period: PERIOD + 2,
+    };
+    let open = builders::open_payment_stream(&payer.pubkey(), &worker, &mint, &terms);
+    let notice = builders::escrow_withdrawal_notice(&payer.pubkey(), &mint, 200);
+
+    env.process(builders::set_pause_flags(&admin.pubkey(), PauseState::ESCROW), &[]).await.unwrap();
+    assert_instruction_error(env.process(open, &[&payer]).await, InstructionError::InvalidArgument);
+    assert_instruction_error(env.process(notice, &[&payer]).await, InstructionError::InvalidArgument);
+}
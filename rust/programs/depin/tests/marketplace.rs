@@ -0,0 +1,572 @@
+mod common;
+
+use common::{
+    assert_instruction_error,
+    builders::{self, OfferTerms, ProofArgs, StreamTerms},
+    license_tree::{License, LicenseTree},
+    mock_compression::INVALID_PROOF,
+    TestEnv, CHECKER_COUNT,
+};
+use shared::{
+    features::{
+        escrow::accounts::EscrowState,
+        infra_offer::accounts::{InfraOffer, InfraOfferStatus, SlaTerms},
+        lease::accounts::{Lease, LeaseStatus},
+        payment_stream::accounts::{PaymentStream, PaymentStreamStatus},
+        worker::accounts::WorkerMetadata,
+    },
+    types::account::DepinAccountType,
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const PERIOD: u16 = 40;
+const PRICE: u64 = 1_000;
+
+const SLA: SlaTerms = SlaTerms {
+    min_uptime: 95,
+    max_latency: 50,
+    penalty_bps: 2_500,
+};
+
+struct Market {
+    env: TestEnv,
+    mint: Pubkey,
+    provider: Keypair,
+    tree: LicenseTree,
+    worker: License,
+    buyer: Keypair,
+}
+
+/// An activated worker and a buyer holding 10 periods worth of escrow, at the start of PERIOD
+async fn market() -> Market {
+    let mut env = TestEnv::start(PERIOD - 1).await;
+    env.init_network().await;
+    env.set_checker_count(PERIOD, CHECKER_COUNT).await;
+    env.warp_to_period(PERIOD).await;
+
+    let mint = env.create_mint();
+    env.allow_mint(&mint).await;
+
+    let mut tree = LicenseTree::new();
+    let (provider, worker) = env.activate_worker(&mut tree).await;
+
+    let buyer = env.funded_keypair();
+    env.fund_escrow(&buyer, &mint, 10 * PRICE).await;
+
+    Market { env, mint, provider, tree, worker, buyer }
+}
+
+impl Market {
+    fn terms(&self, sla: Option<SlaTerms>) -> OfferTerms {
+        OfferTerms {
+            price_mint: self.mint,
+            price_per_period: PRICE,
+            sla,
+        }
+    }
+
+    async fn register_offer(&mut self, sla: Option<SlaTerms>) -> Pubkey {
+        let instruction = builders::register_infra_offer(&self.provider.pubkey(), &self.worker, 0, &self.terms(sla));
+        self.env.process(instruction, &[&self.provider]).await.unwrap();
+        builders::infra_offer(&self.worker.asset_id, 0)
+    }
+
+    /// Opens lease 1 of the buyer on `offer`, returns the lease address
+    async fn open_lease(&mut self, offer: &Pubkey, periods: u16) -> Pubkey {
+        let instruction = builders::open_lease(&self.buyer.pubkey(), offer, &self.worker, &self.mint, 1, PERIOD, periods);
+        self.env.process(instruction, &[&self.buyer]).await.unwrap();
+        builders::lease(offer, &self.buyer.pubkey(), 1)
+    }
+
+    /// Submits the worker proof for the period before the current one
+    async fn submit_proof(&mut self, period: u16, uptime: u32) {
+        self.env.warp_to_period(period + 1).await;
+        let args = ProofArgs { period, checkers: [0; 8], uptime, latency: 10 };
+        self.env
+            .process(builders::submit_worker_proof(&self.provider.pubkey(), &self.worker, &args), &[&self.provider])
+            .await
+            .unwrap();
+    }
+
+    async fn escrow_balance(&mut self) -> u64 {
+        let escrow = builders::escrow_token(&self.buyer.pubkey(), &self.mint);
+        self.env.token_balance(&escrow).await
+    }
+}
+
+#[tokio::test]
+async fn offers_are_managed_by_their_provider() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let provider = market.provider.insecure_clone();
+
+    let stored: InfraOffer = market.env.read(&offer, DepinAccountType::InfraOffer).await;
+    assert_eq!((stored.provider, stored.worker_license), (provider.pubkey(), market.worker.asset_id));
+    assert_eq!((stored.price_mint, stored.price_per_period, stored.status), (market.mint, PRICE, InfraOfferStatus::Active));
+
+    let result = market.env.process(builders::register_infra_offer(&provider.pubkey(), &market.worker, 0, &market.terms(None)), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::AccountAlreadyInitialized);
+
+    let terms = market.terms(Some(SLA));
+    market.env.process(builders::update_infra_offer(&provider.pubkey(), &offer, &terms, "us-east"), &[&provider]).await.unwrap();
+    let stored: InfraOffer = market.env.read(&offer, DepinAccountType::InfraOffer).await;
+    assert_eq!((stored.region.as_str(), stored.sla), ("us-east", Some(SLA)));
+
+    let result = market.env.process(builders::update_infra_offer(&provider.pubkey(), &offer, &terms, "US East"), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::InvalidInstructionData);
+
+    // Offers are only priced in mints the escrow accepts
+    let unlisted = OfferTerms { price_mint: market.env.create_mint(), ..terms };
+    let result = market.env.process(builders::update_infra_offer(&provider.pubkey(), &offer, &unlisted, "us-east"), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+    let result = market.env.process(builders::register_infra_offer(&provider.pubkey(), &market.worker, 1, &unlisted), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let buyer = market.buyer.insecure_clone();
+    let result = market.env.process(builders::close_infra_offer(&buyer.pubkey(), &offer), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+
+    market
+        .env
+        .process(builders::set_infra_offer_status(&provider.pubkey(), &offer, &market.worker, InfraOfferStatus::Paused), &[&provider])
+        .await
+        .unwrap();
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &market.worker, &market.mint, 1, PERIOD, 1), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::InvalidAccountData);
+
+    market.env.process(builders::close_infra_offer(&provider.pubkey(), &offer), &[&provider]).await.unwrap();
+    assert!(market.env.account(&offer).await.is_none());
+}
+
+#[tokio::test]
+async fn register_offer_needs_an_activated_worker() {
+    let mut market = market().await;
+    let mut tree = LicenseTree::new();
+    let owner = market.env.funded_keypair();
+    let index = tree.mint(owner.pubkey());
+    market.env.sync_tree(&tree);
+    let license = tree.license(index);
+
+    let instruction = builders::register_infra_offer(&owner.pubkey(), &license, 0, &market.terms(None));
+    assert_instruction_error(market.env.process(instruction, &[&owner]).await, InstructionError::UninitializedAccount);
+}
+
+#[tokio::test]
+async fn lease_payments_follow_worker_proofs() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let lease = market.open_lease(&offer, 3).await;
+    let provider = market.provider.insecure_clone();
+    let provider_token = market.env.create_token_account(&market.mint, &provider.pubkey(), 0);
+
+    assert_eq!(market.escrow_balance().await, 7 * PRICE);
+    assert_eq!(market.env.token_balance(&builders::lease_vault(&lease)).await, 3 * PRICE);
+
+    let (worker, mint) = (market.worker.clone(), market.mint);
+    let claim = |period| builders::claim_lease_payment(&provider.pubkey(), &lease, &provider_token, &worker, &mint, period);
+
+    // Nothing is payable before the worker proved the period
+    assert_instruction_error(market.env.process(claim(PERIOD), &[&provider]).await, InstructionError::UninitializedAccount);
+
+    market.submit_proof(PERIOD, 100).await;
+    market.env.process(claim(PERIOD), &[&provider]).await.unwrap();
+    assert_eq!(market.env.token_balance(&provider_token).await, PRICE);
+
+    assert_instruction_error(market.env.process(claim(PERIOD), &[&provider]).await, InstructionError::InvalidArgument);
+    assert_instruction_error(market.env.process(claim(PERIOD + 3), &[&provider]).await, InstructionError::InvalidArgument);
+
+    let buyer = market.buyer.insecure_clone();
+    let foreign = builders::claim_lease_payment(&buyer.pubkey(), &lease, &provider_token, &market.worker, &market.mint, PERIOD + 1);
+    assert_instruction_error(market.env.process(foreign, &[&buyer]).await, InstructionError::InvalidArgument);
+
+    let stored: Lease = market.env.read(&lease, DepinAccountType::Lease).await;
+    assert!(stored.is_settled(PERIOD) && !stored.is_settled(PERIOD + 1));
+}
+
+#[tokio::test]
+async fn leases_follow_license_transfers() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let lease = market.open_lease(&offer, 2).await;
+    let previous_owner = market.provider.insecure_clone();
+    market.submit_proof(PERIOD, 100).await;
+
+    let new_owner = market.env.funded_keypair();
+    market.tree.transfer(market.worker.index, new_owner.pubkey());
+    market.env.sync_tree(&market.tree);
+    let transferred = market.tree.license(market.worker.index);
+
+    // The offer stays with the previous owner, who can no longer sell it
+    let buyer = market.buyer.insecure_clone();
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &market.worker, &market.mint, 2, PERIOD + 1, 1), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::Custom(INVALID_PROOF));
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &transferred, &market.mint, 2, PERIOD + 1, 1), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+
+    // Lease payments go to the current owner
+    let stale_token = market.env.create_token_account(&market.mint, &previous_owner.pubkey(), 0);
+    let owner_token = market.env.create_token_account(&market.mint, &new_owner.pubkey(), 0);
+    let stale = builders::claim_lease_payment(&previous_owner.pubkey(), &lease, &stale_token, &market.worker, &market.mint, PERIOD);
+    assert_instruction_error(market.env.process(stale, &[&previous_owner]).await, InstructionError::Custom(INVALID_PROOF));
+    let stale = builders::claim_lease_payment(&previous_owner.pubkey(), &lease, &stale_token, &transferred, &market.mint, PERIOD);
+    assert_instruction_error(market.env.process(stale, &[&previous_owner]).await, InstructionError::InvalidArgument);
+
+    // The new owner claims once it migrated the metadata
+    let claim = builders::claim_lease_payment(&new_owner.pubkey(), &lease, &owner_token, &transferred, &market.mint, PERIOD);
+    assert_instruction_error(market.env.process(claim.clone(), &[&new_owner]).await, InstructionError::InvalidArgument);
+    market.env
+        .process(builders::migrate_worker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner])
+        .await
+        .unwrap();
+    market.env.process(claim, &[&new_owner]).await.unwrap();
+    assert_eq!(market.env.token_balance(&owner_token).await, PRICE);
+    assert_eq!(market.env.token_balance(&stale_token).await, 0);
+}
+
+#[tokio::test]
+async fn lease_payments_go_to_the_worker_reward_beneficiary() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let lease = market.open_lease(&offer, 1).await;
+    let provider = market.provider.insecure_clone();
+    let beneficiary = market.env.funded_keypair();
+    let owner_token = market.env.create_token_account(&market.mint, &provider.pubkey(), 0);
+    let beneficiary_token = market.env.create_token_account(&market.mint, &beneficiary.pubkey(), 0);
+
+    market.env
+        .process(builders::set_worker_reward_beneficiary(&provider.pubkey(), &market.worker, Some(beneficiary.pubkey())), &[&provider])
+        .await
+        .unwrap();
+    market.submit_proof(PERIOD, 100).await;
+
+    let (worker, mint) = (market.worker.clone(), market.mint);
+    let claim = |recipient| builders::claim_lease_payment(&provider.pubkey(), &lease, &recipient, &worker, &mint, PERIOD);
+    assert_instruction_error(market.env.process(claim(owner_token), &[&provider]).await, InstructionError::IllegalOwner);
+    market.env.process(claim(beneficiary_token), &[&provider]).await.unwrap();
+    assert_eq!(market.env.token_balance(&beneficiary_token).await, PRICE);
+    assert_eq!(market.env.token_balance(&owner_token).await, 0);
+}
+
+#[tokio::test]
+async fn offers_of_a_previous_license_owner_can_be_taken_over() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let previous_owner = market.provider.insecure_clone();
+
+    let new_owner = market.env.funded_keypair();
+    market.tree.transfer(market.worker.index, new_owner.pubkey());
+    market.env.sync_tree(&market.tree);
+    let transferred = market.tree.license(market.worker.index);
+    market.env
+        .process(builders::migrate_worker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner])
+        .await
+        .unwrap();
+
+    // The previous owner cannot re-register with its stale leaf
+    let terms = market.terms(Some(SLA));
+    let result = market.env.process(builders::register_infra_offer(&previous_owner.pubkey(), &market.worker, 0, &terms), &[&previous_owner]).await;
+    assert_instruction_error(result, InstructionError::Custom(INVALID_PROOF));
+
+    market.env.process(builders::register_infra_offer(&new_owner.pubkey(), &transferred, 0, &terms), &[&new_owner]).await.unwrap();
+    let stored: InfraOffer = market.env.read(&offer, DepinAccountType::InfraOffer).await;
+    assert_eq!((stored.provider, stored.worker_license, stored.sla), (new_owner.pubkey(), transferred.asset_id, Some(SLA)));
+
+    let result = market.env.process(builders::register_infra_offer(&new_owner.pubkey(), &transferred, 0, &terms), &[&new_owner]).await;
+    assert_instruction_error(result, InstructionError::AccountAlreadyInitialized);
+    let result = market.env.process(builders::close_infra_offer(&previous_owner.pubkey(), &offer), &[&previous_owner]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+
+    market.env.process(builders::close_infra_offer(&new_owner.pubkey(), &offer), &[&new_owner]).await.unwrap();
+    assert!(market.env.account(&offer).await.is_none());
+}
+
+#[tokio::test]
+async fn open_lease_needs_an_active_provider_worker() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let buyer = market.buyer.insecure_clone();
+
+    market.env.suspend_worker(&market.worker).await;
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &market.worker, &market.mint, 1, PERIOD, 1), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn enforce_lease_sla_refunds_breaches_and_missing_proofs() {
+    let mut market = market().await;
+    let offer = market.register_offer(Some(SLA)).await;
+    let lease = market.open_lease(&offer, 3).await;
+    let provider = market.provider.insecure_clone();
+    let provider_token = market.env.create_token_account(&market.mint, &provider.pubkey(), 0);
+    let cranker = market.env.funded_keypair();
+    let (buyer, worker, mint) = (market.buyer.pubkey(), market.worker.clone(), market.mint);
+
+    let enforce = |period| builders::enforce_lease_sla(&cranker.pubkey(), &lease, &buyer, &worker.asset_id, &mint, period);
+    let claim = |period| builders::claim_lease_payment(&provider.pubkey(), &lease, &provider_token, &worker, &mint, period);
+
+    // A proof meeting the SLA leaves nothing to enforce
+    market.submit_proof(PERIOD, 99).await;
+    assert_instruction_error(market.env.process(enforce(PERIOD), &[&cranker]).await, InstructionError::InvalidArgument);
+
+    // The proof window of the next period is still open
+    assert_instruction_error(market.env.process(enforce(PERIOD + 1), &[&cranker]).await, InstructionError::InvalidArgument);
+
+    market.submit_proof(PERIOD + 1, 80).await;
+    assert_instruction_error(market.env.process(claim(PERIOD + 1), &[&provider]).await, InstructionError::InvalidAccountData);
+
+    let penalty = SLA.penalty(PRICE);
+    market.env.process(enforce(PERIOD + 1), &[&cranker]).await.unwrap();
+    assert_eq!(market.escrow_balance().await, 7 * PRICE + penalty);
+
+    market.env.process(claim(PERIOD + 1), &[&provider]).await.unwrap();
+    assert_eq!(market.env.token_balance(&provider_token).await, PRICE - penalty);
+
+    // No proof ever arrived for the last period, it is refunded in full once its window closed
+    market.env.warp_to_period(PERIOD + 4).await;
+    market.env.process(enforce(PERIOD + 2), &[&cranker]).await.unwrap();
+    assert_eq!(market.escrow_balance().await, 8 * PRICE + penalty);
+    assert_instruction_error(market.env.process(enforce(PERIOD + 2), &[&cranker]).await, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn cancel_lease_refunds_the_periods_not_started() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let lease = market.open_lease(&offer, 4).await;
+    let buyer = market.buyer.insecure_clone();
+    let provider = market.provider.insecure_clone();
+
+    let result = market.env.process(builders::cancel_lease(&provider.pubkey(), &lease, &market.mint), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+
+    market.env.process(builders::cancel_lease(&buyer.pubkey(), &lease, &market.mint), &[&buyer]).await.unwrap();
+    assert_eq!(market.escrow_balance().await, 9 * PRICE);
+    assert_eq!(market.env.token_balance(&builders::lease_vault(&lease)).await, PRICE);
+
+    let stored: Lease = market.env.read(&lease, DepinAccountType::Lease).await;
+    assert_eq!(stored.status, LeaseStatus::Cancelled);
+
+    let result = market.env.process(builders::cancel_lease(&buyer.pubkey(), &lease, &market.mint), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::InvalidAccountData);
+}
+
+#[tokio::test]
+async fn open_lease_cannot_spend_more_than_the_escrow() {
+    let mut market = market().await;
+    let offer = market.register_offer(None).await;
+    let buyer = market.buyer.insecure_clone();
+
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &market.worker, &market.mint, 1, PERIOD, 11), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::InsufficientFunds);
+
+    let result = market.env.process(builders::open_lease(&buyer.pubkey(), &offer, &market.worker, &market.mint, 1, PERIOD - 1, 1), &[&buyer]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn payment_streams_pay_proven_periods_and_release_the_rest() {
+    let mut market = market().await;
+    let payer = market.buyer.insecure_clone();
+    let provider = market.provider.insecure_clone();
+    let recipient = market.env.create_token_account(&market.mint, &provider.pubkey(), 0);
+    let terms = StreamTerms {
+        stream_id: 7,
+        rate_per_period: 500,
+        start_period: PERIOD,
+        end_period: PERIOD + 4,
+    };
+
+    let result = market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &StreamTerms { rate_per_period: 3_000, ..terms }), &[&payer]).await;
+    assert_instruction_error(result, InstructionError::InsufficientFunds);
+    let result = market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &StreamTerms { rate_per_period: u64::MAX, ..terms }), &[&payer]).await;
+    assert_instruction_error(result, InstructionError::ArithmeticOverflow);
+
+    market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &terms), &[&payer]).await.unwrap();
+    let stream = builders::payment_stream(&payer.pubkey(), &market.worker.asset_id, terms.stream_id);
+    let escrow_state = builders::escrow_state(&payer.pubkey(), &market.mint);
+
+    let cranker = market.env.funded_keypair();
+    let (worker, mint) = (market.worker.clone(), market.mint);
+    let crank = |period| builders::crank_payment_stream(&cranker.pubkey(), &stream, &payer.pubkey(), &worker, &recipient, &mint, period);
+
+    // The current period is not over yet
+    assert_instruction_error(market.env.process(crank(PERIOD), &[&cranker]).await, InstructionError::InvalidArgument);
+
+    market.submit_proof(PERIOD, 100).await;
+    market.env.process(crank(PERIOD), &[&cranker]).await.unwrap();
+    assert_eq!(market.env.token_balance(&recipient).await, 500);
+
+    // Without a proof the next period waits for its window to close, then it is skipped unpaid
+    market.env.warp_to_period(PERIOD + 2).await;
+    assert_instruction_error(market.env.process(crank(PERIOD + 1), &[&cranker]).await, InstructionError::InvalidArgument);
+    market.env.warp_to_period(PERIOD + 3).await;
+    market.env.process(crank(PERIOD + 1), &[&cranker]).await.unwrap();
+    assert_eq!(market.env.token_balance(&recipient).await, 500);
+
+    let stored: PaymentStream = market.env.read(&stream, DepinAccountType::PaymentStream).await;
+    assert_eq!((stored.next_period, stored.status), (PERIOD + 2, PaymentStreamStatus::Active));
+    let state: EscrowState = market.env.read(&escrow_state, DepinAccountType::EscrowState).await;
+    assert_eq!(state.encumbered, 2 * 500);
+
+    let result = market.env.process(builders::close_payment_stream(&provider.pubkey(), &stream, &worker, &recipient, &mint, PERIOD + 2..PERIOD + 3), &[&provider]).await;
+    assert_instruction_error(result, InstructionError::IllegalOwner);
+
+    // Once the stream ended nothing is left to give notice for, closing settles the last periods
+    market.submit_proof(PERIOD + 3, 100).await;
+    market.env
+        .process(builders::close_payment_stream(&payer.pubkey(), &stream, &worker, &recipient, &mint, PERIOD + 2..PERIOD + 4), &[&payer])
+        .await
+        .unwrap();
+    assert!(market.env.account(&stream).await.is_none());
+    assert_eq!(market.env.token_balance(&recipient).await, 1_000);
+    let state: EscrowState = market.env.read(&escrow_state, DepinAccountType::EscrowState).await;
+    assert_eq!(state.encumbered, 0);
+    assert_eq!(market.escrow_balance().await, 10 * PRICE - 1_000);
+}
+
+#[tokio::test]
+async fn closing_a_payment_stream_settles_finished_periods_first() {
+    let mut market = market().await;
+    let payer = market.buyer.insecure_clone();
+    let recipient = market.env.create_token_account(&market.mint, &market.provider.pubkey(), 0);
+    let terms = StreamTerms {
+        stream_id: 9,
+        rate_per_period: 500,
+        start_period: PERIOD,
+        end_period: PERIOD + 6,
+    };
+
+    market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &terms), &[&payer]).await.unwrap();
+    let stream = builders::payment_stream(&payer.pubkey(), &market.worker.asset_id, terms.stream_id);
+    let escrow_state = builders::escrow_state(&payer.pubkey(), &market.mint);
+    let (worker, mint) = (market.worker.clone(), market.mint);
+    let close = |unsettled| builders::close_payment_stream(&payer.pubkey(), &stream, &worker, &recipient, &mint, unsettled);
+
+    // The periods not started yet stay committed until a withdrawal notice for them matured
+    market.submit_proof(PERIOD, 100).await;
+    assert_instruction_error(market.env.process(close(PERIOD..PERIOD + 1), &[&payer]).await, InstructionError::InsufficientFunds);
+    market.env.process(builders::escrow_withdrawal_notice(&payer.pubkey(), &market.mint, 5 * 500), &[&payer]).await.unwrap();
+
+    // PERIOD + 1 goes unproven, PERIOD and PERIOD + 2 were never cranked
+    market.submit_proof(PERIOD + 2, 100).await;
+    market.env.process(close(PERIOD..PERIOD + 3), &[&payer]).await.unwrap();
+
+    assert!(market.env.account(&stream).await.is_none());
+    assert_eq!(market.env.token_balance(&recipient).await, 2 * 500);
+    let state: EscrowState = market.env.read(&escrow_state, DepinAccountType::EscrowState).await;
+    assert_eq!(state.encumbered, 0);
+    assert_eq!(market.escrow_balance().await, 10 * PRICE - 2 * 500);
+}
+
+#[tokio::test]
+async fn closing_payment_streams_spends_the_withdrawal_notice() {
+    let mut market = market().await;
+    let payer = market.buyer.insecure_clone();
+    let recipient = market.env.create_token_account(&market.mint, &market.provider.pubkey(), 0);
+    let start_period = PERIOD + EscrowState::NOTICE_PERIODS + 1;
+    let (worker, mint) = (market.worker.clone(), market.mint);
+
+    let mut streams = Vec::new();
+    for stream_id in [1, 2] {
+        let terms = StreamTerms { stream_id, rate_per_period: 100, start_period, end_period: start_period + 5 };
+        market.env.process(builders::open_payment_stream(&payer.pubkey(), &worker, &mint, &terms), &[&payer]).await.unwrap();
+        streams.push(builders::payment_stream(&payer.pubkey(), &worker.asset_id, stream_id));
+    }
+
+    // The notice covers a single stream, neither stream has started when it matures
+    market.env.process(builders::escrow_withdrawal_notice(&payer.pubkey(), &mint, 500), &[&payer]).await.unwrap();
+    market.env.warp_to_period(PERIOD + EscrowState::NOTICE_PERIODS).await;
+    let close = |stream| builders::close_payment_stream(&payer.pubkey(), stream, &worker, &recipient, &mint, PERIOD..PERIOD);
+
+    market.env.process(close(&streams[0]), &[&payer]).await.unwrap();
+    let state: EscrowState = market.env.read(&builders::escrow_state(&payer.pubkey(), &mint), DepinAccountType::EscrowState).await;
+    assert_eq!((state.encumbered, state.pending_withdrawal), (500, 0));
+
+    assert_instruction_error(market.env.process(close(&streams[1]), &[&payer]).await, InstructionError::InsufficientFunds);
+    let target = market.env.create_token_account(&mint, &payer.pubkey(), 0);
+    let withdraw = builders::escrow_withdraw(&payer.pubkey(), &target, &payer.pubkey(), &mint, 10 * PRICE);
+    assert_instruction_error(market.env.process(withdraw, &[&payer]).await, InstructionError::InsufficientFunds);
+}
+
+#[tokio::test]
+async fn payment_streams_pay_the_worker_reward_beneficiary() {
+    let mut market = market().await;
+    let payer = market.buyer.insecure_clone();
+    let provider = market.provider.insecure_clone();
+    let beneficiary = market.env.funded_keypair();
+    let owner_account = market.env.create_token_account(&market.mint, &provider.pubkey(), 0);
+    let beneficiary_account = market.env.create_token_account(&market.mint, &beneficiary.pubkey(), 0);
+    let terms = StreamTerms {
+        stream_id: 3,
+        rate_per_period: 500,
+        start_period: PERIOD,
+        end_period: PERIOD + 2,
+    };
+
+    market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &terms), &[&payer]).await.unwrap();
+    let stream = builders::payment_stream(&payer.pubkey(), &market.worker.asset_id, terms.stream_id);
+
+    let result = market.env.process(builders::set_worker_reward_beneficiary(&beneficiary.pubkey(), &market.worker, Some(beneficiary.pubkey())), &[&beneficiary]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+    market.env
+        .process(builders::set_worker_reward_beneficiary(&provider.pubkey(), &market.worker, Some(beneficiary.pubkey())), &[&provider])
+        .await
+        .unwrap();
+
+    let cranker = market.env.funded_keypair();
+    let (worker, mint) = (market.worker.clone(), market.mint);
+    let crank = |recipient| builders::crank_payment_stream(&cranker.pubkey(), &stream, &payer.pubkey(), &worker, &recipient, &mint, PERIOD);
+
+    market.submit_proof(PERIOD, 100).await;
+    assert_instruction_error(market.env.process(crank(owner_account), &[&cranker]).await, InstructionError::IllegalOwner);
+    market.env.process(crank(beneficiary_account), &[&cranker]).await.unwrap();
+    assert_eq!(market.env.token_balance(&beneficiary_account).await, 500);
+    assert_eq!(market.env.token_balance(&owner_account).await, 0);
+}
+
+#[tokio::test]
+async fn payment_streams_follow_license_transfers() {
+    let mut market = market().await;
+    let payer = market.buyer.insecure_clone();
+    let previous_owner = market.provider.insecure_clone();
+    let terms = StreamTerms {
+        stream_id: 5,
+        rate_per_period: 500,
+        start_period: PERIOD,
+        end_period: PERIOD + 2,
+    };
+
+    market.env.process(builders::open_payment_stream(&payer.pubkey(), &market.worker, &market.mint, &terms), &[&payer]).await.unwrap();
+    let stream = builders::payment_stream(&payer.pubkey(), &market.worker.asset_id, terms.stream_id);
+    market.submit_proof(PERIOD, 100).await;
+
+    let new_owner = market.env.funded_keypair();
+    market.tree.transfer(market.worker.index, new_owner.pubkey());
+    market.env.sync_tree(&market.tree);
+    let transferred = market.tree.license(market.worker.index);
+
+    let stale_recipient = market.env.create_token_account(&market.mint, &previous_owner.pubkey(), 0);
+    let recipient = market.env.create_token_account(&market.mint, &new_owner.pubkey(), 0);
+    let cranker = market.env.funded_keypair();
+    let (payer_key, mint) = (payer.pubkey(), market.mint);
+    let crank = |license: &License, recipient| builders::crank_payment_stream(&cranker.pubkey(), &stream, &payer_key, license, &recipient, &mint, PERIOD);
+
+    // The previous owner's leaf is no longer in the tree
+    let result = market.env.process(crank(&market.worker, stale_recipient), &[&cranker]).await;
+    assert_instruction_error(result, InstructionError::Custom(INVALID_PROOF));
+
+    // The current leaf does not vouch for the metadata left behind by the previous owner
+    let mut instruction = crank(&transferred, stale_recipient);
+    instruction.accounts[3].pubkey = WorkerMetadata::find_pda(&depin::ID, &market.worker.asset_id, &previous_owner.pubkey()).0;
+    assert_instruction_error(market.env.process(instruction, &[&cranker]).await, InstructionError::InvalidArgument);
+
+    // Payments resume once the new owner migrated the metadata
+    assert_instruction_error(market.env.process(crank(&transferred, recipient), &[&cranker]).await, InstructionError::InvalidArgument);
+    market.env
+        .process(builders::migrate_worker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner])
+        .await
+        .unwrap();
+    market.env.process(crank(&transferred, recipient), &[&cranker]).await.unwrap();
+    assert_eq!(market.env.token_balance(&recipient).await, 500);
+    assert_eq!(market.env.token_balance(&stale_recipient).await, 0);
+}
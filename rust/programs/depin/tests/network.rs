@@ -0,0 +1,177 @@
+mod common;
+
+use common::{assert_instruction_error, builders, license_tree::LicenseTree, TestEnv};
+use shared::{
+    features::{
+        global::accounts::{AllowedMints, BMBState, PauseState},
+        rewards::accounts::GlobalRewards,
+        treasury::accounts::{TreasuryConfig, TreasuryState},
+    },
+    types::account::DepinAccountType,
+    utils::bmb::SECONDS_PER_PERIOD,
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, signer::Signer};
+
+#[tokio::test]
+async fn init_network_grows_global_rewards_and_creates_the_treasury() {
+    let mut env = TestEnv::start(10).await;
+    let caller = env.payer();
+
+    env.process(builders::init_network(&caller.pubkey()), &[]).await.unwrap();
+    let (global_rewards, _) = GlobalRewards::find_pda(&depin::ID);
+    assert_eq!(env.account(&global_rewards).await.unwrap().data.len(), 8);
+
+    env.process(builders::init_network(&caller.pubkey()), &[]).await.unwrap();
+    assert_eq!(env.account(&global_rewards).await.unwrap().data.len(), 8 + 10_240);
+
+    env.init_network().await;
+
+    let (treasury_state, _) = TreasuryState::find_pda(&depin::ID);
+    let state: TreasuryState = env.read(&treasury_state, DepinAccountType::TreasuryState).await;
+    assert_eq!(state.locked_balance, 0);
+
+    let (treasury_config, _) = TreasuryConfig::find_pda(&depin::ID);
+    let config: TreasuryConfig = env.read(&treasury_config, DepinAccountType::TreasuryConfig).await;
+    assert_eq!(config, TreasuryConfig::new());
+}
+
+#[tokio::test]
+async fn init_network_requires_a_signer() {
+    let mut env = TestEnv::start(10).await;
+    let caller = Pubkey::new_unique();
+
+    let mut instruction = builders::init_network(&caller);
+    instruction.accounts[0].is_signer = false;
+
+    assert_instruction_error(env.process(instruction, &[]).await, InstructionError::MissingRequiredSignature);
+}
+
+#[tokio::test]
+async fn checker_count_applies_from_its_period_onwards() {
+    let mut env = TestEnv::start(10).await;
+
+    env.set_checker_count(11, 4).await;
+    env.set_checker_count(13, 8).await;
+
+    let (bmb_state, _) = BMBState::find_pda(&depin::ID);
+    let state: BMBState = env.read(&bmb_state, DepinAccountType::BMBState).await;
+    assert_eq!(state.get_checker_count_for_period(10), None);
+    assert_eq!(state.get_checker_count_for_period(12), Some(4));
+    assert_eq!(state.get_checker_count_for_period(13), Some(8));
+    assert_eq!(state.get_checker_count_for_period(40), Some(8));
+}
+
+#[tokio::test]
+async fn checker_count_cannot_be_set_for_a_started_or_earlier_period() {
+    let mut env = TestEnv::start(10).await;
+    let admin = env.payer();
+
+    let result = env.process(builders::activate_checker_licenses(&admin.pubkey(), 10, 4), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.set_checker_count(12, 4).await;
+    let result = env.process(builders::activate_checker_licenses(&admin.pubkey(), 11, 4), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn allowed_mints_can_be_updated_and_removed() {
+    let mut env = TestEnv::start(10).await;
+    let admin = env.payer();
+    let mint = env.create_mint();
+    let (allowed_mints, _) = AllowedMints::find_pda(&depin::ID);
+
+    env.allow_mint(&mint).await;
+    env.process(builders::set_allowed_mint(&admin.pubkey(), &mint, 500, true), &[]).await.unwrap();
+
+    let listed: AllowedMints = env.read(&allowed_mints, DepinAccountType::AllowedMints).await;
+    let entry = listed.mints.iter().find(|entry| entry.mint == mint).unwrap();
+    assert_eq!((entry.max_deposit, entry.paused), (500, true));
+    assert_eq!(listed.mints.len(), 1);
+
+    env.process(builders::remove_allowed_mint(&admin.pubkey(), &mint), &[]).await.unwrap();
+    let listed: AllowedMints = env.read(&allowed_mints, DepinAccountType::AllowedMints).await;
+    assert!(listed.mints.is_empty());
+
+    let result = env.process(builders::remove_allowed_mint(&admin.pubkey(), &mint), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn instant_payout_haircut_is_set_by_the_admin() {
+    let mut env = TestEnv::start(10).await;
+    let admin = env.payer();
+    env.init_network().await;
+    let (treasury_config, _) = TreasuryConfig::find_pda(&depin::ID);
+
+    env.process(builders::set_instant_payout_haircut(&admin.pubkey(), 2_000), &[]).await.unwrap();
+    let config: TreasuryConfig = env.read(&treasury_config, DepinAccountType::TreasuryConfig).await;
+    assert_eq!((config.checker_rewards_lock_days, config.instant_payout_haircut_bps), (365, 2_000));
+
+    let result = env.process(builders::set_instant_payout_haircut(&admin.pubkey(), 10_000), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidInstructionData);
+}
+
+#[tokio::test]
+async fn migrate_account_only_accepts_outdated_program_accounts() {
+    let mut env = TestEnv::start(10).await;
+    let caller = env.funded_keypair();
+    env.init_network().await;
+
+    // Accounts created by this program already have the current layout, so there is nothing to migrate
+    let (treasury_config, _) = TreasuryConfig::find_pda(&depin::ID);
+    let result = env.process(builders::migrate_account(&caller.pubkey(), &treasury_config), &[&caller]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let (global_rewards, _) = GlobalRewards::find_pda(&depin::ID);
+    let result = env.process(builders::migrate_account(&caller.pubkey(), &global_rewards), &[&caller]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let result = env.process(builders::migrate_account(&caller.pubkey(), &caller.pubkey()), &[&caller]).await;
+    assert_instruction_error(result, InstructionError::IncorrectProgramId);
+
+    let config: TreasuryConfig = env.read(&treasury_config, DepinAccountType::TreasuryConfig).await;
+    assert_eq!(config.checker_rewards_lock_days, 365);
+}
+
+#[tokio::test]
+async fn paused_instructions_are_rejected_until_the_unpause_delay_passed() {
+    let mut env = TestEnv::start(10).await;
+    let admin = env.payer();
+    env.init_network().await;
+
+    // Guarded instructions work before PauseState exists, but only with its PDA as the last account
+    let mut tree = LicenseTree::new();
+    env.activate_worker(&mut tree).await;
+
+    let owner = env.funded_keypair();
+    let index = tree.mint(owner.pubkey());
+    env.sync_tree(&tree);
+    let license = tree.license(index);
+    let activate = builders::activate_worker(&owner.pubkey(), &license, &owner.pubkey(), "https://worker.example");
+
+    let mut forged = activate.clone();
+    forged.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+    assert_instruction_error(env.process(forged, &[&owner]).await, InstructionError::InvalidArgument);
+
+    let flags = PauseState::ACTIVATION | PauseState::PAYOUTS;
+    env.process(builders::set_pause_flags(&admin.pubkey(), flags), &[]).await.unwrap();
+    assert_instruction_error(env.process(activate.clone(), &[&owner]).await, InstructionError::InvalidArgument);
+
+    // Unpausing is scheduled first and can only be applied once the delay passed
+    env.process(builders::set_pause_flags(&admin.pubkey(), PauseState::PAYOUTS), &[]).await.unwrap();
+    let pause_state = builders::pause_state();
+    let state: PauseState = env.read(&pause_state, DepinAccountType::PauseState).await;
+    assert_eq!((state.flags, state.pending_flags), (flags, PauseState::PAYOUTS));
+    assert_instruction_error(env.process(activate.clone(), &[&owner]).await, InstructionError::InvalidArgument);
+
+    let result = env.process(builders::set_pause_flags(&admin.pubkey(), PauseState::PAYOUTS), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.warp_to_period(10 + (PauseState::UNPAUSE_DELAY / SECONDS_PER_PERIOD) as u16 + 1).await;
+    env.process(builders::set_pause_flags(&admin.pubkey(), PauseState::PAYOUTS), &[]).await.unwrap();
+    let state: PauseState = env.read(&pause_state, DepinAccountType::PauseState).await;
+    assert_eq!(state, PauseState { flags: PauseState::PAYOUTS, pending_flags: 0, unpause_at: 0 });
+
+    env.process(activate, &[&owner]).await.unwrap();
+}
@@ -0,0 +1,237 @@
+mod common;
+
+use common::{
+    assert_instruction_error, bitmap_for_checker,
+    builders::{self, ProofArgs},
+    license_tree::LicenseTree,
+    mock_compression::INVALID_PROOF,
+    TestEnv, CHECKER_COUNT,
+};
+use shared::{
+    features::{rewards::accounts::GlobalRewards, worker::accounts::{WorkerMetadata, WorkerProof}},
+    types::account::DepinAccountType,
+};
+use solana_sdk::{instruction::InstructionError, signer::Signer};
+
+const PERIOD: u16 = 20;
+
+fn proof_args(period: u16, checkers: [u64; 8]) -> ProofArgs {
+    ProofArgs {
+        period,
+        checkers,
+        uptime: 100,
+        latency: 10,
+    }
+}
+
+/// Network with a checker count from PERIOD on, the clock one period later so PERIOD accepts proofs
+async fn network() -> TestEnv {
+    let mut env = TestEnv::start(PERIOD - 1).await;
+    env.init_network().await;
+    env.set_checker_count(PERIOD, CHECKER_COUNT).await;
+    env.warp_to_period(PERIOD + 1).await;
+    env
+}
+
+#[tokio::test]
+async fn activate_worker_creates_metadata_for_the_owner() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    let metadata: WorkerMetadata = env.read(&builders::worker_metadata(&license), DepinAccountType::WorkerMetadata).await;
+    assert_eq!(metadata.owner, owner.pubkey());
+    assert_eq!(metadata.delegated_to, owner.pubkey());
+    assert_eq!(metadata.license, license.asset_id);
+    assert_eq!(metadata.discovery_uri, "https://worker.example");
+    assert!(metadata.suspended_at.is_none());
+}
+
+#[tokio::test]
+async fn activate_worker_rejects_a_stale_root_and_a_foreign_signer() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let owner = env.funded_keypair();
+    let index = tree.mint(owner.pubkey());
+    let stale = tree.license(index);
+
+    // Any later mint moves the root away from the one the license was read at
+    tree.mint(owner.pubkey());
+    env.sync_tree(&tree);
+
+    let instruction = builders::activate_worker(&owner.pubkey(), &stale, &owner.pubkey(), "");
+    assert_instruction_error(env.process(instruction, &[&owner]).await, InstructionError::Custom(INVALID_PROOF));
+
+    let stranger = env.funded_keypair();
+    let instruction = builders::activate_worker(&stranger.pubkey(), &tree.license(index), &stranger.pubkey(), "");
+    assert_instruction_error(env.process(instruction, &[&stranger]).await, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn update_worker_uri_is_reserved_to_the_delegate() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    let delegate = env.funded_keypair();
+    env.process(builders::set_worker_delegate(&owner.pubkey(), &license, &delegate.pubkey()), &[&owner]).await.unwrap();
+
+    let result = env.process(builders::update_worker_uri(&owner.pubkey(), &license, "https://owner.example"), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.process(builders::update_worker_uri(&delegate.pubkey(), &license, "https://delegate.example"), &[&delegate])
+        .await
+        .unwrap();
+
+    let metadata: WorkerMetadata = env.read(&builders::worker_metadata(&license), DepinAccountType::WorkerMetadata).await;
+    assert_eq!(metadata.discovery_uri, "https://delegate.example");
+}
+
+#[tokio::test]
+async fn revoked_delegate_can_no_longer_submit_proofs() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+    let delegate = env.funded_keypair();
+
+    let result = env.process(builders::set_worker_delegate(&delegate.pubkey(), &license, &delegate.pubkey()), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    env.process(builders::set_worker_delegate(&owner.pubkey(), &license, &delegate.pubkey()), &[&owner]).await.unwrap();
+    env.process(builders::revoke_worker_delegate(&owner.pubkey(), &license), &[&owner]).await.unwrap();
+
+    let metadata: WorkerMetadata = env.read(&builders::worker_metadata(&license), DepinAccountType::WorkerMetadata).await;
+    assert_eq!(metadata.delegated_to, owner.pubkey());
+
+    let instruction = builders::submit_worker_proof(&delegate.pubkey(), &license, &proof_args(PERIOD, [0; 8]));
+    assert_instruction_error(env.process(instruction, &[&delegate]).await, InstructionError::MissingRequiredSignature);
+}
+
+#[tokio::test]
+async fn submit_worker_proof_stores_the_proof_and_credits_checkers() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    let checkers = bitmap_for_checker(&license, PERIOD, CHECKER_COUNT, 3);
+    env.process(builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(PERIOD, checkers)), &[&owner])
+        .await
+        .unwrap();
+
+    let proof: WorkerProof = env.read(&builders::worker_proof(&license.asset_id, PERIOD), DepinAccountType::WorkerProof).await;
+    assert_eq!(proof.period, PERIOD);
+    assert_eq!(proof.checkers, checkers);
+    assert_eq!((proof.uptime, proof.latency), (100, 10));
+
+    let reward = GlobalRewards::get_checker_reward(PERIOD);
+    assert_eq!(env.checker_balance(3).await, reward);
+    assert_eq!(env.checker_balance(4).await, 0);
+
+    // One proof per period, a replay is rejected
+    let result = env.process(builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(PERIOD, checkers)), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::AccountAlreadyInitialized);
+    assert_eq!(env.checker_balance(3).await, reward);
+}
+
+#[tokio::test]
+async fn submit_worker_proof_only_accepts_the_previous_period() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    for period in [PERIOD + 1, PERIOD - 1] {
+        let result = env.process(builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(period, [0; 8])), &[&owner]).await;
+        assert_instruction_error(result, InstructionError::InvalidArgument);
+    }
+
+    // The window closes once the next period starts
+    env.warp_to_period(PERIOD + 2).await;
+    let result = env.process(builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(PERIOD, [0; 8])), &[&owner]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+}
+
+#[tokio::test]
+async fn submit_worker_proof_has_no_previous_period_in_period_zero() {
+    let mut env = TestEnv::start(0).await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    for period in [0, u16::MAX] {
+        let result = env.process(builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(period, [0; 8])), &[&owner]).await;
+        assert_instruction_error(result, InstructionError::InvalidArgument);
+    }
+}
+
+#[tokio::test]
+async fn submit_worker_proof_requires_the_delegate_signature() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let (owner, license) = env.activate_worker(&mut tree).await;
+
+    let mut instruction = builders::submit_worker_proof(&owner.pubkey(), &license, &proof_args(PERIOD, [0; 8]));
+    instruction.accounts[0].is_signer = false;
+    assert_instruction_error(env.process(instruction, &[]).await, InstructionError::MissingRequiredSignature);
+}
+
+#[tokio::test]
+async fn submit_worker_proof_batch_credits_every_entry() {
+    let mut env = network().await;
+    let mut tree = LicenseTree::new();
+    let delegate = env.funded_keypair();
+
+    let (first_owner, _) = env.activate_worker(&mut tree).await;
+    let (second_owner, _) = env.activate_worker(&mut tree).await;
+    let first = tree.license(0);
+    let second = tree.license(1);
+
+    let result = env.process(builders::submit_worker_proof_batch(&delegate.pubkey(), PERIOD, &[(&first, [0; 8])]), &[&delegate]).await;
+    assert_instruction_error(result, InstructionError::MissingRequiredSignature);
+
+    env.process(builders::set_worker_delegate(&first_owner.pubkey(), &first, &delegate.pubkey()), &[&first_owner]).await.unwrap();
+    env.process(builders::set_worker_delegate(&second_owner.pubkey(), &second, &delegate.pubkey()), &[&second_owner]).await.unwrap();
+
+    let entries = [
+        (&first, bitmap_for_checker(&first, PERIOD, CHECKER_COUNT, 7)),
+        (&second, bitmap_for_checker(&second, PERIOD, CHECKER_COUNT, 7)),
+    ];
+    env.process(builders::submit_worker_proof_batch(&delegate.pubkey(), PERIOD, &entries), &[&delegate]).await.unwrap();
+
+    for license in [&first, &second] {
+        let proof: WorkerProof = env.read(&builders::worker_proof(&license.asset_id, PERIOD), DepinAccountType::WorkerProof).await;
+        assert_eq!(proof.period, PERIOD);
+    }
+    assert_eq!(env.checker_balance(7).await, 2 * GlobalRewards::get_checker_reward(PERIOD));
+}
+
+#[tokio::test]
+async fn migrate_worker_metadata_moves_it_to_the_new_owner() {
+    let mut env = TestEnv::start(PERIOD).await;
+    let mut tree = LicenseTree::new();
+    let (previous_owner, license) = env.activate_worker(&mut tree).await;
+    let delegate = env.funded_keypair();
+    env.process(builders::set_worker_delegate(&previous_owner.pubkey(), &license, &delegate.pubkey()), &[&previous_owner])
+        .await
+        .unwrap();
+
+    // Nothing to migrate while the owner is unchanged
+    let result = env.process(builders::migrate_worker_metadata(&env.payer().pubkey(), &license, &previous_owner.pubkey()), &[]).await;
+    assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let new_owner = env.funded_keypair();
+    tree.transfer(license.index, new_owner.pubkey());
+    env.sync_tree(&tree);
+    let transferred = tree.license(license.index);
+
+    env.process(builders::migrate_worker_metadata(&new_owner.pubkey(), &transferred, &previous_owner.pubkey()), &[&new_owner])
+        .await
+        .unwrap();
+
+    let (stale, _) = WorkerMetadata::find_pda(&depin::ID, &license.asset_id, &previous_owner.pubkey());
+    assert!(env.account(&stale).await.is_none());
+
+    let metadata: WorkerMetadata = env.read(&builders::worker_metadata(&transferred), DepinAccountType::WorkerMetadata).await;
+    assert_eq!(metadata.owner, new_owner.pubkey());
+    assert_eq!(metadata.delegated_to, new_owner.pubkey());
+    assert_eq!(metadata.discovery_uri, "https://worker.example");
+}
@@ -6,8 +6,11 @@ export const USDC_MINT = address('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v')
 export const BMB_MINT = address('bmbWbZjP3qCT3TuG7b2ph4ZMDarHLVeGCgk9Xp9vLDw');
 export const ESCROW_SEED = "escrow";
 export const TOKEN_SEED = "token";
+export const AUTHORITY_SEED = "authority";
 export const TREASURY_SEED = "treasury";
 export const CONFIG_SEED = "config";
+export const MINTS_SEED = "mints";
+export const PAUSE_SEED = "pause";
 export const GLOBAL_SEED = "global";
 export const STATE_SEED = "state";
 export const GLOBAL_REWARDS_SEED = "rewards";
@@ -17,4 +20,9 @@ export const CHECKER_SEED = "checker";
 export const METADATA_SEED = "meta";
 export const LICENSE_SEED = "license";
 export const LOCK_SEED = "lock";
+export const INFRA_SEED = "infra";
+export const OFFER_SEED = "offer";
+export const LEASE_SEED = "lease";
+export const VAULT_SEED = "vault";
+export const STREAM_SEED = "stream";
 export const SYSTEM_PROGRAM_ADDRESS = address('11111111111111111111111111111111');
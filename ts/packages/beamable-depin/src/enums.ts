@@ -6,7 +6,38 @@ export enum DepinInstruction {
     ActivateChecker = 6,
     Unlock = 7,
     PayoutCheckerRewards = 8,
-    UpdateWorkerUri = 9
+    UpdateWorkerUri = 9,
+    SubmitWorkerProofBatch = 10,
+    PayoutCheckerRewardsBatch = 11,
+    MigrateWorkerMetadata = 12,
+    MigrateCheckerMetadata = 13,
+    SetWorkerDelegate = 14,
+    RevokeWorkerDelegate = 15,
+    SetCheckerDelegate = 16,
+    RevokeCheckerDelegate = 17,
+    RegisterInfraOffer = 18,
+    UpdateInfraOffer = 19,
+    SetInfraOfferStatus = 20,
+    CloseInfraOffer = 21,
+    EscrowDeposit = 22,
+    EscrowWithdraw = 23,
+    OpenLease = 24,
+    ClaimLeasePayment = 25,
+    CancelLease = 26,
+    EnforceLeaseSla = 27,
+    OpenPaymentStream = 28,
+    CrankPaymentStream = 29,
+    ClosePaymentStream = 30,
+    SetAllowedMint = 31,
+    RemoveAllowedMint = 32,
+    AddEscrowDelegate = 33,
+    RemoveEscrowDelegate = 34,
+    EscrowWithdrawalNotice = 35,
+    MigrateAccount = 36,
+    SetPauseFlags = 37,
+    SetCheckerRewardBeneficiary = 38,
+    SetWorkerRewardBeneficiary = 39,
+    SetInstantPayoutHaircut = 40
 }
 
 export enum DepinAccountType {
@@ -19,5 +50,12 @@ export enum DepinAccountType {
     CheckerLicenseMetadata = 7,
     TreasuryState = 8,
     LockedTokens = 9,
-    TreasuryConfig = 10
+    TreasuryConfig = 10,
+    InfraOffer = 11,
+    Lease = 12,
+    PaymentStream = 13,
+    AllowedMints = 14,
+    EscrowAuthority = 15,
+    EscrowState = 16,
+    PauseState = 17
 }
@@ -10,6 +10,7 @@ import {
 import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
 import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "../global/pause-state-account.js";
 import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
 import { CheckerMetadataAccount } from "./checker-metadata-account.js";
 
@@ -55,6 +56,7 @@ export class ActivateChecker {
             address(this.params.license_context.owner)
         );
         
+        const pauseStatePda = await PauseStateAccount.findPDA();
         let accounts = [
             { address: this.signer, role: AccountRole.READONLY_SIGNER },
             { address: checkerMetadataPda[0], role: AccountRole.WRITABLE },
@@ -64,7 +66,8 @@ export class ActivateChecker {
             ...this.checker_license.proof.map(proof => ({
                 address: address(proof),
                 role: AccountRole.READONLY
-            }))
+            })),
+            { address: pauseStatePda[0], role: AccountRole.READONLY },
         ];
         
         return {
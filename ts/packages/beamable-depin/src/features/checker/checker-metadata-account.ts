@@ -1,28 +1,47 @@
-import { Address, Base58EncodedBytes, Codec, getAddressCodec, getAddressEncoder, getBase58Codec, getOptionCodec, getProgramDerivedAddress, getStructCodec, getU64Codec, Option, ProgramDerivedAddress } from "gill";
+import { Address, Base58EncodedBytes, Codec, getAddressCodec, getAddressEncoder, getBase58Codec, getOptionCodec, getProgramDerivedAddress, getStructCodec, getU64Codec, isSome, none, Option, ProgramDerivedAddress } from "gill";
 import { CHECKER_SEED, DEPIN_PROGRAM, METADATA_SEED } from "../../constants.js";
 import { DepinAccountType } from "../../enums.js";
+import { expectAccountHeader, VERSIONED_FLAG } from "../../types/account-header.js";
 
 const addressEncoder = getAddressEncoder();
 
 export class CheckerMetadataAccount {
     suspendedAt: Option<bigint>;
     delegatedTo: Address;
+    /** Wallet that payouts are locked for instead of the license owner */
+    rewardBeneficiary: Option<Address>;
 
     constructor(fields: {
         suspendedAt: Option<bigint>;
         delegatedTo: Address;
+        rewardBeneficiary?: Option<Address>;
     }) {
         this.suspendedAt = fields.suspendedAt;
         this.delegatedTo = fields.delegatedTo;
+        this.rewardBeneficiary = fields.rewardBeneficiary ?? none();
     }
 
-    public static LEN = 1 + 9 + 32;
+    /** Layout version written by the program */
+    public static VERSION = 1;
+
+    public static LEN = 2 + 9 + 32 + 33;
 
     public static readonly DataCodecV1: Codec<CheckerMetadataAccount> = getStructCodec([
         ["suspendedAt", getOptionCodec(getU64Codec())],
         ["delegatedTo", getAddressCodec()]
     ]);
 
+    public static readonly DataCodecV2: Codec<CheckerMetadataAccount> = getStructCodec([
+        ["suspendedAt", getOptionCodec(getU64Codec())],
+        ["delegatedTo", getAddressCodec()],
+        ["rewardBeneficiary", getOptionCodec(getAddressCodec())]
+    ]);
+
+    /** Wallet the payouts of a checker owned by `owner` are locked for */
+    public static rewardRecipient(metadata: CheckerMetadataAccount, owner: Address): Address {
+        return isSome(metadata.rewardBeneficiary) ? metadata.rewardBeneficiary.value : owner;
+    }
+
     public static deserializeFrom(accountData: ArrayLike<number>): CheckerMetadataAccount;
     public static deserializeFrom(accountDataBase58: Base58EncodedBytes): CheckerMetadataAccount;
     public static deserializeFrom(accountData: ArrayLike<number> | Base58EncodedBytes): CheckerMetadataAccount {
@@ -34,14 +53,13 @@ export class CheckerMetadataAccount {
             accountDataBuffer = accountData;
         }
 
-        const accountDiscriminator = accountDataBuffer[0];
-        if (accountDiscriminator !== DepinAccountType.CheckerMetadata) {
-            throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
+        const header = expectAccountHeader(accountDataBuffer, DepinAccountType.CheckerMetadata, this.VERSION);
+        const data = Buffer.from(accountDataBuffer).subarray(header.bodyOffset);
+        // Version 0 accounts predate the reward beneficiary and pay the owner
+        if (header.version === 0) {
+            return { ...this.DataCodecV1.decode(data), rewardBeneficiary: none() };
         }
-
-        const data = Buffer.from(accountDataBuffer).subarray(1); // Skip the first byte (discriminator)
-        const result = this.DataCodecV1.decode(data);
-        return result;
+        return this.DataCodecV2.decode(data);
     }
 
     public static async findCheckerMetadataPDA(checkerLicense: Address, checker: Address): Promise<ProgramDerivedAddress> {
@@ -53,16 +71,18 @@ export class CheckerMetadataAccount {
     }
 
     /**
-     * Serialize a CheckerMetadataAccount payload into account bytes, including discriminator.
+     * Serialize a CheckerMetadataAccount payload into account bytes at the current version, including the header.
      */
     public static serialize(fields: {
         suspendedAt: Option<bigint>;
         delegatedTo: Address;
+        rewardBeneficiary?: Option<Address>;
     }): Uint8Array {
-        const inner = this.DataCodecV1.encode(fields);
-        const out = new Uint8Array(1 + inner.length);
-        out[0] = DepinAccountType.CheckerMetadata;
-        out.set(inner, 1);
+        const inner = this.DataCodecV2.encode({ ...fields, rewardBeneficiary: fields.rewardBeneficiary ?? none() });
+        const out = new Uint8Array(2 + inner.length);
+        out[0] = DepinAccountType.CheckerMetadata | VERSIONED_FLAG;
+        out[1] = this.VERSION;
+        out.set(inner, 2);
         return out;
     }
 }
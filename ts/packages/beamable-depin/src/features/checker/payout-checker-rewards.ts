@@ -3,14 +3,18 @@ import {
     Address,
     address,
     Codec,
-    getStructCodec
+    getStructCodec,
+    getU16Codec,
+    getU8Codec
 } from "gill";
 
 import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
-import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { findAssociatedTokenPda, TOKEN_PROGRAM_ADDRESS } from "@solana-program/token";
+import { BMB_MINT, DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "../global/pause-state-account.js";
 import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
-import { getCurrentPeriod } from "../../utils/bmb.js";
+import { daysToPeriods, getCurrentPeriod } from "../../utils/bmb.js";
 import { GlobalRewardsAccount } from "../global/global-rewards-account.js";
 import { LockedTokensAccount } from "../treasury/locked-tokens-account.js";
 import { TreasuryAuthority } from "../treasury/treasury-authority.js";
@@ -19,30 +23,60 @@ import { TreasuryStateAccount } from "../treasury/treasury-state-account.js";
 import { CheckerLicenseMetadataAccount } from "./checker-license-metadata-account.js";
 import { CheckerMetadataAccount } from "./checker-metadata-account.js";
 
+export enum PayoutMode {
+    /** Into LockedTokens of the recipient for `checkerRewardsLockDays` */
+    Locked = 0,
+    /** Straight to the recipient's BMB token account, less `instantPayoutHaircutBps` of TreasuryConfig */
+    Instant = 1
+}
+
 export interface PayoutCheckerRewardsParams {
     license_context: CNftContext;
+    mode: PayoutMode;
+    max_haircut_bps: number;
 }
 
 export const PayoutCheckerRewardsParamsCodec: Codec<PayoutCheckerRewardsParams> = getStructCodec([
     ["license_context", CNftContextCodec],
+    ["mode", getU8Codec()],
+    ["max_haircut_bps", getU16Codec()],
 ]);
 
 export interface CreatePayoutCheckerRewardsInput {
     signer: Address;
     checker_license: AssetWithProof;
+    /** Reward beneficiary of the checker, see `CheckerMetadataAccount.rewardRecipient`. Defaults to the license owner */
+    recipient?: Address;
+    /** Defaults to `PayoutMode.Locked` */
+    mode?: PayoutMode;
+    /** Highest haircut an instant payout accepts, the payout fails if TreasuryConfig holds more when it executes */
+    max_haircut_bps?: number;
+    /** Token account of an instant payout, defaults to the recipient's BMB associated token account */
+    recipient_token_account?: Address;
 }
 
 export class PayoutCheckerRewards {
     signer: Address;
     readonly checker_license: AssetWithProof;
+    readonly recipient: Address;
+    readonly recipient_token_account?: Address;
     readonly params: PayoutCheckerRewardsParams;
 
     constructor(input: CreatePayoutCheckerRewardsInput) {
+        const mode = input.mode ?? PayoutMode.Locked;
+        if (mode === PayoutMode.Instant && input.max_haircut_bps === undefined) {
+            throw new Error("Instant payouts need max_haircut_bps");
+        }
+
         this.params = {
             license_context: assetToCNftContext(input.checker_license),
+            mode,
+            max_haircut_bps: input.max_haircut_bps ?? 0,
         };
 
         this.checker_license = input.checker_license;
+        this.recipient = input.recipient ?? address(this.params.license_context.owner);
+        this.recipient_token_account = input.recipient_token_account;
         this.signer = input.signer;
     }
 
@@ -62,19 +96,31 @@ export class PayoutCheckerRewards {
         );
         const treasuryStatePda = await TreasuryStateAccount.findTreasuryStatePDA();
         const treasuryAtaPda = await TreasuryAuthority.findAssociatedTokenAccount();
+        const treasuryAuthorityPda = await TreasuryAuthority.findTreasuryPDA();
 
-        if (!currentPeriod) {
-            currentPeriod = getCurrentPeriod();
+        let destination: Address;
+        if (this.params.mode === PayoutMode.Instant) {
+            destination = this.recipient_token_account ?? (await findAssociatedTokenPda({
+                mint: BMB_MINT,
+                owner: this.recipient,
+                tokenProgram: TOKEN_PROGRAM_ADDRESS,
+            }))[0];
+        } else {
+            if (!currentPeriod) {
+                currentPeriod = getCurrentPeriod();
+            }
+            console.log("PayoutCheckerRewards.getInstruction currentPeriod", currentPeriod);
+            // Read lock duration from provided TreasuryConfig account
+            const lockDays = treasuryConfig.data.checkerRewardsLockDays;
+            const lockedTokensPda = await LockedTokensAccount.findLockedTokensPDA(
+                this.recipient,
+                currentPeriod,
+                currentPeriod + daysToPeriods(lockDays)
+            );
+            destination = lockedTokensPda[0];
         }
-        console.log("PayoutCheckerRewards.getInstruction currentPeriod", currentPeriod);
-        // Read lock duration from provided TreasuryConfig account
-        const lockDays = treasuryConfig.data.checkerRewardsLockDays;
-        const lockedTokensPda = await LockedTokensAccount.findLockedTokensPDA(
-            this.params.license_context.owner,
-            currentPeriod,
-            currentPeriod + lockDays
-        );
 
+        const pauseStatePda = await PauseStateAccount.findPDA();
         let accounts = [
             { address: this.signer, role: AccountRole.READONLY_SIGNER },
             { address: globalRewardsPda[0], role: AccountRole.WRITABLE },
@@ -86,11 +132,14 @@ export class PayoutCheckerRewards {
             { address: treasuryStatePda[0], role: AccountRole.WRITABLE },
             { address: treasuryAtaPda[0], role: AccountRole.WRITABLE },
             { address: treasuryConfig.address, role: AccountRole.READONLY },
-            { address: lockedTokensPda[0], role: AccountRole.WRITABLE },
+            { address: destination, role: AccountRole.WRITABLE },
+            { address: treasuryAuthorityPda[0], role: AccountRole.READONLY },
+            { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
             ...this.checker_license.proof.map(proof => ({
                 address: address(proof),
                 role: AccountRole.READONLY
-            }))
+            })),
+            { address: pauseStatePda[0], role: AccountRole.READONLY },
         ];
 
         return {
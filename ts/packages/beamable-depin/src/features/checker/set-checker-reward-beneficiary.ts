@@ -0,0 +1,68 @@
+import {
+    AccountRole,
+    Address,
+    Codec,
+    getAddressCodec,
+    getOptionCodec,
+    getStructCodec,
+    none,
+    Option,
+    some
+} from "gill";
+
+import { DEPIN_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
+import { CheckerMetadataAccount } from "./checker-metadata-account.js";
+
+export interface SetCheckerRewardBeneficiaryParams {
+    license: Address;
+    beneficiary: Option<Address>;
+}
+
+export const SetCheckerRewardBeneficiaryParamsCodec: Codec<SetCheckerRewardBeneficiaryParams> = getStructCodec([
+    ["license", getAddressCodec()],
+    ["beneficiary", getOptionCodec(getAddressCodec())],
+]);
+
+export interface CreateSetCheckerRewardBeneficiaryInput {
+    /** Checker license owner */
+    signer: Address;
+    license: Address;
+    /** Wallet that checker payouts are locked for, omit it to pay the owner again */
+    beneficiary?: Address;
+}
+
+export class SetCheckerRewardBeneficiary {
+    signer: Address;
+    readonly params: SetCheckerRewardBeneficiaryParams;
+
+    constructor(input: CreateSetCheckerRewardBeneficiaryInput) {
+        this.params = {
+            license: input.license,
+            beneficiary: input.beneficiary ? some(input.beneficiary) : none(),
+        };
+
+        this.signer = input.signer;
+    }
+
+    private serialize(): Uint8Array {
+        const inner = SetCheckerRewardBeneficiaryParamsCodec.encode(this.params);
+        return Uint8Array.of(DepinInstruction.SetCheckerRewardBeneficiary, ...inner);
+    }
+
+    public async getInstruction() {
+        const checkerMetadataPda = await CheckerMetadataAccount.findCheckerMetadataPDA(this.params.license, this.signer);
+
+        let accounts = [
+            { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+            { address: checkerMetadataPda[0], role: AccountRole.WRITABLE },
+            { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+        ];
+
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: accounts,
+            data: this.serialize(),
+        };
+    }
+}
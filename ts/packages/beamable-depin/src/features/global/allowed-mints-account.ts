@@ -0,0 +1,11 @@
+import { getProgramDerivedAddress, ProgramDerivedAddress } from "gill";
+import { DEPIN_PROGRAM, GLOBAL_SEED, MINTS_SEED } from "../../constants.js";
+
+/** Mints the escrow accepts, offers are priced in one of them */
+export async function findAllowedMintsPDA(): Promise<ProgramDerivedAddress> {
+    const pda = await getProgramDerivedAddress({
+        programAddress: DEPIN_PROGRAM,
+        seeds: [GLOBAL_SEED, MINTS_SEED]
+    });
+    return pda;
+}
@@ -0,0 +1,73 @@
+import { Base58EncodedBytes, Codec, getBase58Codec, getI64Codec, getProgramDerivedAddress, getStructCodec, getU8Codec, ProgramDerivedAddress } from "gill";
+import { DEPIN_PROGRAM, GLOBAL_SEED, PAUSE_SEED } from "../../constants.js";
+import { DepinAccountType } from "../../enums.js";
+
+/** Instruction groups a PauseState flag stops */
+export enum PauseFlag {
+    Proofs = 1 << 0,
+    Payouts = 1 << 1,
+    Unlocks = 1 << 2,
+    Escrow = 1 << 3,
+    Activation = 1 << 4,
+}
+
+interface PauseStateAccountData {
+    flags: number;
+    pendingFlags: number;
+    unpauseAt: bigint;
+}
+
+export class PauseStateAccount implements PauseStateAccountData {
+    flags: number;
+    pendingFlags: number;
+    unpauseAt: bigint;
+
+    constructor(fields: PauseStateAccountData) {
+        this.flags = fields.flags;
+        this.pendingFlags = fields.pendingFlags;
+        this.unpauseAt = fields.unpauseAt;
+    }
+
+    public static calculateAccountSize(): number {
+        return 1 + 1 + 1 + 8; // discriminator + flags + pendingFlags + unpauseAt (i64)
+    }
+
+    public static readonly DataCodecV1: Codec<PauseStateAccountData> = getStructCodec([
+        ["flags", getU8Codec()],
+        ["pendingFlags", getU8Codec()],
+        ["unpauseAt", getI64Codec()],
+    ]);
+
+    public isPaused(flag: PauseFlag): boolean {
+        return (this.flags & flag) !== 0;
+    }
+
+    public static deserializeFrom(accountData: ArrayLike<number>): PauseStateAccount;
+    public static deserializeFrom(accountDataBase58: Base58EncodedBytes): PauseStateAccount;
+    public static deserializeFrom(accountData: ArrayLike<number> | Base58EncodedBytes): PauseStateAccount {
+        let accountDataBuffer: ArrayLike<number>;
+
+        if (typeof accountData === 'string') {
+            accountDataBuffer = getBase58Codec().encode(accountData);
+        } else {
+            accountDataBuffer = accountData;
+        }
+
+        const accountDiscriminator = accountDataBuffer[0];
+        if (accountDiscriminator !== DepinAccountType.PauseState) {
+            throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
+        }
+
+        const data = Buffer.from(accountDataBuffer).subarray(1);
+        return new PauseStateAccount(this.DataCodecV1.decode(data));
+    }
+
+    /** Guarded instructions pass this PDA as their last account, whether or not it exists yet */
+    public static async findPDA(): Promise<ProgramDerivedAddress> {
+        const pda = await getProgramDerivedAddress({
+            programAddress: DEPIN_PROGRAM,
+            seeds: [GLOBAL_SEED, PAUSE_SEED]
+        });
+        return pda;
+    }
+}
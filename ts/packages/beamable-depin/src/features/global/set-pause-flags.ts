@@ -0,0 +1,59 @@
+import {
+    AccountRole,
+    Address,
+    Codec,
+    getStructCodec,
+    getU8Codec
+} from "gill";
+
+import { DEPIN_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "./pause-state-account.js";
+
+export interface SetPauseFlagsParams {
+    flags: number;
+}
+
+export const SetPauseFlagsParamsCodec: Codec<SetPauseFlagsParams> = getStructCodec([
+    ["flags", getU8Codec()],
+]);
+
+export interface CreateSetPauseFlagsInput {
+    signer: Address;
+    /** Full set of PauseFlag bits, clearing a bit is scheduled and applied by repeating the call after the delay */
+    flags: number;
+}
+
+export class SetPauseFlags {
+    signer: Address;
+    readonly params: SetPauseFlagsParams;
+
+    constructor(input: CreateSetPauseFlagsInput) {
+        this.params = {
+            flags: input.flags,
+        };
+
+        this.signer = input.signer;
+    }
+
+    private serialize(): Uint8Array {
+        const inner = SetPauseFlagsParamsCodec.encode(this.params);
+        return Uint8Array.of(DepinInstruction.SetPauseFlags, ...inner);
+    }
+
+    public async getInstruction() {
+        const pauseStatePda = await PauseStateAccount.findPDA();
+
+        let accounts = [
+            { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+            { address: pauseStatePda[0], role: AccountRole.WRITABLE },
+            { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+        ];
+
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: accounts,
+            data: this.serialize(),
+        };
+    }
+}
@@ -1,37 +1,35 @@
-// import type { TransactionSigner } from "gill";
-// import { AccountRole } from "gill";
+import { AccountRole, Address } from "gill";
 
-// import { DEPIN_PROGRAM } from "../../constants.js";
-// import { DepinInstruction } from "../../enums.js";
+import { DEPIN_PROGRAM } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
 
-// export interface GetCloseInfraOfferInstructionParams {
-//   payer: TransactionSigner;
-//   offerAccount: TransactionSigner;
-// }
+export interface CreateCloseInfraOfferInput {
+    signer: Address;
+    offer: Address;
+}
 
-// export class CloseInfraOfferInput {
-//   constructor() { }
+export class CloseInfraOffer {
+    readonly signer: Address;
+    readonly offer: Address;
 
-//   serialize(): Uint8Array {
-//     // Only the instruction discriminator
-//     return Uint8Array.of(DepinInstruction.CloseInfraOffer);
-//   }
+    constructor(input: CreateCloseInfraOfferInput) {
+        this.signer = input.signer;
+        this.offer = input.offer;
+    }
 
-//   getCloseInfraOfferInstruction(
-//     params: GetCloseInfraOfferInstructionParams) {
-//     const closeInfraOfferIx = {
-//       programAddress: DEPIN_PROGRAM,
-//       accounts: [
-//         {
-//           address: params.payer.address,
-//           role: AccountRole.READONLY_SIGNER,
-//           signer: params.payer,
-//         },
-//         { address: params.offerAccount, role: AccountRole.WRITABLE },
-//       ] as const,
-//       data: this.serialize(),
-//     };
+    private serialize(): Uint8Array {
+        // Only the instruction discriminator
+        return Uint8Array.of(DepinInstruction.CloseInfraOffer);
+    }
 
-//     return closeInfraOfferIx;
-//   }
-// }
+    public getInstruction() {
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: [
+                { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+                { address: this.offer, role: AccountRole.WRITABLE },
+            ],
+            data: this.serialize(),
+        };
+    }
+}
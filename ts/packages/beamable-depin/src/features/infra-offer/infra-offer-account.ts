@@ -1,69 +1,132 @@
-// import { addCodecSizePrefix, Address, Base58EncodedBytes, Codec, getAddressCodec, getBase58Codec, getBooleanCodec, getStructCodec, getU32Codec, getU64Codec, getUtf8Codec } from 'gill';
-// import { DepinAccountType } from './../../enums.js';
-// import { RegisterInfraOfferInput } from './register-infra-offer-input.js';
+import { addCodecSizePrefix, Address, Base58EncodedBytes, Codec, getAddressCodec, getAddressEncoder, getBase58Codec, getOptionCodec, getProgramDerivedAddress, getStructCodec, getU16Codec, getU16Encoder, getU32Codec, getU64Codec, getU8Codec, getUtf8Codec, Option, ProgramDerivedAddress } from 'gill';
+import { DEPIN_PROGRAM, INFRA_SEED, OFFER_SEED } from '../../constants.js';
+import { DepinAccountType } from '../../enums.js';
 
-// export class InfraOfferAccount {
-//     provider: Address;
-//     name: string;
-//     description: string;
-//     cpu: bigint;
-//     memory: bigint;
-//     price: bigint;
-//     is_active: boolean;
+const addressEncoder = getAddressEncoder();
+const stringCodec = addCodecSizePrefix(getUtf8Codec(), getU32Codec());
 
-//     constructor(fields: { provider: Address; name: string; description: string; cpu: bigint; memory: bigint; price: bigint; is_active: boolean; }) {
-//         this.provider = fields.provider;
-//         this.name = fields.name;
-//         this.description = fields.description;
-//         this.cpu = fields.cpu;
-//         this.memory = fields.memory;
-//         this.price = fields.price;
-//         this.is_active = fields.is_active;
-//     }
+export enum InfraOfferStatus {
+    Active = 0,
+    Paused = 1
+}
 
-//     public static calculateAccountSize(input: RegisterInfraOfferInput): bigint {
-//         const utf8EncodedName = Buffer.from(input.name, 'utf8');
-//         const utf8EncodedDescription = Buffer.from(input.description, 'utf8');
-//         return BigInt(
-//             1 + // discriminator
-//             32 + // provider (address)
-//             8 + // cpu (u64)
-//             8 + // memory (u64)
-//             8 + // price (u64)
-//             1 + // is_active (u8)
-//             4 + utf8EncodedName.length + // name (string with length prefix)
-//             4 + utf8EncodedDescription.length // description (string with length prefix)
-//         );
-//     }
+export interface SlaTerms {
+    min_uptime: number;
+    max_latency: number;
+    penalty_bps: number;
+}
 
-//     public static readonly DataCodec: Codec<InfraOfferAccount> = getStructCodec([
-//         ["provider", getAddressCodec()],
-//         ["cpu", getU64Codec()],
-//         ["memory", getU64Codec()],
-//         ["price", getU64Codec()],
-//         ["is_active", getBooleanCodec()],
-//         ["name", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
-//         ["description", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
-//     ]);
+export const SlaTermsCodec: Codec<SlaTerms> = getStructCodec([
+    ["min_uptime", getU32Codec()],
+    ["max_latency", getU32Codec()],
+    ["penalty_bps", getU16Codec()],
+]);
 
-//     public static deserializeFrom(accountData: ArrayLike<number>): InfraOfferAccount;
-//     public static deserializeFrom(accountDataBase58: Base58EncodedBytes): InfraOfferAccount;
-//     public static deserializeFrom(accountData: ArrayLike<number> | Base58EncodedBytes): InfraOfferAccount {
-//         let accountDataBuffer: ArrayLike<number>;
-    
-//         if (typeof accountData === 'string') {
-//             accountDataBuffer = getBase58Codec().encode(accountData);
-//         } else {
-//             accountDataBuffer = accountData;
-//         }
-    
-//         const accountDiscriminator = accountDataBuffer[0];
-//         if (accountDiscriminator !== DepinAccountType.InfraOffer) {
-//             throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
-//         }
-    
-//         const data = Buffer.from(accountDataBuffer).subarray(1); // Skip the first byte (discriminator)
-//         const result = this.DataCodec.decode(data);
-//         return result;
-//     }
-// }
\ No newline at end of file
+export class InfraOfferAccount {
+    provider: Address;
+    workerLicense: Address;
+    offerId: number;
+    status: InfraOfferStatus;
+    cpu: bigint;
+    memory: bigint;
+    region: string;
+    priceMint: Address;
+    pricePerPeriod: bigint;
+    name: string;
+    description: string;
+    sla: Option<SlaTerms>;
+
+    constructor(fields: {
+        provider: Address;
+        workerLicense: Address;
+        offerId: number;
+        status: InfraOfferStatus;
+        cpu: bigint;
+        memory: bigint;
+        region: string;
+        priceMint: Address;
+        pricePerPeriod: bigint;
+        name: string;
+        description: string;
+        sla: Option<SlaTerms>;
+    }) {
+        this.provider = fields.provider;
+        this.workerLicense = fields.workerLicense;
+        this.offerId = fields.offerId;
+        this.status = fields.status;
+        this.cpu = fields.cpu;
+        this.memory = fields.memory;
+        this.region = fields.region;
+        this.priceMint = fields.priceMint;
+        this.pricePerPeriod = fields.pricePerPeriod;
+        this.name = fields.name;
+        this.description = fields.description;
+        this.sla = fields.sla;
+    }
+
+    public static readonly MAX_OFFERS_PER_LICENSE = 16;
+
+    // Sized for the longest strings, matches InfraOffer::LEN on chain
+    public static calculateAccountSize(): bigint {
+        return BigInt(
+            1 + // discriminator
+            32 + // provider (address)
+            32 + // workerLicense (address)
+            2 + // offerId (u16)
+            1 + // status (enum)
+            8 + // cpu (u64)
+            8 + // memory (u64)
+            4 + 16 + // region (string with length prefix)
+            32 + // priceMint (address)
+            8 + // pricePerPeriod (u64)
+            4 + 32 + // name (string with length prefix)
+            4 + 128 + // description (string with length prefix)
+            1 + 4 + 4 + 2 // sla (Option<SlaTerms>)
+        );
+    }
+
+    public static readonly DataCodecV1: Codec<InfraOfferAccount> = getStructCodec([
+        ["provider", getAddressCodec()],
+        ["workerLicense", getAddressCodec()],
+        ["offerId", getU16Codec()],
+        ["status", getU8Codec()],
+        ["cpu", getU64Codec()],
+        ["memory", getU64Codec()],
+        ["region", stringCodec],
+        ["priceMint", getAddressCodec()],
+        ["pricePerPeriod", getU64Codec()],
+        ["name", stringCodec],
+        ["description", stringCodec],
+        ["sla", getOptionCodec(SlaTermsCodec)],
+    ]);
+
+    public static deserializeFrom(accountData: ArrayLike<number>): InfraOfferAccount;
+    public static deserializeFrom(accountDataBase58: Base58EncodedBytes): InfraOfferAccount;
+    public static deserializeFrom(accountData: ArrayLike<number> | Base58EncodedBytes): InfraOfferAccount {
+        let accountDataBuffer: ArrayLike<number>;
+
+        if (typeof accountData === 'string') {
+            accountDataBuffer = getBase58Codec().encode(accountData);
+        } else {
+            accountDataBuffer = accountData;
+        }
+
+        const accountDiscriminator = accountDataBuffer[0];
+        if (accountDiscriminator !== DepinAccountType.InfraOffer) {
+            throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
+        }
+
+        const data = Buffer.from(accountDataBuffer).subarray(1); // Skip the first byte (discriminator)
+        // The account is zero padded after the strings, read only the encoded fields
+        const [result] = this.DataCodecV1.read(data, 0);
+        return result;
+    }
+
+    public static async findInfraOfferPDA(workerLicense: Address, offerId: number): Promise<ProgramDerivedAddress> {
+        const pda = await getProgramDerivedAddress({
+            programAddress: DEPIN_PROGRAM,
+            seeds: [INFRA_SEED, OFFER_SEED, addressEncoder.encode(workerLicense), getU16Encoder().encode(offerId)]
+        });
+        return pda;
+    }
+}
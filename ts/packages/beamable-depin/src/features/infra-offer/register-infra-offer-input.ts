@@ -1,66 +1,124 @@
-// import {
-//     AccountRole,
-//     addCodecSizePrefix,
-//     Codec,
-//     getStructCodec,
-//     getU32Codec,
-//     getU64Codec,
-//     getUtf8Codec,
-//     type TransactionSigner
-// } from "gill";
+import {
+    AccountRole,
+    addCodecSizePrefix,
+    address,
+    Address,
+    Codec,
+    getAddressCodec,
+    getOptionCodec,
+    getStructCodec,
+    getU16Codec,
+    getU32Codec,
+    getU64Codec,
+    getUtf8Codec,
+    none,
+    Option,
+    some
+} from "gill";
 
-// import { DEPIN_PROGRAM } from "../../constants.js";
-// import { DepinInstruction } from "../../enums.js";
+import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
+import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
+import { findAllowedMintsPDA } from "../global/allowed-mints-account.js";
+import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
+import { findWorkerLicenseMetadataPDA } from "../worker/worker-license-metadata-account.js";
+import { WorkerMetadataAccount } from "../worker/worker-metadata-account.js";
+import { InfraOfferAccount, SlaTerms, SlaTermsCodec } from "./infra-offer-account.js";
 
-// export interface RegisterInfraOfferParams {
-//     name: string;
-//     description: string;
-//     cpu: bigint;
-//     memory: bigint;
-//     price: bigint;
-// }
+export interface RegisterInfraOfferParams {
+    license_context: CNftContext;
+    offer_id: number;
+    cpu: bigint;
+    memory: bigint;
+    region: string;
+    price_mint: Address;
+    price_per_period: bigint;
+    name: string;
+    description: string;
+    sla: Option<SlaTerms>;
+}
 
-// export interface GetRegisterOfferInstructionParams {
-//     payer: TransactionSigner;
-//     offerAccount: TransactionSigner;
-// }
+export const RegisterInfraOfferParamsCodec: Codec<RegisterInfraOfferParams> = getStructCodec([
+    ["license_context", CNftContextCodec],
+    ["offer_id", getU16Codec()],
+    ["cpu", getU64Codec()],
+    ["memory", getU64Codec()],
+    ["region", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
+    ["price_mint", getAddressCodec()],
+    ["price_per_period", getU64Codec()],
+    ["name", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
+    ["description", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
+    ["sla", getOptionCodec(SlaTermsCodec)],
+]);
 
-// export const RegisterInfraOfferParamsCodec: Codec<RegisterInfraOfferParams> = getStructCodec([
-//     ["cpu", getU64Codec()],
-//     ["memory", getU64Codec()],
-//     ["price", getU64Codec()],
-//     ["name", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
-//     ["description", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
-// ]);
+export interface CreateRegisterInfraOfferInput {
+    signer: Address;
+    worker_license: AssetWithProof;
+    offer_id: number;
+    cpu: bigint;
+    memory: bigint;
+    region: string;
+    price_mint: Address;
+    price_per_period: bigint;
+    name: string;
+    description: string;
+    sla?: SlaTerms;
+}
 
-// export class RegisterInfraOfferInput {
-//     readonly name: string;
-//     readonly description: string;
-//     readonly cpu: bigint;
-//     readonly memory: bigint;
-//     readonly price: bigint;
+export class RegisterInfraOffer {
+    signer: Address;
+    readonly worker_license: AssetWithProof;
+    readonly params: RegisterInfraOfferParams;
 
-//     constructor(params: RegisterInfraOfferParams) {
-//         this.name = params.name;
-//         this.description = params.description;
-//         this.cpu = params.cpu;
-//         this.memory = params.memory;
-//         this.price = params.price;
-//     }
-    
-//     private serialize(): Uint8Array {
-//         const inner = RegisterInfraOfferParamsCodec.encode(this);
-//         return Uint8Array.of(DepinInstruction.RegisterInfraOffer, ...inner);
-//     }    
+    constructor(input: CreateRegisterInfraOfferInput) {
+        this.params = {
+            license_context: assetToCNftContext(input.worker_license),
+            offer_id: input.offer_id,
+            cpu: input.cpu,
+            memory: input.memory,
+            region: input.region,
+            price_mint: input.price_mint,
+            price_per_period: input.price_per_period,
+            name: input.name,
+            description: input.description,
+            sla: input.sla ? some(input.sla) : none(),
+        };
 
-//     public getRegisterOfferInstruction(params: GetRegisterOfferInstructionParams) {
-//         return {
-//             programAddress: DEPIN_PROGRAM,
-//             accounts: [
-//                 { address: params.payer.address, role: AccountRole.READONLY_SIGNER },
-//                 { address: params.offerAccount.address, role: AccountRole.WRITABLE },
-//             ],
-//             data: this.serialize(),
-//         };
-//     }
-// }
+        this.worker_license = input.worker_license;
+        this.signer = input.signer;
+    }
+
+    private serialize(): Uint8Array {
+        const inner = RegisterInfraOfferParamsCodec.encode(this.params);
+        return Uint8Array.of(DepinInstruction.RegisterInfraOffer, ...inner);
+    }
+
+    public async getInstruction() {
+        const workerLicense = address(this.worker_license.rpcAsset.id);
+        const offerPda = await InfraOfferAccount.findInfraOfferPDA(workerLicense, this.params.offer_id);
+        const workerMetadataPda = await WorkerMetadataAccount.findWorkerMetadataPDA(workerLicense, this.signer);
+        const workerLicenseMetadataPda = await findWorkerLicenseMetadataPDA(workerLicense);
+        const allowedMintsPda = await findAllowedMintsPDA();
+
+        let accounts = [
+            { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+            { address: offerPda[0], role: AccountRole.WRITABLE },
+            { address: workerMetadataPda[0], role: AccountRole.READONLY },
+            { address: workerLicenseMetadataPda[0], role: AccountRole.READONLY },
+            { address: MPL_ACCOUNT_COMPRESSION_PROGRAM, role: AccountRole.READONLY },
+            { address: address(this.worker_license.merkleTree), role: AccountRole.READONLY },
+            { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+            { address: allowedMintsPda[0], role: AccountRole.READONLY },
+            ...this.worker_license.proof.map(proof => ({
+                address: address(proof),
+                role: AccountRole.READONLY
+            }))
+        ];
+
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: accounts,
+            data: this.serialize(),
+        };
+    }
+}
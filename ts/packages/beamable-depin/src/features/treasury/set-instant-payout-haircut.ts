@@ -0,0 +1,59 @@
+import {
+    AccountRole,
+    Address,
+    Codec,
+    getStructCodec,
+    getU16Codec
+} from "gill";
+
+import { DEPIN_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
+import { TreasuryConfigAccount } from "./treasury-config-account.js";
+
+export interface SetInstantPayoutHaircutParams {
+    haircut_bps: number;
+}
+
+export const SetInstantPayoutHaircutParamsCodec: Codec<SetInstantPayoutHaircutParams> = getStructCodec([
+    ["haircut_bps", getU16Codec()],
+]);
+
+export interface CreateSetInstantPayoutHaircutInput {
+    signer: Address;
+    /** Share of instant checker payouts kept by the treasury, in basis points below 10000 */
+    haircut_bps: number;
+}
+
+export class SetInstantPayoutHaircut {
+    signer: Address;
+    readonly params: SetInstantPayoutHaircutParams;
+
+    constructor(input: CreateSetInstantPayoutHaircutInput) {
+        this.params = {
+            haircut_bps: input.haircut_bps,
+        };
+
+        this.signer = input.signer;
+    }
+
+    private serialize(): Uint8Array {
+        const inner = SetInstantPayoutHaircutParamsCodec.encode(this.params);
+        return Uint8Array.of(DepinInstruction.SetInstantPayoutHaircut, ...inner);
+    }
+
+    public async getInstruction() {
+        const treasuryConfigPda = await TreasuryConfigAccount.findTreasuryConfigPDA();
+
+        let accounts = [
+            { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+            { address: treasuryConfigPda[0], role: AccountRole.WRITABLE },
+            { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+        ];
+
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: accounts,
+            data: this.serialize(),
+        };
+    }
+}
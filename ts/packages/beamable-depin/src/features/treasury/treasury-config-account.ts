@@ -1,27 +1,52 @@
 import { Address, Base58EncodedBytes, Codec, ProgramDerivedAddress, getBase58Codec, getProgramDerivedAddress, getStructCodec, getU16Codec } from "gill";
 import { CONFIG_SEED, DEPIN_PROGRAM, TREASURY_SEED } from "../../constants.js";
 import { DepinAccountType } from "../../enums.js";
+import { expectAccountHeader, VERSIONED_FLAG } from "../../types/account-header.js";
 
 export class TreasuryConfigAccount {
     checkerRewardsLockDays: number;
+    /** Share of instant checker payouts kept by the treasury, in basis points */
+    instantPayoutHaircutBps: number;
 
-    constructor(fields: { checkerRewardsLockDays: number }) {
+    constructor(fields: { checkerRewardsLockDays: number; instantPayoutHaircutBps?: number }) {
         this.checkerRewardsLockDays = fields.checkerRewardsLockDays;
+        this.instantPayoutHaircutBps = fields.instantPayoutHaircutBps ?? TreasuryConfigAccount.INSTANT_PAYOUTS_DISABLED;
     }
 
+    /** Layout version written by the program */
+    public static VERSION = 1;
+
+    /** Haircut InitNetwork starts with */
+    public static DEFAULT_INSTANT_PAYOUT_HAIRCUT_BPS = 5_000;
+
+    /** Haircut of configs written before it was configurable, instant payouts stay refused until the admin sets one */
+    public static INSTANT_PAYOUTS_DISABLED = 10_000;
+
     public static calculateAccountSize(): number {
-        return 1 + 2; // discriminator + checkerRewardsLockDays (u16)
+        return 2 + 2 + 2; // header + checkerRewardsLockDays (u16) + instantPayoutHaircutBps (u16)
     }
 
-    public static readonly DataCodecV1: Codec<TreasuryConfigAccount> = getStructCodec([
+    public static readonly DataCodecV1: Codec<{ checkerRewardsLockDays: number }> = getStructCodec([
+        ["checkerRewardsLockDays", getU16Codec()],
+    ]);
+
+    public static readonly DataCodecV2: Codec<TreasuryConfigAccount> = getStructCodec([
         ["checkerRewardsLockDays", getU16Codec()],
+        ["instantPayoutHaircutBps", getU16Codec()],
     ]);
 
+    /** Splits `amount` into the instant payout and the haircut kept by the treasury */
+    public static instantPayoutSplit(config: TreasuryConfigAccount, amount: bigint): { payout: bigint; haircut: bigint } {
+        const haircut = amount * BigInt(config.instantPayoutHaircutBps) / 10_000n;
+        return { payout: amount - haircut, haircut };
+    }
+
     public static serialize(account: TreasuryConfigAccount): Uint8Array {
-        const data = this.DataCodecV1.encode(account);
-        const result = new Uint8Array(1 + data.length);
-        result[0] = DepinAccountType.TreasuryConfig;
-        result.set(data, 1);
+        const data = this.DataCodecV2.encode(account);
+        const result = new Uint8Array(2 + data.length);
+        result[0] = DepinAccountType.TreasuryConfig | VERSIONED_FLAG;
+        result[1] = this.VERSION;
+        result.set(data, 2);
         return result;
     }
 
@@ -36,14 +61,13 @@ export class TreasuryConfigAccount {
             accountDataBuffer = accountData;
         }
 
-        const accountDiscriminator = accountDataBuffer[0];
-        if (accountDiscriminator !== DepinAccountType.TreasuryConfig) {
-            throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
+        const header = expectAccountHeader(accountDataBuffer, DepinAccountType.TreasuryConfig, this.VERSION);
+        const data = Buffer.from(accountDataBuffer).subarray(header.bodyOffset);
+        // Version 0 accounts predate the haircut, the program reads them with instant payouts disabled
+        if (header.version === 0) {
+            return new TreasuryConfigAccount(this.DataCodecV1.decode(data));
         }
-
-        const data = Buffer.from(accountDataBuffer).subarray(1);
-        const result = this.DataCodecV1.decode(data);
-        return result;
+        return this.DataCodecV2.decode(data);
     }
 
     public static async findTreasuryConfigPDA(): Promise<ProgramDerivedAddress> {
@@ -11,6 +11,7 @@ import {
 import { TOKEN_PROGRAM_ADDRESS } from "@solana-program/token";
 import { DEPIN_PROGRAM } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "../global/pause-state-account.js";
 import { LockedTokensAccount } from "./locked-tokens-account.js";
 import { TreasuryAuthority } from "./treasury-authority.js";
 import { TreasuryStateAccount } from "./treasury-state-account.js";
@@ -79,6 +80,7 @@ export class Unlock {
             unlockPeriod
         );
 
+        const pauseStatePda = await PauseStateAccount.findPDA();
         let accounts = [
             { address: this.owner, role: AccountRole.READONLY_SIGNER },
             { address: treasuryStatePda[0], role: AccountRole.WRITABLE },
@@ -87,6 +89,7 @@ export class Unlock {
             { address: lockedTokensPda[0], role: AccountRole.WRITABLE },
             { address: this.owner_bmb_token_account, role: AccountRole.WRITABLE },
             { address: TOKEN_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+            { address: pauseStatePda[0], role: AccountRole.READONLY },
         ];
 
         return {
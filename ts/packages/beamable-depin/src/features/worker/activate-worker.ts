@@ -4,28 +4,35 @@ import {
     address,
     Address,
     Codec,
+    getOptionCodec,
     getAddressCodec,
     getStructCodec,
     getU32Codec,
-    getUtf8Codec
+    getUtf8Codec,
+    Option,
+    none,
+    some
 } from "gill";
 
 import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
 import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "../global/pause-state-account.js";
 import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
-import { WorkerMetadataAccount } from "./worker-metadata-account.js";
+import { WorkerDiscovery, WorkerDiscoveryCodec, WorkerMetadataAccount } from "./worker-metadata-account.js";
 
 export interface ActivateWorkerParams {
     license_context: CNftContext;
     delegated_to: Address;
     discovery_uri: string;
+    discovery: Option<WorkerDiscovery>;
 }
 
 export const ActivateWorkerParamsCodec: Codec<ActivateWorkerParams> = getStructCodec([
     ["license_context", CNftContextCodec],
     ["delegated_to", getAddressCodec()],
     ["discovery_uri", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
+    ["discovery", getOptionCodec(WorkerDiscoveryCodec)],
 ]);
 
 export interface CreateActivateWorkerInput {
@@ -33,6 +40,7 @@ export interface CreateActivateWorkerInput {
     worker_license: AssetWithProof;
     delegated_to: Address;
     discovery_uri: string;
+    discovery?: WorkerDiscovery;
 }
 
 export class ActivateWorker {
@@ -45,6 +53,7 @@ export class ActivateWorker {
             license_context: assetToCNftContext(input.worker_license),
             delegated_to: input.delegated_to,
             discovery_uri: input.discovery_uri,
+            discovery: input.discovery ? some(input.discovery) : none(),
         };
 
         this.worker_license = input.worker_license;
@@ -62,6 +71,7 @@ export class ActivateWorker {
             address(this.params.license_context.owner)
         );
         
+        const pauseStatePda = await PauseStateAccount.findPDA();
         let accounts = [
             { address: this.signer, role: AccountRole.READONLY_SIGNER },
             { address: workerMetadataPda[0], role: AccountRole.WRITABLE },
@@ -71,7 +81,8 @@ export class ActivateWorker {
             ...this.worker_license.proof.map(proof => ({
                 address: address(proof),
                 role: AccountRole.READONLY
-            }))
+            })),
+            { address: pauseStatePda[0], role: AccountRole.READONLY },
         ];
         
         return {
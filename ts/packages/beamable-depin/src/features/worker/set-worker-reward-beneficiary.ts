@@ -0,0 +1,68 @@
+import {
+    AccountRole,
+    Address,
+    Codec,
+    getAddressCodec,
+    getOptionCodec,
+    getStructCodec,
+    none,
+    Option,
+    some
+} from "gill";
+
+import { DEPIN_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
+import { DepinInstruction } from "../../enums.js";
+import { WorkerMetadataAccount } from "./worker-metadata-account.js";
+
+export interface SetWorkerRewardBeneficiaryParams {
+    license: Address;
+    beneficiary: Option<Address>;
+}
+
+export const SetWorkerRewardBeneficiaryParamsCodec: Codec<SetWorkerRewardBeneficiaryParams> = getStructCodec([
+    ["license", getAddressCodec()],
+    ["beneficiary", getOptionCodec(getAddressCodec())],
+]);
+
+export interface CreateSetWorkerRewardBeneficiaryInput {
+    /** Worker license owner */
+    signer: Address;
+    license: Address;
+    /** Wallet that payment streams pay, omit it to pay the owner again */
+    beneficiary?: Address;
+}
+
+export class SetWorkerRewardBeneficiary {
+    signer: Address;
+    readonly params: SetWorkerRewardBeneficiaryParams;
+
+    constructor(input: CreateSetWorkerRewardBeneficiaryInput) {
+        this.params = {
+            license: input.license,
+            beneficiary: input.beneficiary ? some(input.beneficiary) : none(),
+        };
+
+        this.signer = input.signer;
+    }
+
+    private serialize(): Uint8Array {
+        const inner = SetWorkerRewardBeneficiaryParamsCodec.encode(this.params);
+        return Uint8Array.of(DepinInstruction.SetWorkerRewardBeneficiary, ...inner);
+    }
+
+    public async getInstruction() {
+        const workerMetadataPda = await WorkerMetadataAccount.findWorkerMetadataPDA(this.params.license, this.signer);
+
+        let accounts = [
+            { address: this.signer, role: AccountRole.WRITABLE_SIGNER },
+            { address: workerMetadataPda[0], role: AccountRole.WRITABLE },
+            { address: SYSTEM_PROGRAM_ADDRESS, role: AccountRole.READONLY },
+        ];
+
+        return {
+            programAddress: DEPIN_PROGRAM,
+            accounts: accounts,
+            data: this.serialize(),
+        };
+    }
+}
@@ -13,6 +13,7 @@ import {
 import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
 import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
+import { PauseStateAccount } from "../global/pause-state-account.js";
 import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
 import { GlobalRewardsAccount } from "../global/global-rewards-account.js";
 import { findWorkerProofPDA } from "./worker-proof-account.js";
@@ -78,6 +79,7 @@ export class SubmitWorkerProof {
         let workerMetadataPda = await WorkerMetadataAccount.findWorkerMetadataPDA(address(this.worker_license.rpcAsset.id), address(this.params.license_context.owner));
         let workerLicenseMetadataPda = await findWorkerLicenseMetadataPDA(address(this.worker_license.rpcAsset.id));
         let bmbStatePda = await BMBStateAccount.findPDA();
+        const pauseStatePda = await PauseStateAccount.findPDA();
         let accounts = [
             { address: this.payer.address, role: AccountRole.READONLY_SIGNER },
             { address: globalRewardsPda[0], role: AccountRole.WRITABLE },
@@ -91,7 +93,8 @@ export class SubmitWorkerProof {
             ...this.worker_license.proof.map(proof => ({
                 address: address(proof),
                 role: AccountRole.READONLY
-            }))
+            })),
+            { address: pauseStatePda[0], role: AccountRole.READONLY },
         ];
         return {
             programAddress: DEPIN_PROGRAM,
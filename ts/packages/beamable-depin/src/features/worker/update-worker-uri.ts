@@ -4,31 +4,38 @@ import {
     address,
     Address,
     Codec,
+    getOptionCodec,
     getStructCodec,
     getU32Codec,
-    getUtf8Codec
+    getUtf8Codec,
+    Option,
+    none,
+    some
 } from "gill";
 
 import { AssetWithProof } from "@metaplex-foundation/mpl-bubblegum";
 import { DEPIN_PROGRAM, MPL_ACCOUNT_COMPRESSION_PROGRAM, SYSTEM_PROGRAM_ADDRESS } from "../../constants.js";
 import { DepinInstruction } from "../../enums.js";
 import { assetToCNftContext, CNftContext, CNftContextCodec } from "../../utils/bubblegum.js";
-import { WorkerMetadataAccount } from "./worker-metadata-account.js";
+import { WorkerDiscovery, WorkerDiscoveryCodec, WorkerMetadataAccount } from "./worker-metadata-account.js";
 
 export interface UpdateWorkerUriParams {
     license_context: CNftContext;
     discovery_uri: string;
+    discovery: Option<WorkerDiscovery>;
 }
 
 export const UpdateWorkerUriParamsCodec: Codec<UpdateWorkerUriParams> = getStructCodec([
     ["license_context", CNftContextCodec],
     ["discovery_uri", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
+    ["discovery", getOptionCodec(WorkerDiscoveryCodec)],
 ]);
 
 export interface CreateUpdateWorkerUriInput {
     signer: Address;
     worker_license: AssetWithProof;
     discovery_uri: string;
+    discovery?: WorkerDiscovery;
 }
 
 export class UpdateWorkerUri {
@@ -40,6 +47,7 @@ export class UpdateWorkerUri {
         this.params = {
             license_context: assetToCNftContext(input.worker_license),
             discovery_uri: input.discovery_uri,
+            discovery: input.discovery ? some(input.discovery) : none(),
         };
 
         this.worker_license = input.worker_license;
@@ -1,8 +1,26 @@
-import { addCodecSizePrefix, Address, Base58EncodedBytes, Codec, getAddressCodec, getAddressEncoder, getBase58Codec, getOptionCodec, getProgramDerivedAddress, getStructCodec, getU32Codec, getU64Codec, getUtf8Codec, Option, ProgramDerivedAddress } from "gill";
+import { addCodecSizePrefix, Address, Base58EncodedBytes, Codec, getAddressCodec, getAddressEncoder, getArrayCodec, getBase58Codec, getOptionCodec, getProgramDerivedAddress, getStructCodec, getU16Codec, getU32Codec, getU64Codec, getUtf8Codec, isSome, none, Option, ProgramDerivedAddress } from "gill";
 import { DEPIN_PROGRAM, METADATA_SEED, WORKER_SEED } from "../../constants.js";
 import { DepinAccountType } from "../../enums.js";
+import { expectAccountHeader } from "../../types/account-header.js";
 
 const addressEncoder = getAddressEncoder();
+const stringCodec = addCodecSizePrefix(getUtf8Codec(), getU32Codec());
+
+export interface WorkerDiscovery {
+    region: string;
+    runtimes: string[];
+    capacity_slots: number;
+    protocol_version: number;
+    endpoint: string;
+}
+
+export const WorkerDiscoveryCodec: Codec<WorkerDiscovery> = getStructCodec([
+    ["region", stringCodec],
+    ["runtimes", getArrayCodec(stringCodec, { size: getU32Codec() })],
+    ["capacity_slots", getU16Codec()],
+    ["protocol_version", getU16Codec()],
+    ["endpoint", stringCodec],
+]);
 
 export class WorkerMetadataAccount {
     suspendedAt: Option<bigint>;
@@ -10,27 +28,44 @@ export class WorkerMetadataAccount {
     license: Address;
     owner: Address;
     discoveryUri: string;
+    discovery: Option<WorkerDiscovery>;
+    /** Wallet that payment streams pay instead of the owner */
+    rewardBeneficiary: Option<Address>;
 
     constructor(fields: {
         suspendedAt: Option<bigint>;
         delegatedTo: Address;
         license: Address;
         discoveryUri: string;
+        discovery?: Option<WorkerDiscovery>;
+        rewardBeneficiary?: Option<Address>;
     }) {
         this.suspendedAt = fields.suspendedAt;
         this.delegatedTo = fields.delegatedTo;
         this.license = fields.license;
         this.discoveryUri = fields.discoveryUri;
+        this.discovery = fields.discovery ?? none();
+        this.rewardBeneficiary = fields.rewardBeneficiary ?? none();
+    }
+
+    /** Layout version written by the program */
+    public static VERSION = 1;
+
+    /** Wallet whose token account receives the worker's payment stream payments */
+    public static rewardRecipient(metadata: WorkerMetadataAccount): Address {
+        return isSome(metadata.rewardBeneficiary) ? metadata.rewardBeneficiary.value : metadata.owner;
     }
 
-    public static calculateAccountSize(discoveryUriLength: number): bigint {
+    public static calculateAccountSize(discoveryUriLength: number, discovery?: WorkerDiscovery): bigint {
         return BigInt(
-            1 + // discriminator
+            2 + // account type and version
             1 + 8 + // suspendedAt (Option<u64>)
             32 + // delegatedTo (address)
             32 + // license (address)
             32 + // owner (address)
-            4 + discoveryUriLength // discoveryUri (String with length prefix)
+            4 + discoveryUriLength + // discoveryUri (String with length prefix)
+            1 + (discovery ? WorkerDiscoveryCodec.getSizeFromValue(discovery) : 0) + // discovery (Option<WorkerDiscovery>)
+            1 + 32 // rewardBeneficiary (Option<Address>)
         );
     }
 
@@ -42,6 +77,25 @@ export class WorkerMetadataAccount {
         ["discoveryUri", addCodecSizePrefix(getUtf8Codec(), getU32Codec())],
     ]);
 
+    public static readonly DataCodecV2: Codec<WorkerMetadataAccount> = getStructCodec([
+        ["suspendedAt", getOptionCodec(getU64Codec())],
+        ["delegatedTo", getAddressCodec()],
+        ["license", getAddressCodec()],
+        ["owner", getAddressCodec()],
+        ["discoveryUri", stringCodec],
+        ["discovery", getOptionCodec(WorkerDiscoveryCodec)],
+    ]);
+
+    public static readonly DataCodecV3: Codec<WorkerMetadataAccount> = getStructCodec([
+        ["suspendedAt", getOptionCodec(getU64Codec())],
+        ["delegatedTo", getAddressCodec()],
+        ["license", getAddressCodec()],
+        ["owner", getAddressCodec()],
+        ["discoveryUri", stringCodec],
+        ["discovery", getOptionCodec(WorkerDiscoveryCodec)],
+        ["rewardBeneficiary", getOptionCodec(getAddressCodec())],
+    ]);
+
     public static deserializeFrom(accountData: ArrayLike<number>): WorkerMetadataAccount;
     public static deserializeFrom(accountDataBase58: Base58EncodedBytes): WorkerMetadataAccount;
     public static deserializeFrom(accountData: ArrayLike<number> | Base58EncodedBytes): WorkerMetadataAccount {
@@ -53,14 +107,18 @@ export class WorkerMetadataAccount {
             accountDataBuffer = accountData;
         }
 
-        const accountDiscriminator = accountDataBuffer[0];
-        if (accountDiscriminator !== DepinAccountType.WorkerMetadata) {
-            throw new Error(`Invalid discriminator: ${accountDiscriminator}`);
+        const header = expectAccountHeader(accountDataBuffer, DepinAccountType.WorkerMetadata, this.VERSION);
+        const data = Buffer.from(accountDataBuffer).subarray(header.bodyOffset);
+        if (header.version > 0) {
+            return this.DataCodecV3.decode(data);
         }
 
-        const data = Buffer.from(accountDataBuffer).subarray(1); // Skip the first byte (discriminator)
-        const result = this.DataCodecV1.decode(data);
-        return result;
+        // Version 0 accounts pay the owner, those written before the discovery record existed end right after the URI
+        const v1 = this.DataCodecV1.read(data, 0);
+        if (v1[1] >= data.length) {
+            return { ...v1[0], discovery: none(), rewardBeneficiary: none() };
+        }
+        return { ...this.DataCodecV2.decode(data), rewardBeneficiary: none() };
     }
 
     public static async findWorkerMetadataPDA(workerLicense: Address, worker: Address): Promise<ProgramDerivedAddress> {
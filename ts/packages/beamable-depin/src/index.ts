@@ -16,17 +16,26 @@ export { TreasuryStateAccount } from './features/treasury/treasury-state-account
 export { TreasuryConfigAccount } from './features/treasury/treasury-config-account.js';
 export { BMBStateAccount } from './features/global/bmb-state-account.js';
 export { ActivateCheckerLicenses } from './features/global/activate-checker-licenses.js';
+export { PauseFlag, PauseStateAccount } from './features/global/pause-state-account.js';
+export { SetPauseFlags } from './features/global/set-pause-flags.js';
+export { findAllowedMintsPDA } from './features/global/allowed-mints-account.js';
+export { SetInstantPayoutHaircut } from './features/treasury/set-instant-payout-haircut.js';
 export { ActivateWorker } from './features/worker/activate-worker.js';
 export { UpdateWorkerUri } from './features/worker/update-worker-uri.js';
+export { SetWorkerRewardBeneficiary } from './features/worker/set-worker-reward-beneficiary.js';
 export { ActivateChecker } from './features/checker/activate-checker.js';
-export { PayoutCheckerRewards } from './features/checker/payout-checker-rewards.js';
+export { PayoutCheckerRewards, PayoutMode } from './features/checker/payout-checker-rewards.js';
+export { SetCheckerRewardBeneficiary } from './features/checker/set-checker-reward-beneficiary.js';
 export { LockedTokensAccount } from './features/treasury/locked-tokens-account.js';
+export { RegisterInfraOffer } from './features/infra-offer/register-infra-offer-input.js';
+export { CloseInfraOffer } from './features/infra-offer/close-infra-offer-input.js';
 export { assetToCNftContext } from './utils/bubblegum.js';
 
 export * from './features/worker/worker-metadata-account.js';
 export * from './features/checker/checker-metadata-account.js';
 export * from './features/worker/worker-proof-account.js';
 export * from './features/checker/checker-license-metadata-account.js';
+export * from './features/infra-offer/infra-offer-account.js';
 export * from './features/treasury/unlock.js';
 
 export * from './utils/brand.js';
@@ -0,0 +1,31 @@
+import { DepinAccountType } from "../enums.js";
+
+/** Set on the type byte of accounts written at version 1 or later, the version follows in the next byte */
+export const VERSIONED_FLAG = 0x80;
+
+export interface AccountHeader {
+    accountType: number;
+    version: number;
+    /** Offset of the account body, right after the header */
+    bodyOffset: number;
+}
+
+export function readAccountHeader(accountData: ArrayLike<number>): AccountHeader {
+    const first = accountData[0];
+    if ((first & VERSIONED_FLAG) === 0) {
+        return { accountType: first, version: 0, bodyOffset: 1 };
+    }
+    return { accountType: first & ~VERSIONED_FLAG, version: accountData[1], bodyOffset: 2 };
+}
+
+/** Header of an account of `expected` type, throws for any other type or a version newer than `maxVersion` */
+export function expectAccountHeader(accountData: ArrayLike<number>, expected: DepinAccountType, maxVersion: number): AccountHeader {
+    const header = readAccountHeader(accountData);
+    if (header.accountType !== expected) {
+        throw new Error(`Invalid discriminator: ${header.accountType}`);
+    }
+    if (header.version > maxVersion) {
+        throw new Error(`Unsupported ${DepinAccountType[expected]} version ${header.version}`);
+    }
+    return header;
+}
@@ -1,2 +1,3 @@
 export * from './ring-buffer-64.js';
-export * from './program-account.js';
\ No newline at end of file
+export * from './program-account.js';
+export * from './account-header.js';
\ No newline at end of file
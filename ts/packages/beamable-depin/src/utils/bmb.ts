@@ -1,4 +1,29 @@
-const PERIOD_ZERO = 1748736000; // 2025-06-01 00:00:00 UTC
+/**
+ * Period clock of the network, must match the DEPIN_PERIOD_ZERO and
+ * DEPIN_PERIOD_SECONDS values the program was built with.
+ */
+export interface PeriodConfig {
+    periodZero: number;
+    secondsPerPeriod: number;
+}
+
+export const DEFAULT_PERIOD_CONFIG: PeriodConfig = {
+    periodZero: 1748736000, // 2025-06-01 00:00:00 UTC
+    secondsPerPeriod: 86400,
+};
+
+let periodConfig: PeriodConfig = { ...DEFAULT_PERIOD_CONFIG };
+
+export function setPeriodConfig(config: PeriodConfig): void {
+    if (!Number.isInteger(config.secondsPerPeriod) || config.secondsPerPeriod <= 0) {
+        throw new Error("secondsPerPeriod must be a positive integer");
+    }
+    periodConfig = { ...config };
+}
+
+export function getPeriodConfig(): PeriodConfig {
+    return { ...periodConfig };
+}
 
 export function getCurrentPeriod(): number {
     const now = Math.floor(Date.now() / 1000); // Current Unix timestamp in seconds
@@ -14,24 +39,30 @@ export function periodToTimestamp(period: number): bigint {
         throw new Error("Period exceeds u16::MAX");
     }
 
-    // Convert period (days) back to Unix timestamp
-    const secondsFromStart = period * 86400; // 86400 seconds in a day
-    return BigInt(PERIOD_ZERO + secondsFromStart);
+    // Convert period back to Unix timestamp
+    const secondsFromStart = period * periodConfig.secondsPerPeriod;
+    return BigInt(periodConfig.periodZero + secondsFromStart);
 }
 
 export function timestampToPeriod(timestamp: bigint): number {
-    if (timestamp < PERIOD_ZERO) {
+    const periodZero = BigInt(periodConfig.periodZero);
+    if (timestamp < periodZero) {
         return 0;
     }
 
-    const secondsSinceStart = timestamp - BigInt(PERIOD_ZERO);
-    const daysSinceStart = Math.floor(Number(secondsSinceStart / BigInt(86400)));
+    const secondsSinceStart = timestamp - periodZero;
+    const periodsSinceStart = Number(secondsSinceStart / BigInt(periodConfig.secondsPerPeriod));
 
-    if (daysSinceStart > 65535) { // u16::MAX = 65535
+    if (periodsSinceStart > 65535) { // u16::MAX = 65535
         throw new Error("Period exceeds u16::MAX");
     }
 
-    return daysSinceStart;
+    return periodsSinceStart;
+}
+
+/** Number of periods covering `days`, rounded up like the program does for durations set in days */
+export function daysToPeriods(days: number): number {
+    return Math.ceil(days * 86400 / periodConfig.secondsPerPeriod);
 }
 
 /**
@@ -49,7 +80,7 @@ export function getRemainingTimeInPeriodMs(period?: number): number {
 
 /**
  * Returns the epoch milliseconds for the end of the given period.
- * Periods are daily windows starting at 2025-06-01 00:00:00 UTC (PERIOD_ZERO).
+ * Periods are windows of secondsPerPeriod starting at periodZero, daily from 2025-06-01 00:00:00 UTC by default.
  * The end of period N is the start of period N+1.
  */
 export function getPeriodEndMs(period: number): number {
@@ -1,7 +1,7 @@
 import { Address } from 'gill';
 import { beforeEach, describe, expect, it } from 'vitest';
 
-import { GlobalRewardsAccount, LockedTokensAccount, PayoutCheckerRewards, TreasuryConfigAccount, TreasuryStateAccount, getCurrentPeriod } from '@beamable-network/depin';
+import { BMB_MINT, GlobalRewardsAccount, LockedTokensAccount, PayoutCheckerRewards, PayoutMode, TreasuryConfigAccount, TreasuryStateAccount, getCurrentPeriod } from '@beamable-network/depin';
 import { AssetWithProof } from '@metaplex-foundation/mpl-bubblegum';
 import { activateChecker, activateCheckerLicenses, createCheckers, standardNetworkSetup } from '../../helpers/bmb-utils.js';
 import { LiteDepin, LiteKeyPair } from '../../helpers/lite-depin.js';
@@ -53,6 +53,34 @@ describe('Payout checker rewards', async () => {
         await verifyTreasuryState(lite, BigInt(mockedRewards));
     });
 
+    it('should pay out instantly less the haircut without locking', async () => {
+        const mockedRewards = 5_000;
+        const checkerIndex = checkerLicense.index;
+        await setMockedRewardsInGlobalRewards(lite, checkerIndex, mockedRewards);
+
+        // Create the owner's BMB ATA, the instant payout goes there
+        await lite.mintToken(BMB_MINT, checkerOwner.address, 0n, authority);
+
+        const payout = new PayoutCheckerRewards({
+            signer: checkerOwner.address,
+            checker_license: checkerLicense,
+            mode: PayoutMode.Instant,
+            max_haircut_bps: 5_000,
+        });
+
+        const cfg = await getTreasuryConfig(lite);
+        await lite.buildTransaction()
+            .addInstruction(await payout.getInstruction(cfg))
+            .sendTransaction({ payer: checkerOwner });
+
+        const { payout: expected } = TreasuryConfigAccount.instantPayoutSplit(cfg.data, BigInt(mockedRewards));
+        expect(expected).toBe(2_500n);
+        expect(await lite.getTokenBalance(BMB_MINT, checkerOwner.address)).toBe(expected);
+        await verifyNoLockedTokensForAddress(lite, checkerOwner.address, getCurrentPeriod());
+        await verifyGlobalRewardsReset(lite, checkerIndex);
+        await verifyTreasuryState(lite, 0n);
+    });
+
     it('should fail when trying to payout with zero rewards', async () => {
         const payout = new PayoutCheckerRewards({
             signer: checkerOwner.address,
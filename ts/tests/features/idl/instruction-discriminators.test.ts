@@ -0,0 +1,22 @@
+import { readFileSync } from 'node:fs';
+import { describe, expect, it } from 'vitest';
+
+import { DepinInstruction } from '@beamable-network/depin';
+
+const idl = JSON.parse(readFileSync(new URL('../../../../rust/idl/depin.json', import.meta.url), 'utf8'));
+
+describe('IDL drift', () => {
+    it('should list the same instruction discriminators as the SDK', () => {
+        const fromIdl = Object.fromEntries(
+            idl.instructions.map((instruction: { name: string; discriminant: { value: number } }) => [
+                instruction.name.charAt(0).toUpperCase() + instruction.name.slice(1),
+                instruction.discriminant.value
+            ])
+        );
+        const fromSdk = Object.fromEntries(
+            Object.entries(DepinInstruction).filter(([, value]) => typeof value === 'number')
+        );
+
+        expect(fromSdk).toEqual(fromIdl);
+    });
+});
@@ -95,4 +95,5 @@ async function verifyTreasuryConfigAccount(lite: LiteDepin): Promise<void> {
 
     const treasuryConfig = TreasuryConfigAccount.deserializeFrom(treasuryConfigData!);
     expect(treasuryConfig.checkerRewardsLockDays).toBe(365);
+    expect(treasuryConfig.instantPayoutHaircutBps).toBe(TreasuryConfigAccount.DEFAULT_INSTANT_PAYOUT_HAIRCUT_BPS);
 }